@@ -1,12 +1,57 @@
 use anyhow::{Context, Result};
-use shared::{ServerError, WorkRequest, WorkResult, PROTOCOL_VERSION};
-use std::time::{Duration, Instant};
+use serde::Serialize;
+use shared::auth::{sign_request, KEY_ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use shared::{ServerError, SpectatorFrame, WorkRequest, WorkResult, PROTOCOL_VERSION};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
+/// Frames waiting to be sent; newer frames are dropped while the server is slow
+const FRAME_QUEUE: usize = 2;
+
+/// API key used to sign requests when the server requires authentication
+pub struct Credentials {
+    pub key_id: String,
+    pub secret: String,
+}
+
+/// Where and how often to publish island frames while simulating
+pub struct Spectator {
+    pub interval: Duration,
+    frames: mpsc::Sender<SpectatorFrame>,
+}
+
+impl Spectator {
+    /// Start a background task sending frames for `client` to the server
+    pub fn start(client: Arc<Client>, interval: Duration) -> Self {
+        let (frames, mut receiver) = mpsc::channel(FRAME_QUEUE);
+        tokio::spawn(async move {
+            while let Some(frame) = receiver.recv().await {
+                if let Err(e) = client.send_frame(&frame).await {
+                    tracing::debug!("Failed to send spectator frame: {}", e);
+                }
+            }
+        });
+        Self { interval, frames }
+    }
+
+    /// Queue a frame unless the previous ones haven't been sent yet
+    fn publish(&self, frame: SpectatorFrame) {
+        let _ = self.frames.try_send(frame);
+    }
+}
+
 pub struct Client {
     client_id: Uuid,
     server_url: String,
     http_client: reqwest::Client,
+
+    /// Experiment to request work from (server assigns one if None)
+    experiment: Option<String>,
+
+    /// Requests are signed with this key if set
+    credentials: Option<Credentials>,
 }
 
 impl Client {
@@ -23,24 +68,64 @@ impl Client {
             client_id,
             server_url: server_url.to_string(),
             http_client,
+            experiment: None,
+            credentials: None,
         }
     }
 
+    /// Only request work from the named experiment
+    pub fn with_experiment(mut self, experiment: Option<String>) -> Self {
+        self.experiment = experiment;
+        self
+    }
+
+    /// Sign every request with an API key
+    pub fn with_credentials(mut self, credentials: Option<Credentials>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// POST a JSON body, signed with the client's API key if it has one
+    async fn post_json<T: Serialize>(&self, path: &str, body: &T) -> Result<reqwest::Response> {
+        let body = serde_json::to_vec(body)?;
+        let mut request = self
+            .http_client
+            .post(format!("{}{}", self.server_url, path))
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+        if let Some(credentials) = &self.credentials {
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let signature = sign_request(&credentials.secret, timestamp, "POST", path, &body);
+            request = request
+                .header(KEY_ID_HEADER, &credentials.key_id)
+                .header(TIMESTAMP_HEADER, timestamp)
+                .header(SIGNATURE_HEADER, signature);
+        }
+
+        Ok(request.body(body).send().await?)
+    }
+
     /// Request work from the server
     pub async fn request_work(&self) -> Result<shared::WorkAssignment> {
-        let url = format!("{}/api/work/request", self.server_url);
-        let request = WorkRequest::new(self.client_id, PROTOCOL_VERSION);
+        let mut request = WorkRequest::new(self.client_id, PROTOCOL_VERSION);
+        request.experiment = self.experiment.clone();
 
         tracing::debug!("Requesting work from server");
 
         let response = self
-            .http_client
-            .post(&url)
-            .json(&request)
-            .send()
+            .post_json("/api/work/request", &request)
             .await
             .context("Failed to send work request")?;
 
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            || response.status() == reqwest::StatusCode::FORBIDDEN
+        {
+            anyhow::bail!(
+                "Server rejected credentials ({}) - set API_KEY_ID and API_KEY_SECRET",
+                response.status()
+            );
+        }
+
         if !response.status().is_success() {
             let status = response.status();
             // Try to parse error response
@@ -82,27 +167,45 @@ impl Client {
 
     /// Submit work results to the server
     pub async fn submit_results(&self, result: WorkResult) -> Result<()> {
-        let url = format!("{}/api/work/submit", self.server_url);
-
         tracing::debug!("Submitting work results");
 
         let response = self
-            .http_client
-            .post(&url)
-            .json(&result)
-            .send()
+            .post_json("/api/work/submit", &result)
             .await
             .context("Failed to send work results")?;
 
         if !response.status().is_success() {
-            anyhow::bail!("Submit failed with status: {}", response.status());
+            let status = response.status();
+            // Implausible results are rejected with a reason
+            if let Ok(ServerError::InvalidRequest(reason)) = response.json::<ServerError>().await {
+                anyhow::bail!("Result rejected: {}", reason);
+            }
+            anyhow::bail!("Submit failed with status: {}", status);
         }
 
         Ok(())
     }
 
-    /// Run a work assignment
-    pub fn process_work(&self, assignment: shared::WorkAssignment) -> Result<WorkResult> {
+    /// Send a frame of a running island to the server for spectators
+    pub async fn send_frame(&self, frame: &SpectatorFrame) -> Result<()> {
+        let response = self
+            .post_json("/api/spectate/frames", frame)
+            .await
+            .context("Failed to send spectator frame")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Frame rejected with status: {}", response.status());
+        }
+
+        Ok(())
+    }
+
+    /// Run a work assignment, publishing frames of spatial simulations if spectated
+    pub fn process_work(
+        &self,
+        assignment: shared::WorkAssignment,
+        spectator: Option<&Spectator>,
+    ) -> Result<WorkResult> {
         // Check if this is a spatial simulation (Version 2)
         if !assignment.seed_genomes_v2.is_empty() && assignment.max_steps > 0 {
             tracing::info!(
@@ -113,16 +216,8 @@ impl Client {
                 assignment.seed_genomes_v2.len()
             );
 
-            // Create config
-            let config = sim::IslandConfig {
-                world_width: assignment.grid_width,
-                world_height: assignment.grid_height,
-                max_steps: assignment.max_steps,
-                mutation_rate: assignment.mutation_rate,
-                plant_density: 0.05,
-                food_density: 0.02,
-                reproduction_threshold: 100.0,
-            };
+            // Create config, using the experiment's world parameters if given
+            let config = sim::IslandConfig::from_assignment(&assignment);
 
             // Convert GenomeWithId to (Uuid, Genome) tuples
             let seed_genomes: Vec<(uuid::Uuid, shared::Genome)> = assignment
@@ -131,10 +226,24 @@ impl Client {
                 .map(|g| (g.genome_id, g.genome))
                 .collect();
 
-            // Run spatial simulation with Island to also capture best genomes
-            let mut island = sim::Island::new(config, seed_genomes);
-            let survival_stats = island.run_simulation();
-            let best_genomes = island.get_best_genomes(10);
+            // Run the islands, migrating between them, to also capture best genomes
+            let mut archipelago = sim::Archipelago::new(config, assignment.migration, seed_genomes);
+            let survival_stats = match spectator {
+                Some(spectator) => {
+                    let mut last_frame: Option<Instant> = None;
+                    archipelago.run_simulation_observed(|island| {
+                        if last_frame.is_some_and(|t| t.elapsed() < spectator.interval) {
+                            return;
+                        }
+                        last_frame = Some(Instant::now());
+                        let mut frame = island.spectator_frame(assignment.work_id, self.client_id);
+                        frame.experiment = assignment.experiment.clone();
+                        spectator.publish(frame);
+                    })
+                }
+                None => archipelago.run_simulation(),
+            };
+            let best_genomes = archipelago.get_best_genomes(10);
 
             // Convert SurvivalStats to SurvivalResult
             let survival_results = survival_stats
@@ -152,6 +261,7 @@ impl Client {
                 client_id: self.client_id,
                 survival_results,
                 steps_completed: assignment.max_steps,
+                experiment: assignment.experiment,
                 // Legacy fields
                 best_genomes,
                 generations_completed: 0,
@@ -177,6 +287,7 @@ impl Client {
                 client_id: self.client_id,
                 survival_results: vec![],
                 steps_completed: 0,
+                experiment: assignment.experiment,
                 best_genomes,
                 generations_completed: assignment.generations,
                 stats: Some(stats),
@@ -186,14 +297,36 @@ impl Client {
 }
 
 /// Main client loop
-pub async fn run(server_url: &str) -> Result<()> {
-    let client = Client::new(server_url);
+pub async fn run(
+    server_url: &str,
+    experiment: Option<String>,
+    credentials: Option<Credentials>,
+    spectate_interval: Option<Duration>,
+) -> Result<()> {
+    let client = Arc::new(
+        Client::new(server_url)
+            .with_experiment(experiment)
+            .with_credentials(credentials),
+    );
+    let spectator = spectate_interval.map(|interval| Spectator::start(client.clone(), interval));
 
     // Set 1-hour timeout for container lifecycle
     let start_time = Instant::now();
     let timeout = Duration::from_secs(60 * 60); // 1 hour
 
     tracing::info!("Client ID: {}", client.client_id);
+    if let Some(experiment) = &client.experiment {
+        tracing::info!("Requesting work from experiment {}", experiment);
+    }
+    if let Some(credentials) = &client.credentials {
+        tracing::info!("Signing requests with API key {}", credentials.key_id);
+    }
+    if let Some(spectator) = &spectator {
+        tracing::info!(
+            "Streaming island frames to spectators every {:?}",
+            spectator.interval
+        );
+    }
     tracing::info!("Client will run for 1 hour before exiting for restart");
 
     loop {
@@ -218,8 +351,10 @@ pub async fn run(server_url: &str) -> Result<()> {
             }
         };
 
-        // Process work
-        let result = match client.process_work(assignment) {
+        // Process work, letting the frame sender run while this thread simulates
+        let result = match tokio::task::block_in_place(|| {
+            client.process_work(assignment, spectator.as_ref())
+        }) {
             Ok(r) => r,
             Err(e) => {
                 tracing::error!("Failed to process work: {}", e);
@@ -3,9 +3,11 @@ mod tui;
 
 use anyhow::Result;
 use std::env;
+use std::time::Duration;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 const DEFAULT_SERVER_URL: &str = "https://evo-islands.rackspace.koski.co";
+const DEFAULT_SPECTATE_INTERVAL_MS: u64 = 1000;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -21,9 +23,31 @@ async fn main() -> Result<()> {
     // Get server URL from environment or use default
     let server_url = env::var("SERVER_URL").unwrap_or_else(|_| DEFAULT_SERVER_URL.to_string());
 
+    // Optionally pin the client to one experiment
+    let experiment = env::var("EXPERIMENT").ok().filter(|e| !e.is_empty());
+
+    // API key for servers that require worker authentication
+    let credentials = match (env::var("API_KEY_ID"), env::var("API_KEY_SECRET")) {
+        (Ok(key_id), Ok(secret)) if !key_id.is_empty() => {
+            Some(client::Credentials { key_id, secret })
+        }
+        _ => None,
+    };
+
+    // Opt in to streaming frames of running islands to dashboard spectators
+    let spectate_interval = env::var("SPECTATE")
+        .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .then(|| {
+            let millis = env::var("SPECTATE_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_SPECTATE_INTERVAL_MS);
+            Duration::from_millis(millis)
+        });
+
     tracing::info!("Starting EvoIslands client");
     tracing::info!("Server URL: {}", server_url);
 
     // Run the client
-    client::run(&server_url).await
+    client::run(&server_url, experiment, credentials, spectate_interval).await
 }
@@ -0,0 +1,556 @@
+use crate::audit::{unix_millis, AuditRecord};
+use crate::auth::{self, ApiKey, ApiKeyInfo, Principal, Scope};
+use crate::experiment::{Experiment, ExperimentQuery, IslandSettings};
+use crate::server::AppState;
+use crate::validation::ClientTrust;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    routing::{get, post, put},
+    Extension, Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use shared::Genome;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Number of admin actions kept in memory for `GET /api/admin/actions`
+const MAX_TRAIL_LEN: usize = 1000;
+
+/// Population given to injected genomes when none is specified
+const DEFAULT_INJECTED_POPULATION: u32 = 100;
+
+/// A change made to a running experiment through the admin API
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AdminAction {
+    InjectGenome {
+        genome_id: Uuid,
+        genome: Genome,
+        population: u32,
+    },
+    SetPopulation {
+        genome_id: Uuid,
+        old_population: u32,
+        new_population: u32,
+    },
+    UpdateSettings {
+        seed_mutation_rate: f64,
+        island: IslandSettings,
+    },
+    Pause,
+    Resume,
+    Reset,
+    CreateKey {
+        key_id: String,
+        scopes: Vec<Scope>,
+    },
+    RevokeKey {
+        key_id: String,
+    },
+}
+
+/// An admin action as kept in the in-memory trail
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminActionRecord {
+    pub timestamp_ms: u64,
+
+    /// Experiment the action applied to, or None for server-wide actions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<String>,
+
+    /// ID of the API key the action was made with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+
+    #[serde(flatten)]
+    pub action: AdminAction,
+}
+
+/// Trail of recent admin actions
+#[derive(Clone, Default)]
+pub struct AdminState {
+    trail: Arc<Mutex<VecDeque<AdminActionRecord>>>,
+}
+
+impl AdminState {
+    /// Record an action in the trail and in the audit logs of the affected
+    /// experiments (all of them for server-wide actions)
+    async fn record(
+        &self,
+        state: &AppState,
+        experiment: Option<&Experiment>,
+        principal: Option<Extension<Principal>>,
+        action: AdminAction,
+    ) {
+        let timestamp_ms = unix_millis();
+        let actor = principal.map(|Extension(p)| p.key_id);
+        tracing::info!(
+            experiment = experiment.map(|e| tracing::field::display(&e.name)),
+            actor,
+            ?action,
+            "Admin action"
+        );
+
+        let record = AuditRecord::AdminAction {
+            timestamp_ms,
+            actor: actor.clone(),
+            action: action.clone(),
+        };
+        match experiment {
+            Some(experiment) => experiment.gene_pool.audit(&record).await,
+            None => {
+                for experiment in state.experiments.all() {
+                    experiment.gene_pool.audit(&record).await;
+                }
+            }
+        }
+
+        let mut trail = match self.trail.lock() {
+            Ok(trail) => trail,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if trail.len() >= MAX_TRAIL_LEN {
+            trail.pop_front();
+        }
+        trail.push_back(AdminActionRecord {
+            timestamp_ms,
+            experiment: experiment.map(|e| e.name.clone()),
+            actor,
+            action,
+        });
+    }
+}
+
+/// Routes under `/api/admin`, all requiring a key with the admin scope
+pub fn router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/genomes", post(inject_genome))
+        .route("/genomes/:id/population", put(set_population))
+        .route("/settings", get(get_settings).put(update_settings))
+        .route("/pause", post(pause))
+        .route("/resume", post(resume))
+        .route("/reset", post(reset))
+        .route("/actions", get(list_actions))
+        .route("/clients", get(list_clients))
+        .route("/keys", get(list_keys).post(create_key))
+        .route("/keys/:key_id/revoke", post(revoke_key))
+        .route_layer(middleware::from_fn_with_state(state, auth::require_admin))
+}
+
+fn experiment<'a>(
+    state: &'a AppState,
+    scope: &ExperimentQuery,
+) -> Result<&'a Experiment, StatusCode> {
+    state
+        .experiments
+        .get(scope.experiment.as_deref())
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[derive(Debug, Deserialize)]
+struct InjectRequest {
+    genome: Genome,
+    population: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct InjectResponse {
+    genome_id: Uuid,
+}
+
+/// Add a hand-written genome to the pool
+async fn inject_genome(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Query(scope): Query<ExperimentQuery>,
+    Json(request): Json<InjectRequest>,
+) -> Result<Json<InjectResponse>, StatusCode> {
+    let experiment = experiment(&state, &scope)?;
+    // Hand-written genomes are held to the same trait budget as evolved ones,
+    // and must pass the checks results from clients evolving them will face
+    let genome = request.genome.normalized();
+    if !genome.is_valid() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let population = request.population.unwrap_or(DEFAULT_INJECTED_POPULATION);
+
+    let genome_id = experiment
+        .gene_pool
+        .inject_genome(genome.clone(), population)
+        .await;
+    state
+        .admin
+        .record(
+            &state,
+            Some(experiment),
+            principal,
+            AdminAction::InjectGenome {
+                genome_id,
+                genome,
+                population,
+            },
+        )
+        .await;
+
+    Ok(Json(InjectResponse { genome_id }))
+}
+
+#[derive(Debug, Deserialize)]
+struct PopulationRequest {
+    population: u32,
+}
+
+/// Cull or boost a genome by setting its population
+async fn set_population(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(genome_id): Path<Uuid>,
+    Query(scope): Query<ExperimentQuery>,
+    Json(request): Json<PopulationRequest>,
+) -> Result<StatusCode, StatusCode> {
+    let experiment = experiment(&state, &scope)?;
+    let (old_population, new_population) = experiment
+        .gene_pool
+        .set_population(genome_id, request.population)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    state
+        .admin
+        .record(
+            &state,
+            Some(experiment),
+            principal,
+            AdminAction::SetPopulation {
+                genome_id,
+                old_population,
+                new_population,
+            },
+        )
+        .await;
+
+    Ok(StatusCode::OK)
+}
+
+/// Live-tunable parameters of an experiment
+#[derive(Debug, Serialize)]
+struct Settings {
+    /// Mutation applied server-side to seed genomes; `island.mutation_rate`
+    /// is the rate clients use during the simulation
+    seed_mutation_rate: f64,
+    island: IslandSettings,
+    paused: bool,
+}
+
+/// Partial update of the live-tunable parameters
+#[derive(Debug, Deserialize)]
+struct SettingsUpdate {
+    seed_mutation_rate: Option<f64>,
+
+    /// Island fields to change; fields not given keep their current value
+    island: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+async fn settings(experiment: &Experiment) -> Settings {
+    Settings {
+        seed_mutation_rate: experiment.gene_pool.mutation_rate().await,
+        island: experiment.island().await,
+        paused: experiment.is_paused(),
+    }
+}
+
+async fn get_settings(
+    State(state): State<AppState>,
+    Query(scope): Query<ExperimentQuery>,
+) -> Result<Json<Settings>, StatusCode> {
+    let experiment = experiment(&state, &scope)?;
+    Ok(Json(settings(experiment).await))
+}
+
+/// Change the mutation rate and world parameters for new work
+async fn update_settings(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Query(scope): Query<ExperimentQuery>,
+    Json(update): Json<SettingsUpdate>,
+) -> Result<Json<Settings>, (StatusCode, String)> {
+    let experiment =
+        experiment(&state, &scope).map_err(|s| (s, "Unknown experiment".to_string()))?;
+    let invalid = |e: String| (StatusCode::BAD_REQUEST, e);
+
+    let seed_mutation_rate = match update.seed_mutation_rate {
+        Some(rate) if (0.0..=1.0).contains(&rate) => rate,
+        Some(_) => {
+            return Err(invalid(
+                "seed_mutation_rate must be between 0 and 1".to_string(),
+            ))
+        }
+        None => experiment.gene_pool.mutation_rate().await,
+    };
+
+    let mut island = experiment.island().await;
+    if let Some(changes) = update.island {
+        let serde_json::Value::Object(mut current) =
+            serde_json::to_value(island).map_err(|e| invalid(e.to_string()))?
+        else {
+            unreachable!("island settings serialize to an object");
+        };
+        for (key, value) in changes {
+            if !current.contains_key(&key) {
+                return Err(invalid(format!("Unknown island setting: {}", key)));
+            }
+            current.insert(key, value);
+        }
+        island = serde_json::from_value(serde_json::Value::Object(current))
+            .map_err(|e| invalid(e.to_string()))?;
+        island.validate().map_err(invalid)?;
+    }
+
+    experiment
+        .gene_pool
+        .set_mutation_rate(seed_mutation_rate)
+        .await;
+    experiment.set_island(island.clone()).await;
+    state
+        .admin
+        .record(
+            &state,
+            Some(experiment),
+            principal,
+            AdminAction::UpdateSettings {
+                seed_mutation_rate,
+                island,
+            },
+        )
+        .await;
+
+    Ok(Json(settings(experiment).await))
+}
+
+/// Stop handing out work for the experiment
+async fn pause(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Query(scope): Query<ExperimentQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let experiment = experiment(&state, &scope)?;
+    experiment.set_paused(true);
+    state
+        .admin
+        .record(&state, Some(experiment), principal, AdminAction::Pause)
+        .await;
+    Ok(StatusCode::OK)
+}
+
+/// Resume handing out work for the experiment
+async fn resume(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Query(scope): Query<ExperimentQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let experiment = experiment(&state, &scope)?;
+    experiment.set_paused(false);
+    state
+        .admin
+        .record(&state, Some(experiment), principal, AdminAction::Resume)
+        .await;
+    Ok(StatusCode::OK)
+}
+
+/// Discard the gene pool and statistics and start again from random genomes
+async fn reset(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Query(scope): Query<ExperimentQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let experiment = experiment(&state, &scope)?;
+    experiment.gene_pool.reset().await;
+    state
+        .admin
+        .record(&state, Some(experiment), principal, AdminAction::Reset)
+        .await;
+    Ok(StatusCode::OK)
+}
+
+/// Recent admin actions across all experiments, oldest first
+async fn list_actions(State(state): State<AppState>) -> Json<Vec<AdminActionRecord>> {
+    let trail = match state.admin.trail.lock() {
+        Ok(trail) => trail,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    Json(trail.iter().cloned().collect())
+}
+
+/// Trust scores of the clients that have submitted results, least trusted first
+async fn list_clients(State(state): State<AppState>) -> Json<Vec<ClientTrust>> {
+    Json(state.validator.clients())
+}
+
+/// All API keys, without their secrets
+async fn list_keys(State(state): State<AppState>) -> Json<Vec<ApiKeyInfo>> {
+    Json(state.auth.registry.list())
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateKeyRequest {
+    scopes: Vec<Scope>,
+    #[serde(default)]
+    description: String,
+}
+
+/// Create a key. The response is the only time the secret is returned.
+async fn create_key(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Json(request): Json<CreateKeyRequest>,
+) -> Result<Json<ApiKey>, (StatusCode, String)> {
+    if request.scopes.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "At least one scope is required".to_string(),
+        ));
+    }
+    let key = ApiKey::generate(request.scopes, request.description);
+    state.auth.registry.insert(key.clone()).map_err(|e| {
+        tracing::error!(error = %e, "Failed to save key registry");
+        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    })?;
+    state
+        .admin
+        .record(
+            &state,
+            None,
+            principal,
+            AdminAction::CreateKey {
+                key_id: key.key_id.clone(),
+                scopes: key.scopes.clone(),
+            },
+        )
+        .await;
+
+    Ok(Json(key))
+}
+
+/// Revoke a key; requests made with it are rejected from now on
+async fn revoke_key(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(key_id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let revoked = state.auth.registry.revoke(&key_id).map_err(|e| {
+        tracing::error!(error = %e, "Failed to save key registry");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if !revoked {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    state
+        .admin
+        .record(&state, None, principal, AdminAction::RevokeKey { key_id })
+        .await;
+
+    Ok(StatusCode::OK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthConfig, KeyRegistry};
+    use crate::events::EventBus;
+    use crate::experiment::Experiments;
+    use crate::gene_pool::GenePool;
+    use crate::selection::SelectionConfig;
+    use crate::spectate::SpectatorHub;
+    use crate::validation::Validator;
+
+    fn state() -> AppState {
+        AppState {
+            experiments: Experiments::new(vec![Experiment::new(
+                "control".to_string(),
+                1.0,
+                IslandSettings::default(),
+                SelectionConfig::default(),
+                GenePool::new(),
+            )]),
+            admin: AdminState::default(),
+            auth: AuthConfig::new(KeyRegistry::default(), []),
+            validator: Validator::default(),
+            events: EventBus::default(),
+            spectators: SpectatorHub::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_settings_merges_and_records() {
+        let state = state();
+        let update = SettingsUpdate {
+            seed_mutation_rate: Some(0.1),
+            island: Some(
+                serde_json::from_str(r#"{"food_density": 0.01, "max_steps": 500}"#).unwrap(),
+            ),
+        };
+        let Json(settings) = update_settings(
+            State(state.clone()),
+            None,
+            Query(ExperimentQuery::default()),
+            Json(update),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(settings.seed_mutation_rate, 0.1);
+        assert_eq!(settings.island.food_density, 0.01);
+        assert_eq!(settings.island.max_steps, 500);
+        assert_eq!(settings.island.world_width, 300);
+
+        let bad = SettingsUpdate {
+            seed_mutation_rate: None,
+            island: Some(serde_json::from_str(r#"{"gravity": 1}"#).unwrap()),
+        };
+        assert!(update_settings(
+            State(state.clone()),
+            None,
+            Query(ExperimentQuery::default()),
+            Json(bad)
+        )
+        .await
+        .is_err());
+
+        let Json(actions) = list_actions(State(state)).await;
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].experiment.as_deref(), Some("control"));
+        assert!(matches!(
+            actions[0].action,
+            AdminAction::UpdateSettings { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_inject_rejects_invalid_genome() {
+        let state = state();
+        let inject = |genome: Genome| {
+            inject_genome(
+                State(state.clone()),
+                None,
+                Query(ExperimentQuery::default()),
+                Json(InjectRequest {
+                    genome,
+                    population: None,
+                }),
+            )
+        };
+
+        let mut genome = Genome {
+            brain: Some(shared::Brain::try_from(vec![0.5; shared::brain::BRAIN_WEIGHTS]).unwrap()),
+            ..Default::default()
+        };
+        assert!(inject(genome.clone()).await.is_ok());
+
+        genome.brain =
+            Some(shared::Brain::try_from(vec![10.0; shared::brain::BRAIN_WEIGHTS]).unwrap());
+        assert_eq!(inject(genome).await.unwrap_err(), StatusCode::BAD_REQUEST);
+        assert_eq!(list_actions(State(state.clone())).await.0.len(), 1);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::genomes::{GenomeSummary, SubmissionRecord};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// A genome evicted from the in-memory gene pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedGenome {
+    #[serde(flatten)]
+    pub summary: GenomeSummary,
+
+    /// Uptime (seconds) at which the genome was evicted
+    pub archived_seconds: u64,
+
+    pub submissions: Vec<SubmissionRecord>,
+}
+
+/// Cold storage for evicted genomes.
+///
+/// Genomes are appended to a JSON Lines file; only a map from genome ID to
+/// file offset is kept in memory, so lookups cost a single seek and read.
+pub struct GenomeArchive {
+    path: PathBuf,
+    file: File,
+    offsets: HashMap<Uuid, u64>,
+}
+
+impl GenomeArchive {
+    /// Open (or create) an archive file, indexing any genomes already in it
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)?;
+
+        let mut offsets = HashMap::new();
+        let mut reader = BufReader::new(File::open(&path)?);
+        let mut offset = 0u64;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            if let Ok(archived) = serde_json::from_str::<ArchivedGenome>(&line) {
+                offsets.insert(archived.summary.genome_id, offset);
+            }
+            offset += read as u64;
+        }
+
+        Ok(Self {
+            path,
+            file,
+            offsets,
+        })
+    }
+
+    /// Number of genomes in the archive
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn contains(&self, genome_id: &Uuid) -> bool {
+        self.offsets.contains_key(genome_id)
+    }
+
+    /// Append a genome to the archive
+    pub fn store(&mut self, archived: &ArchivedGenome) -> std::io::Result<()> {
+        let line = serde_json::to_string(archived)?;
+        let offset = self.file.seek(SeekFrom::End(0))?;
+        writeln!(self.file, "{}", line)?;
+        self.offsets.insert(archived.summary.genome_id, offset);
+        Ok(())
+    }
+
+    /// Look up an archived genome by ID
+    pub fn get(&self, genome_id: &Uuid) -> Option<ArchivedGenome> {
+        let offset = *self.offsets.get(genome_id)?;
+        let read = || -> std::io::Result<ArchivedGenome> {
+            let mut file = File::open(&self.path)?;
+            file.seek(SeekFrom::Start(offset))?;
+            let mut line = String::new();
+            BufReader::new(file).read_line(&mut line)?;
+            Ok(serde_json::from_str(&line)?)
+        };
+
+        match read() {
+            Ok(archived) => Some(archived),
+            Err(e) => {
+                tracing::warn!(%genome_id, error = %e, "Failed to read archived genome");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genomes::GenomeOrigin;
+    use shared::Genome;
+
+    fn archived(genome_id: Uuid) -> ArchivedGenome {
+        ArchivedGenome {
+            summary: GenomeSummary {
+                genome_id,
+                genome: Genome::random(),
+                population: 0,
+                fitness: 0.5,
+                empirical: None,
+                origin: GenomeOrigin::ServerMutated,
+                parent_ids: vec![Uuid::new_v4()],
+                lineage_root: genome_id,
+                created_seconds: 1,
+            },
+            archived_seconds: 2,
+            submissions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_archive_store_get_and_reopen() {
+        let path = std::env::temp_dir().join(format!("evo-archive-{}.jsonl", Uuid::new_v4()));
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+
+        {
+            let mut archive = GenomeArchive::open(&path).unwrap();
+            for id in &ids {
+                archive.store(&archived(*id)).unwrap();
+            }
+            assert_eq!(archive.len(), 3);
+            let second = archive.get(&ids[1]).unwrap();
+            assert_eq!(second.summary.genome_id, ids[1]);
+            assert!(archive.get(&Uuid::new_v4()).is_none());
+        }
+
+        // Reopening rebuilds the offset index from the file
+        let archive = GenomeArchive::open(&path).unwrap();
+        assert_eq!(archive.len(), 3);
+        assert!(archive.contains(&ids[2]));
+        assert_eq!(archive.get(&ids[2]).unwrap().summary.genome_id, ids[2]);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,154 @@
+use crate::admin::AdminAction;
+use crate::genomes::GenomeOrigin;
+use serde::Serialize;
+use shared::Genome;
+use std::fs::{File, OpenOptions};
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// Why a genome's population in the gene pool changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PopulationChangeCause {
+    /// Random genome created when the pool was initialised
+    Seeded,
+    /// Mutated copy of a pool genome handed out as a work seed
+    ServerMutation,
+    /// Genome had survivors at the end of a client simulation
+    Survived,
+    /// Genome went extinct in a client simulation
+    Extinct,
+    /// Evolved genome reported back by a client
+    Ingested,
+    /// Population clamped to the pool maximum
+    PopulationCap,
+    /// Set by an operator through the admin API
+    Admin,
+}
+
+/// A single line in the audit log
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditRecord {
+    PopulationChanged {
+        timestamp_ms: u64,
+        genome_id: Uuid,
+        old_population: u32,
+        new_population: u32,
+        cause: PopulationChangeCause,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        client_id: Option<Uuid>,
+    },
+    GenomeCreated {
+        timestamp_ms: u64,
+        genome_id: Uuid,
+        genome: Genome,
+        origin: GenomeOrigin,
+        parent_ids: Vec<Uuid>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        client_id: Option<Uuid>,
+    },
+    GenomeEvicted {
+        timestamp_ms: u64,
+        genome_id: Uuid,
+        population: u32,
+        archived: bool,
+    },
+    ResultRejected {
+        timestamp_ms: u64,
+        client_id: Uuid,
+        work_id: Uuid,
+        reason: String,
+    },
+    AdminAction {
+        timestamp_ms: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        actor: Option<String>,
+        #[serde(flatten)]
+        action: AdminAction,
+    },
+}
+
+/// Append-only JSON Lines log of every change made to the gene pool.
+///
+/// Replaying the log in order reconstructs the genomes, their ancestry and
+/// the population of every genome over the lifetime of the server.
+#[derive(Clone)]
+pub struct AuditLog {
+    writer: Arc<Mutex<LineWriter<File>>>,
+}
+
+impl AuditLog {
+    /// Open (or create) the log file at `path` for appending
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: Arc::new(Mutex::new(LineWriter::new(file))),
+        })
+    }
+
+    /// Append a record to the log. Write failures are reported via tracing
+    /// rather than propagated so that auditing never blocks evolution.
+    pub fn record(&self, record: &AuditRecord) {
+        let line = match serde_json::to_string(record) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to serialize audit record");
+                return;
+            }
+        };
+
+        let mut writer = match self.writer.lock() {
+            Ok(writer) => writer,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Err(e) = writeln!(writer, "{}", line) {
+            tracing::warn!(error = %e, "Failed to write audit record");
+        }
+    }
+}
+
+/// Milliseconds since the Unix epoch, used to timestamp audit records
+pub fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_log_appends_json_lines() {
+        let path = std::env::temp_dir().join(format!("evo-audit-{}.jsonl", Uuid::new_v4()));
+        let log = AuditLog::open(&path).unwrap();
+
+        let genome_id = Uuid::new_v4();
+        for new_population in [100, 120] {
+            log.record(&AuditRecord::PopulationChanged {
+                timestamp_ms: unix_millis(),
+                genome_id,
+                old_population: 100,
+                new_population,
+                cause: PopulationChangeCause::Survived,
+                client_id: None,
+            });
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["event"], "population_changed");
+        assert_eq!(first["cause"], "survived");
+        assert_eq!(first["genome_id"], genome_id.to_string());
+
+        std::fs::remove_file(&path).ok();
+    }
+}
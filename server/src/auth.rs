@@ -0,0 +1,500 @@
+use crate::audit::unix_millis;
+use crate::server::AppState;
+use axum::{
+    body::Body,
+    extract::{OriginalUri, Request, State},
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use shared::auth::{
+    verify_request, KEY_ID_HEADER, MAX_CLOCK_SKEW_SECS, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Largest request body accepted for signature verification
+const MAX_SIGNED_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Most signed requests remembered at once to refuse replays
+const MAX_SEEN_SIGNATURES: usize = 100_000;
+
+/// Key ID under which `ADMIN_TOKEN` is registered
+pub const ADMIN_TOKEN_KEY_ID: &str = "admin-token";
+
+/// Key ID under which `WORKER_TOKEN` is registered
+pub const WORKER_TOKEN_KEY_ID: &str = "worker-token";
+
+/// What an API key may be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Request work and submit results
+    Worker,
+    /// Read the monitoring API
+    ReadOnly,
+    /// Use the admin API; implies every other scope
+    Admin,
+}
+
+impl Scope {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_string()))
+            .map_err(|_| format!("Unknown scope: {}", value))
+    }
+}
+
+/// A registered API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub key_id: String,
+    pub secret: String,
+    pub scopes: Vec<Scope>,
+
+    #[serde(default)]
+    pub description: String,
+
+    #[serde(default)]
+    pub revoked: bool,
+
+    #[serde(default)]
+    pub created_ms: u64,
+}
+
+impl ApiKey {
+    /// Create a key with a random ID and secret
+    pub fn generate(scopes: Vec<Scope>, description: String) -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            key_id: format!("key-{}", hex::encode(rng.gen::<[u8; 6]>())),
+            secret: hex::encode(rng.gen::<[u8; 32]>()),
+            scopes,
+            description,
+            revoked: false,
+            created_ms: unix_millis(),
+        }
+    }
+
+    pub fn allows(&self, scope: Scope) -> bool {
+        !self.revoked && (self.scopes.contains(&scope) || self.scopes.contains(&Scope::Admin))
+    }
+
+    /// The key without its secret
+    pub fn info(&self) -> ApiKeyInfo {
+        ApiKeyInfo {
+            key_id: self.key_id.clone(),
+            scopes: self.scopes.clone(),
+            description: self.description.clone(),
+            revoked: self.revoked,
+            created_ms: self.created_ms,
+        }
+    }
+}
+
+/// Public view of an API key, as listed by the admin API
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKeyInfo {
+    pub key_id: String,
+    pub scopes: Vec<Scope>,
+    pub description: String,
+    pub revoked: bool,
+    pub created_ms: u64,
+}
+
+/// The authenticated caller of a request, added to the request extensions
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub key_id: String,
+}
+
+/// API keys known to the server, optionally persisted to a JSON file
+#[derive(Clone, Default)]
+pub struct KeyRegistry {
+    inner: Arc<RwLock<RegistryInner>>,
+}
+
+#[derive(Default)]
+struct RegistryInner {
+    keys: HashMap<String, ApiKey>,
+
+    /// Keys configured from the environment, never written to the file
+    ephemeral: HashSet<String>,
+
+    path: Option<PathBuf>,
+}
+
+impl KeyRegistry {
+    /// Load the registry from a JSON array of keys; a missing file is an empty registry
+    pub fn load(path: Option<PathBuf>) -> anyhow::Result<Self> {
+        let mut keys = HashMap::new();
+        if let Some(path) = &path {
+            if path.exists() {
+                let json = std::fs::read_to_string(path)?;
+                let list: Vec<ApiKey> = serde_json::from_str(&json)?;
+                keys = list.into_iter().map(|k| (k.key_id.clone(), k)).collect();
+            }
+        }
+
+        Ok(Self {
+            inner: Arc::new(RwLock::new(RegistryInner {
+                keys,
+                ephemeral: HashSet::new(),
+                path,
+            })),
+        })
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, RegistryInner> {
+        match self.inner.read() {
+            Ok(inner) => inner,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, RegistryInner> {
+        match self.inner.write() {
+            Ok(inner) => inner,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Register a key that only lives for this process (e.g. `ADMIN_TOKEN`)
+    pub fn insert_ephemeral(&self, key: ApiKey) {
+        let mut inner = self.write();
+        inner.ephemeral.insert(key.key_id.clone());
+        inner.keys.insert(key.key_id.clone(), key);
+    }
+
+    /// Register a key and persist the registry
+    pub fn insert(&self, key: ApiKey) -> std::io::Result<()> {
+        let mut inner = self.write();
+        inner.keys.insert(key.key_id.clone(), key);
+        inner.save()
+    }
+
+    /// Revoke a key. Returns false if there is no such key.
+    pub fn revoke(&self, key_id: &str) -> std::io::Result<bool> {
+        let mut inner = self.write();
+        let Some(key) = inner.keys.get_mut(key_id) else {
+            return Ok(false);
+        };
+        key.revoked = true;
+        inner.save()?;
+        Ok(true)
+    }
+
+    pub fn list(&self) -> Vec<ApiKeyInfo> {
+        let mut keys: Vec<ApiKeyInfo> = self.read().keys.values().map(ApiKey::info).collect();
+        keys.sort_by_key(|k| k.created_ms);
+        keys
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().keys.len()
+    }
+
+    /// Find the key whose secret is `secret`, comparing every key in constant time
+    fn find_by_secret(&self, secret: &str) -> Option<ApiKey> {
+        let inner = self.read();
+        let mut found = None;
+        for key in inner.keys.values() {
+            if constant_time_eq(key.secret.as_bytes(), secret.as_bytes()) {
+                found = Some(key.clone());
+            }
+        }
+        found
+    }
+
+    fn get(&self, key_id: &str) -> Option<ApiKey> {
+        self.read().keys.get(key_id).cloned()
+    }
+}
+
+impl RegistryInner {
+    fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut keys: Vec<&ApiKey> = self
+            .keys
+            .values()
+            .filter(|k| !self.ephemeral.contains(&k.key_id))
+            .collect();
+        keys.sort_by_key(|k| k.created_ms);
+
+        // Write to a temporary file first so a crash never leaves a truncated registry
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(&keys)?)?;
+        std::fs::rename(tmp, path)
+    }
+}
+
+/// Compare secrets without leaking the position of the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Key registry plus which scopes need credentials
+#[derive(Clone)]
+pub struct AuthConfig {
+    pub registry: KeyRegistry,
+
+    /// Scopes whose endpoints require a key; admin endpoints always do
+    required: Arc<HashSet<Scope>>,
+
+    /// Signed requests already accepted, as (timestamp, key ID, signature),
+    /// kept until their timestamp leaves the clock skew window
+    seen: Arc<Mutex<BTreeSet<(u64, String, String)>>>,
+}
+
+impl AuthConfig {
+    pub fn new(registry: KeyRegistry, required: impl IntoIterator<Item = Scope>) -> Self {
+        let mut required: HashSet<Scope> = required.into_iter().collect();
+        required.insert(Scope::Admin);
+        Self {
+            registry,
+            required: Arc::new(required),
+            seen: Arc::default(),
+        }
+    }
+
+    pub fn requires(&self, scope: Scope) -> bool {
+        self.required.contains(&scope)
+    }
+
+    /// Authenticate a request by bearer secret or HMAC signature
+    fn authenticate(&self, parts: &Parts, body: &[u8]) -> Result<ApiKey, StatusCode> {
+        let header = |name: &str| parts.headers.get(name).and_then(|v| v.to_str().ok());
+
+        if let Some(secret) = header(AUTHORIZATION.as_str()).and_then(|v| v.strip_prefix("Bearer "))
+        {
+            return self
+                .registry
+                .find_by_secret(secret)
+                .ok_or(StatusCode::UNAUTHORIZED);
+        }
+
+        let (Some(key_id), Some(timestamp), Some(signature)) = (
+            header(KEY_ID_HEADER),
+            header(TIMESTAMP_HEADER),
+            header(SIGNATURE_HEADER),
+        ) else {
+            return Err(StatusCode::UNAUTHORIZED);
+        };
+        let timestamp: u64 = timestamp.parse().map_err(|_| StatusCode::UNAUTHORIZED)?;
+        let now = unix_millis() / 1000;
+        if now.abs_diff(timestamp) > MAX_CLOCK_SKEW_SECS {
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        let key = self.registry.get(key_id).ok_or(StatusCode::UNAUTHORIZED)?;
+        // Clients sign the full path; `nest` strips its prefix from `parts.uri`
+        let uri = parts
+            .extensions
+            .get::<OriginalUri>()
+            .map_or(&parts.uri, |original| &original.0);
+        let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+        if !verify_request(
+            &key.secret,
+            timestamp,
+            parts.method.as_str(),
+            path,
+            body,
+            signature,
+        ) {
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        self.record_signature(timestamp, key_id, signature, now)?;
+        Ok(key)
+    }
+
+    /// Remember a verified signature, refusing it if it was seen before so a
+    /// captured request can't be replayed within the clock skew window
+    fn record_signature(
+        &self,
+        timestamp: u64,
+        key_id: &str,
+        signature: &str,
+        now: u64,
+    ) -> Result<(), StatusCode> {
+        let mut seen = match self.seen.lock() {
+            Ok(seen) => seen,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        while let Some((oldest, _, _)) = seen.first() {
+            if oldest + MAX_CLOCK_SKEW_SECS >= now {
+                break;
+            }
+            seen.pop_first();
+        }
+        if seen.len() >= MAX_SEEN_SIGNATURES {
+            return Err(StatusCode::TOO_MANY_REQUESTS);
+        }
+        if !seen.insert((timestamp, key_id.to_string(), signature.to_string())) {
+            tracing::warn!(key_id, "Rejected replayed request");
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Ok(())
+    }
+}
+
+/// Let the request through if it carries a key with `scope` (or the scope
+/// is open), recording the caller as a [`Principal`] extension
+async fn authorize(auth: &AuthConfig, scope: Scope, request: Request, next: Next) -> Response {
+    if !auth.requires(scope) {
+        return next.run(request).await;
+    }
+
+    // The body is part of the signature, so it has to be buffered
+    let (mut parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(_) => return StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+    };
+
+    let key = match auth.authenticate(&parts, &body) {
+        Ok(key) => key,
+        Err(status) => {
+            tracing::warn!(path = %parts.uri.path(), ?scope, "Rejected unauthenticated request");
+            return status.into_response();
+        }
+    };
+    if !key.allows(scope) {
+        tracing::warn!(key_id = %key.key_id, path = %parts.uri.path(), ?scope, "Key not allowed");
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    parts.extensions.insert(Principal { key_id: key.key_id });
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+pub async fn require_worker(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    authorize(&state.auth, Scope::Worker, request, next).await
+}
+
+pub async fn require_read(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    authorize(&state.auth, Scope::ReadOnly, request, next).await
+}
+
+pub async fn require_admin(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    authorize(&state.auth, Scope::Admin, request, next).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Method;
+    use shared::auth::sign_request;
+
+    fn parts(method: Method, path: &str, headers: &[(&str, String)]) -> Parts {
+        let mut builder = Request::builder().method(method).uri(path);
+        for (name, value) in headers {
+            builder = builder.header(*name, value);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn test_scopes() {
+        let worker = ApiKey::generate(vec![Scope::Worker], String::new());
+        assert!(worker.allows(Scope::Worker));
+        assert!(!worker.allows(Scope::ReadOnly));
+        assert!(!worker.allows(Scope::Admin));
+
+        let mut admin = ApiKey::generate(vec![Scope::Admin], String::new());
+        assert!(admin.allows(Scope::Worker));
+        assert!(admin.allows(Scope::ReadOnly));
+        admin.revoked = true;
+        assert!(!admin.allows(Scope::Admin));
+
+        assert_eq!(Scope::parse("read_only").unwrap(), Scope::ReadOnly);
+        assert!(Scope::parse("root").is_err());
+    }
+
+    #[test]
+    fn test_authenticate_bearer_and_signature() {
+        let registry = KeyRegistry::default();
+        let key = ApiKey::generate(vec![Scope::Worker], "worker".to_string());
+        registry.insert(key.clone()).unwrap();
+        let auth = AuthConfig::new(registry, [Scope::Worker]);
+        assert!(auth.requires(Scope::Admin));
+        assert!(!auth.requires(Scope::ReadOnly));
+
+        let bearer = parts(
+            Method::GET,
+            "/api/stats",
+            &[("authorization", format!("Bearer {}", key.secret))],
+        );
+        assert_eq!(auth.authenticate(&bearer, b"").unwrap().key_id, key.key_id);
+        let wrong = parts(
+            Method::GET,
+            "/api/stats",
+            &[("authorization", "Bearer nope".to_string())],
+        );
+        assert!(auth.authenticate(&wrong, b"").is_err());
+
+        let body = br#"{"work_id":"x"}"#;
+        let now = unix_millis() / 1000;
+        let signed = |timestamp: u64, body: &[u8]| {
+            parts(
+                Method::POST,
+                "/api/work/submit",
+                &[
+                    (KEY_ID_HEADER, key.key_id.clone()),
+                    (TIMESTAMP_HEADER, timestamp.to_string()),
+                    (
+                        SIGNATURE_HEADER,
+                        sign_request(&key.secret, timestamp, "POST", "/api/work/submit", body),
+                    ),
+                ],
+            )
+        };
+        assert!(auth.authenticate(&signed(now, body), body).is_ok());
+        // Replayed request
+        assert_eq!(
+            auth.authenticate(&signed(now, body), body).unwrap_err(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert!(auth.authenticate(&signed(now - 1, body), body).is_ok());
+        // Tampered body
+        assert!(auth.authenticate(&signed(now, body), b"{}").is_err());
+        // Stale timestamp
+        let stale = now - MAX_CLOCK_SKEW_SECS - 1;
+        assert!(auth.authenticate(&signed(stale, body), body).is_err());
+        // No credentials
+        assert!(auth
+            .authenticate(&parts(Method::POST, "/api/work/submit", &[]), body)
+            .is_err());
+    }
+
+    #[test]
+    fn test_registry_persists_and_revokes() {
+        let path = std::env::temp_dir().join(format!("evo-keys-{}.json", uuid::Uuid::new_v4()));
+        let registry = KeyRegistry::load(Some(path.clone())).unwrap();
+        let key = ApiKey::generate(vec![Scope::ReadOnly], "dashboard".to_string());
+        registry.insert(key.clone()).unwrap();
+        registry.insert_ephemeral(ApiKey::generate(vec![Scope::Admin], String::new()));
+        assert!(registry.revoke(&key.key_id).unwrap());
+        assert!(!registry.revoke("missing").unwrap());
+
+        // Ephemeral keys are not written to the file
+        let reloaded = KeyRegistry::load(Some(path.clone())).unwrap();
+        assert_eq!(reloaded.len(), 1);
+        assert!(reloaded.list()[0].revoked);
+        assert!(reloaded
+            .find_by_secret(&key.secret)
+            .is_some_and(|k| k.revoked));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,276 @@
+use crate::audit::{unix_millis, PopulationChangeCause};
+use crate::server::AppState;
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use shared::EmpiricalFitness;
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Events buffered per subscriber before the slowest ones start missing events
+const EVENT_BUFFER: usize = 1024;
+
+/// A client that hasn't requested or submitted work for this long has left
+pub const CLIENT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// What happened
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventKind {
+    WorkIssued {
+        work_id: Uuid,
+        client_id: Uuid,
+        seeds: usize,
+    },
+    WorkCompleted {
+        work_id: Uuid,
+        client_id: Uuid,
+        genomes: usize,
+        survivors: u32,
+        trust: f64,
+    },
+    WorkRejected {
+        work_id: Uuid,
+        client_id: Uuid,
+        reason: String,
+    },
+    PopulationChanged {
+        genome_id: Uuid,
+        old_population: u32,
+        new_population: u32,
+        cause: PopulationChangeCause,
+    },
+    /// A different genome now leads the empirical fitness ranking
+    NewBest {
+        genome_id: Uuid,
+        empirical: EmpiricalFitness,
+    },
+    Extinction {
+        genome_id: Uuid,
+    },
+    ClientJoined {
+        client_id: Uuid,
+    },
+    ClientLeft {
+        client_id: Uuid,
+    },
+}
+
+impl EventKind {
+    fn name(&self) -> &'static str {
+        match self {
+            EventKind::WorkIssued { .. } => "work_issued",
+            EventKind::WorkCompleted { .. } => "work_completed",
+            EventKind::WorkRejected { .. } => "work_rejected",
+            EventKind::PopulationChanged { .. } => "population_changed",
+            EventKind::NewBest { .. } => "new_best",
+            EventKind::Extinction { .. } => "extinction",
+            EventKind::ClientJoined { .. } => "client_joined",
+            EventKind::ClientLeft { .. } => "client_left",
+        }
+    }
+}
+
+/// One event as sent to subscribers
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerEvent {
+    pub timestamp_ms: u64,
+
+    /// Experiment the event happened in; None for server-wide events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<String>,
+
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+/// Fan-out of server events to any number of subscribers
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Arc<ServerEvent>>,
+
+    /// Clients seen recently, and when, for join and leave events
+    clients: Arc<Mutex<HashMap<Uuid, Instant>>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(EVENT_BUFFER).0,
+            clients: Arc::default(),
+        }
+    }
+}
+
+impl EventBus {
+    pub fn emit(&self, experiment: Option<&str>, kind: EventKind) {
+        // Sending only fails when nobody is listening
+        let _ = self.sender.send(Arc::new(ServerEvent {
+            timestamp_ms: unix_millis(),
+            experiment: experiment.map(str::to_string),
+            kind,
+        }));
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<ServerEvent>> {
+        self.sender.subscribe()
+    }
+
+    /// Events of one experiment, for its gene pool
+    pub fn for_experiment(&self, experiment: &str) -> ExperimentEvents {
+        ExperimentEvents {
+            bus: self.clone(),
+            experiment: experiment.into(),
+        }
+    }
+
+    /// Note client activity, announcing clients that weren't seen recently
+    pub fn client_seen(&self, client_id: Uuid) {
+        let joined = {
+            let mut clients = match self.clients.lock() {
+                Ok(clients) => clients,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            clients.insert(client_id, Instant::now()).is_none()
+        };
+        if joined {
+            self.emit(None, EventKind::ClientJoined { client_id });
+        }
+    }
+
+    /// Announce and forget clients idle for longer than `timeout`
+    pub fn expire_clients(&self, timeout: Duration) {
+        let left: Vec<Uuid> = {
+            let mut clients = match self.clients.lock() {
+                Ok(clients) => clients,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let left = clients
+                .iter()
+                .filter(|(_, seen)| seen.elapsed() > timeout)
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            for id in &left {
+                clients.remove(id);
+            }
+            left
+        };
+        for client_id in left {
+            self.emit(None, EventKind::ClientLeft { client_id });
+        }
+    }
+}
+
+/// Handle a gene pool uses to publish events tagged with its experiment
+#[derive(Clone)]
+pub struct ExperimentEvents {
+    bus: EventBus,
+    experiment: Arc<str>,
+}
+
+impl ExperimentEvents {
+    pub fn emit(&self, kind: EventKind) {
+        self.bus.emit(Some(&self.experiment), kind);
+    }
+}
+
+/// Query parameters for `GET /api/events`
+#[derive(Debug, Default, Deserialize)]
+pub struct EventsQuery {
+    /// Only events of this experiment (server-wide events are always sent)
+    pub experiment: Option<String>,
+
+    /// Comma-separated event types to send; all if unset
+    pub types: Option<String>,
+}
+
+/// Stream server events as Server-Sent Events. Each event's SSE name is its
+/// type; subscribers that fall behind get a `lagged` event with the number missed.
+pub async fn handle_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let types: Option<HashSet<String>> = query.types.map(|types| {
+        types
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    });
+    let filter = move |event: &ServerEvent| {
+        let experiment_matches = match (&query.experiment, &event.experiment) {
+            (Some(wanted), Some(experiment)) => wanted == experiment,
+            _ => true,
+        };
+        let type_matches = types
+            .as_ref()
+            .is_none_or(|types| types.contains(event.kind.name()));
+        experiment_matches && type_matches
+    };
+
+    let receiver = state.events.subscribe();
+    let stream = stream::unfold(receiver, move |mut receiver| {
+        let filter = filter.clone();
+        async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) if filter(&event) => Event::default()
+                        .event(event.kind.name())
+                        .json_data(&*event)
+                        .unwrap_or_default(),
+                    Ok(_) => continue,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        Event::default().event("lagged").data(missed.to_string())
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                };
+                return Some((Ok(event), receiver));
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_serialization() {
+        let event = ServerEvent {
+            timestamp_ms: 1,
+            experiment: Some("control".to_string()),
+            kind: EventKind::Extinction {
+                genome_id: Uuid::nil(),
+            },
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "extinction");
+        assert_eq!(json["experiment"], "control");
+        assert_eq!(event.kind.name(), "extinction");
+    }
+
+    #[test]
+    fn test_client_presence() {
+        let bus = EventBus::default();
+        let mut receiver = bus.subscribe();
+        let client_id = Uuid::new_v4();
+
+        bus.client_seen(client_id);
+        bus.client_seen(client_id);
+        bus.expire_clients(Duration::ZERO);
+
+        let kinds: Vec<&str> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|e| e.kind.name())
+            .collect();
+        assert_eq!(kinds, ["client_joined", "client_left"]);
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// How the gene pool chooses which genome to evict when it is over capacity.
+///
+/// Both policies prefer extinct genomes; a living genome is only evicted
+/// (lowest population first) when every genome in the pool is alive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// Evict the extinct genome that entered the pool first
+    #[default]
+    OldestExtinct,
+    /// Evict the extinct genome closest in trait space to the thriving genomes
+    LeastNovel,
+}
+
+impl EvictionPolicy {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_string()))
+            .map_err(|_| format!("Unknown eviction policy: {}", value))
+    }
+}
+
+/// Capacity limit and eviction behaviour for the gene pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapacityConfig {
+    /// Maximum number of genomes kept in memory
+    pub max_genomes: usize,
+
+    #[serde(default)]
+    pub policy: EvictionPolicy,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_eviction_policy() {
+        assert_eq!(
+            EvictionPolicy::parse("least_novel").unwrap(),
+            EvictionPolicy::LeastNovel
+        );
+        assert_eq!(
+            EvictionPolicy::parse("oldest_extinct").unwrap(),
+            EvictionPolicy::OldestExtinct
+        );
+        assert!(EvictionPolicy::parse("random").is_err());
+    }
+}
@@ -0,0 +1,526 @@
+use crate::gene_pool::GenePool;
+use crate::selection::SelectionConfig;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use shared::operators::{Crossover, Mutation};
+use shared::{
+    CombatConfig, EnvironmentSchedule, GenomeWithId, IslandEnvironment, MigrationConfig,
+    TerrainConfig, WorkAssignment, WorldTopology,
+};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Name of the experiment run when no experiments are configured
+pub const DEFAULT_EXPERIMENT: &str = "default";
+
+/// Largest world width or height an experiment may use
+const MAX_WORLD_SIZE: usize = 2000;
+
+/// Largest distance creatures may look for a mate over
+const MAX_MATING_RADIUS: f64 = 10.0;
+
+/// Island parameters handed to clients with every work assignment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IslandSettings {
+    pub world_width: usize,
+    pub world_height: usize,
+    pub max_steps: u32,
+    pub mutation_rate: f64,
+    /// Mutation operator clients apply to offspring
+    pub mutation: Mutation,
+    /// Crossover operator clients use to combine parents
+    pub crossover: Crossover,
+    /// Whether creatures whose genome has no brain get a random one
+    pub brains: bool,
+    /// Water, mountains and fertile soil of the world
+    pub terrain: TerrainConfig,
+    /// Whether the world's edges stop, bounce back or wrap creatures around
+    pub world_topology: WorldTopology,
+    /// Fleeing, fighting and sharing over food
+    pub combat: CombatConfig,
+    /// Seasons, droughts, food blooms and climate drift
+    pub schedule: EnvironmentSchedule,
+    /// Islands each client runs side by side, and migration between them
+    pub migration: MigrationConfig,
+    pub plant_density: f64,
+    pub food_density: f64,
+    pub reproduction_threshold: f64,
+    /// Farthest apart creatures can be to mate; None for anywhere
+    pub mating_radius: Option<f64>,
+}
+
+impl Default for IslandSettings {
+    fn default() -> Self {
+        Self {
+            world_width: 300,
+            world_height: 300,
+            max_steps: 3000,
+            mutation_rate: 0.05, // client-side mutation enabled
+            mutation: Mutation::default(),
+            crossover: Crossover::default(),
+            brains: false,
+            terrain: TerrainConfig::Flat,
+            world_topology: WorldTopology::Bounded,
+            combat: CombatConfig::default(),
+            schedule: EnvironmentSchedule::default(),
+            migration: MigrationConfig::default(),
+            plant_density: 0.05,
+            food_density: 0.02,
+            reproduction_threshold: 100.0,
+            mating_radius: Some(3.0),
+        }
+    }
+}
+
+impl IslandSettings {
+    /// Check that the settings describe a runnable island
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_WORLD_SIZE).contains(&self.world_width)
+            || !(1..=MAX_WORLD_SIZE).contains(&self.world_height)
+        {
+            return Err(format!("World size must be 1 to {}", MAX_WORLD_SIZE));
+        }
+        if self.max_steps == 0 {
+            return Err("max_steps must be positive".to_string());
+        }
+        for (name, value) in [
+            ("mutation_rate", self.mutation_rate),
+            ("plant_density", self.plant_density),
+            ("food_density", self.food_density),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} must be between 0 and 1", name));
+            }
+        }
+        if !self.reproduction_threshold.is_finite() || self.reproduction_threshold <= 0.0 {
+            return Err("reproduction_threshold must be positive".to_string());
+        }
+        if self
+            .mating_radius
+            .is_some_and(|radius| !(0.0..=MAX_MATING_RADIUS).contains(&radius))
+        {
+            return Err(format!(
+                "mating_radius must be between 0 and {}",
+                MAX_MATING_RADIUS
+            ));
+        }
+        self.mutation.validate()?;
+        self.crossover.validate()?;
+        self.terrain.validate()?;
+        self.combat.validate()?;
+        self.schedule.validate()?;
+        self.migration.validate()
+    }
+
+    pub fn environment(&self) -> IslandEnvironment {
+        IslandEnvironment {
+            plant_density: self.plant_density,
+            food_density: self.food_density,
+            reproduction_threshold: self.reproduction_threshold,
+            mating_radius: self.mating_radius,
+        }
+    }
+}
+
+/// One entry in the experiments config file
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExperimentConfig {
+    pub name: String,
+
+    /// Relative share of work handed to this experiment when clients don't choose
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+
+    #[serde(default)]
+    pub island: IslandSettings,
+
+    /// Seed selection strategy; the server-wide default if None
+    #[serde(default)]
+    pub selection: Option<SelectionConfig>,
+
+    /// Text or PNG terrain map, relative to the config file, replacing
+    /// `island.terrain` (see `maps`)
+    #[serde(default)]
+    pub terrain_map: Option<PathBuf>,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+impl ExperimentConfig {
+    /// Parse and validate a JSON array of experiments
+    pub fn parse_list(json: &str) -> Result<Vec<Self>, String> {
+        let configs: Vec<Self> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if configs.is_empty() {
+            return Err("No experiments configured".to_string());
+        }
+
+        let mut names = HashSet::new();
+        for config in &configs {
+            if config.name.is_empty() {
+                return Err("Experiment names must not be empty".to_string());
+            }
+            // Names become part of file names for per-experiment audit logs and archives
+            if !config
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(format!(
+                    "Experiment name {:?} may only contain letters, digits, '-' and '_'",
+                    config.name
+                ));
+            }
+            if !names.insert(config.name.as_str()) {
+                return Err(format!("Duplicate experiment name: {}", config.name));
+            }
+            if !config.weight.is_finite() || config.weight < 0.0 {
+                return Err(format!("Invalid weight for experiment {}", config.name));
+            }
+            config
+                .island
+                .validate()
+                .map_err(|e| format!("Experiment {}: {}", config.name, e))?;
+        }
+        if configs.iter().all(|c| c.weight == 0.0) {
+            return Err("At least one experiment needs a positive weight".to_string());
+        }
+
+        Ok(configs)
+    }
+
+    /// Load the experiment's terrain map, if it has one, resolving its path
+    /// against the directory of the config file
+    pub fn load_terrain_map(&mut self, config_dir: &Path) -> Result<(), String> {
+        if let Some(path) = &self.terrain_map {
+            let path = config_dir.join(path);
+            let terrain = crate::maps::load(&path).map_err(|e| {
+                format!(
+                    "Experiment {}: terrain map {}: {}",
+                    self.name,
+                    path.display(),
+                    e
+                )
+            })?;
+            self.island.terrain = terrain;
+        }
+        Ok(())
+    }
+}
+
+/// A named experiment with its own gene pool and island parameters
+pub struct Experiment {
+    pub name: String,
+    pub weight: f64,
+    pub selection: SelectionConfig,
+    pub gene_pool: GenePool,
+
+    /// Island parameters, changeable through the admin API
+    island: RwLock<IslandSettings>,
+
+    /// While paused no work is handed out for this experiment
+    paused: AtomicBool,
+}
+
+impl Experiment {
+    pub fn new(
+        name: String,
+        weight: f64,
+        island: IslandSettings,
+        selection: SelectionConfig,
+        gene_pool: GenePool,
+    ) -> Self {
+        Self {
+            name,
+            weight,
+            selection,
+            gene_pool,
+            island: RwLock::new(island),
+            paused: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn island(&self) -> IslandSettings {
+        self.island.read().await.clone()
+    }
+
+    pub async fn set_island(&self, island: IslandSettings) {
+        *self.island.write().await = island;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Build a work assignment for this experiment
+    pub async fn assignment(&self, seed_genomes: Vec<GenomeWithId>) -> WorkAssignment {
+        let island = self.island().await;
+        // Both server and clients mutate to encourage exploration
+        let mut assignment = WorkAssignment::new_spatial(
+            seed_genomes,
+            island.world_width,
+            island.world_height,
+            island.max_steps,
+            island.mutation_rate,
+        );
+        assignment.mutation = island.mutation;
+        assignment.crossover = island.crossover;
+        assignment.brains = island.brains;
+        assignment.terrain = island.terrain.clone();
+        assignment.world_topology = island.world_topology;
+        assignment.combat = island.combat;
+        // The environment clock runs on with every island the experiment completes
+        assignment.clock_start = self.gene_pool.total_work_units().await * island.max_steps as u64;
+        assignment.schedule = island.schedule.clone();
+        assignment.migration = island.migration;
+        assignment.experiment = Some(self.name.clone());
+        assignment.environment = Some(island.environment());
+        assignment
+    }
+}
+
+/// All experiments run by the server. The first one is the default used by
+/// API calls and results that don't name an experiment.
+#[derive(Clone)]
+pub struct Experiments {
+    experiments: Arc<Vec<Experiment>>,
+}
+
+impl Experiments {
+    pub fn new(experiments: Vec<Experiment>) -> Self {
+        assert!(
+            !experiments.is_empty(),
+            "at least one experiment is required"
+        );
+        Self {
+            experiments: Arc::new(experiments),
+        }
+    }
+
+    pub fn all(&self) -> &[Experiment] {
+        &self.experiments
+    }
+
+    /// Look up an experiment by name, or the default experiment if None
+    pub fn get(&self, name: Option<&str>) -> Option<&Experiment> {
+        match name {
+            Some(name) => self.experiments.iter().find(|e| e.name == name),
+            None => self.experiments.first(),
+        }
+    }
+
+    /// Pick the experiment for a work request: the one requested, or one of
+    /// the running experiments chosen at random in proportion to their
+    /// weights. None if the requested experiment is unknown, or if none was
+    /// requested and every experiment is paused.
+    pub fn assign(&self, requested: Option<&str>, rng: &mut impl Rng) -> Option<&Experiment> {
+        if requested.is_some() {
+            return self.get(requested);
+        }
+
+        let running: Vec<&Experiment> =
+            self.experiments.iter().filter(|e| !e.is_paused()).collect();
+        let total: f64 = running.iter().map(|e| e.weight).sum();
+        let mut target = rng.gen::<f64>() * total;
+        for experiment in running.iter() {
+            if target < experiment.weight {
+                return Some(experiment);
+            }
+            target -= experiment.weight;
+        }
+        running
+            .iter()
+            .rev()
+            .find(|e| e.weight > 0.0)
+            .or(running.first())
+            .copied()
+    }
+}
+
+/// Query parameter selecting the experiment an API call applies to
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExperimentQuery {
+    pub experiment: Option<String>,
+}
+
+/// Entry in the response body for `GET /api/experiments`
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperimentSummary {
+    pub name: String,
+    pub weight: f64,
+
+    /// Used by API calls that don't name an experiment
+    pub default: bool,
+
+    /// No work is handed out while paused
+    pub paused: bool,
+
+    pub island: IslandSettings,
+    pub selection: SelectionConfig,
+    pub gene_pool_size: usize,
+    pub active_clients: usize,
+    pub total_work_units: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Topology;
+
+    fn experiment(name: &str, weight: f64) -> Experiment {
+        Experiment::new(
+            name.to_string(),
+            weight,
+            IslandSettings::default(),
+            SelectionConfig::default(),
+            GenePool::new(),
+        )
+    }
+
+    #[test]
+    fn test_parse_experiment_list() {
+        let configs = ExperimentConfig::parse_list(
+            r#"[
+                {"name": "control"},
+                {"name": "scarce", "weight": 2, "island": {"food_density": 0.01},
+                 "selection": {"type": "tournament", "size": 4}},
+                {"name": "operators", "island": {"mutation": {"type": "gaussian"},
+                 "crossover": {"type": "blend", "alpha": 0.3}, "world_topology": "torus",
+                 "combat": {"flee_chance": 0.9}}},
+                {"name": "terrain", "island": {"terrain": {"type": "islands", "seed": 3},
+                 "schedule": [{"type": "seasons", "period": 1000, "amplitude": 0.8}],
+                 "migration": {"islands": 4, "topology": {"type": "random"}, "interval": 50}},
+                 "terrain_map": "maps/valley.txt"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(configs[0].weight, 1.0);
+        assert!(configs[0].selection.is_none());
+        assert_eq!(configs[1].island.food_density, 0.01);
+        assert_eq!(configs[1].island.world_width, 300);
+        assert_eq!(
+            configs[1].selection,
+            Some(SelectionConfig::Tournament { size: 4 })
+        );
+        assert_eq!(configs[1].island.mutation, Mutation::Uniform);
+        assert_eq!(configs[2].island.mutation, Mutation::Gaussian);
+        assert_eq!(configs[2].island.crossover, Crossover::Blend { alpha: 0.3 });
+        assert_eq!(configs[2].island.world_topology, WorldTopology::Torus);
+        assert_eq!(configs[0].island.world_topology, WorldTopology::Bounded);
+        assert_eq!(configs[2].island.combat.flee_chance, 0.9);
+        assert_eq!(configs[2].island.combat.fight_chance, 0.7);
+        assert_eq!(configs[0].island.mating_radius, Some(3.0));
+        assert!(matches!(
+            configs[3].island.terrain,
+            TerrainConfig::Islands { seed: Some(3), .. }
+        ));
+        assert_eq!(configs[3].island.schedule.events.len(), 1);
+        assert_eq!(configs[3].island.migration.islands, 4);
+        assert_eq!(configs[3].island.migration.topology, Topology::Random);
+        assert_eq!(configs[0].island.migration.islands, 1);
+
+        // Maps load relative to the config file
+        let dir = std::env::temp_dir().join(format!("evo-maps-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("maps")).unwrap();
+        std::fs::write(dir.join("maps/valley.txt"), "^^^\n.*.\n~~~\n").unwrap();
+        let mut config = configs[3].clone();
+        config.load_terrain_map(&dir).unwrap();
+        assert_eq!(
+            config.island.terrain,
+            TerrainConfig::Map {
+                rows: vec!["^^^".to_string(), ".*.".to_string(), "~~~".to_string()]
+            }
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(config.load_terrain_map(&dir).is_err());
+
+        assert!(ExperimentConfig::parse_list("[]").is_err());
+        assert!(ExperimentConfig::parse_list(r#"[{"name": "a"}, {"name": "a"}]"#).is_err());
+        assert!(ExperimentConfig::parse_list(r#"[{"name": "a", "weight": 0}]"#).is_err());
+        assert!(
+            ExperimentConfig::parse_list(r#"[{"name": "a", "island": {"food_density": 2}}]"#)
+                .is_err()
+        );
+        assert!(ExperimentConfig::parse_list(
+            r#"[{"name": "a", "island": {"crossover": {"type": "blend", "alpha": 2}}}]"#
+        )
+        .is_err());
+        assert!(ExperimentConfig::parse_list(
+            r#"[{"name": "a", "island": {"terrain": {"type": "map", "rows": ["~x"]}}}]"#
+        )
+        .is_err());
+        assert!(ExperimentConfig::parse_list(
+            r#"[{"name": "a", "island": {"schedule": [{"type": "drift", "rate": "fast"}]}}]"#
+        )
+        .is_err());
+        assert!(ExperimentConfig::parse_list(
+            r#"[{"name": "a", "island": {"migration": {"islands": 100}}}]"#
+        )
+        .is_err());
+        assert!(ExperimentConfig::parse_list(
+            r#"[{"name": "a", "island": {"combat": {"fight_chance": 2}}}]"#
+        )
+        .is_err());
+        assert!(ExperimentConfig::parse_list(
+            r#"[{"name": "a", "island": {"mating_radius": 50}}]"#
+        )
+        .is_err());
+        for name in ["../escape", "a/b", "..", "a.b", "a\\b"] {
+            let json = format!(r#"[{{"name": {:?}}}]"#, name);
+            assert!(ExperimentConfig::parse_list(&json).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_assign_by_name_and_weight() {
+        let experiments = Experiments::new(vec![
+            experiment("control", 1.0),
+            experiment("variant", 3.0),
+            experiment("paused", 0.0),
+        ]);
+
+        assert_eq!(experiments.get(None).unwrap().name, "control");
+        let mut rng = rand::thread_rng();
+        assert_eq!(
+            experiments.assign(Some("paused"), &mut rng).unwrap().name,
+            "paused"
+        );
+        assert!(experiments.assign(Some("missing"), &mut rng).is_none());
+
+        let variant = (0..4000)
+            .filter(|_| experiments.assign(None, &mut rng).unwrap().name == "variant")
+            .count();
+        assert!((2700..3300).contains(&variant), "variant = {}", variant);
+    }
+
+    #[test]
+    fn test_assign_skips_paused_experiments() {
+        let experiments =
+            Experiments::new(vec![experiment("control", 1.0), experiment("variant", 3.0)]);
+        let mut rng = rand::thread_rng();
+
+        experiments.get(Some("variant")).unwrap().set_paused(true);
+        assert!((0..100).all(|_| experiments.assign(None, &mut rng).unwrap().name == "control"));
+        // A paused experiment can still be asked for by name
+        assert_eq!(
+            experiments.assign(Some("variant"), &mut rng).unwrap().name,
+            "variant"
+        );
+
+        experiments.get(Some("control")).unwrap().set_paused(true);
+        assert!(experiments.assign(None, &mut rng).is_none());
+
+        experiments.get(Some("variant")).unwrap().set_paused(false);
+        assert_eq!(experiments.assign(None, &mut rng).unwrap().name, "variant");
+    }
+}
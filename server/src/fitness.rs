@@ -0,0 +1,135 @@
+use shared::{EmpiricalFitness, SurvivalResult};
+
+/// Lineage size at which the spawn component of the score reaches 0.5
+const SPAWN_HALF_SATURATION: f64 = 10.0;
+
+/// Food eaten per creature at which the foraging component reaches 0.5
+const FOOD_HALF_SATURATION: f64 = 50.0;
+
+/// Weights of the survival, spawn and foraging components
+const SURVIVAL_WEIGHT: f64 = 0.5;
+const SPAWN_WEIGHT: f64 = 0.3;
+const FORAGING_WEIGHT: f64 = 0.2;
+
+/// z-score for a two-sided 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// Score a single island outcome for a genome lineage, from 0.0 to 1.0.
+///
+/// Combines the fraction of the lineage still alive at the end of the run,
+/// how many creatures the lineage spawned and how much each of them ate.
+pub fn outcome_score(result: &SurvivalResult) -> f64 {
+    if result.total_spawned == 0 {
+        return 0.0;
+    }
+    let spawned = result.total_spawned as f64;
+
+    let survival = (result.survived as f64 / spawned).min(1.0);
+    let spawn = spawned / (spawned + SPAWN_HALF_SATURATION);
+    let food_per_creature = result.total_food_eaten as f64 / spawned;
+    let foraging = food_per_creature / (food_per_creature + FOOD_HALF_SATURATION);
+
+    SURVIVAL_WEIGHT * survival + SPAWN_WEIGHT * spawn + FORAGING_WEIGHT * foraging
+}
+
+/// Running mean and variance of a genome's outcome scores (Welford's algorithm)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FitnessEstimate {
+    samples: u32,
+    mean: f64,
+    m2: f64,
+}
+
+impl FitnessEstimate {
+    pub fn observe(&mut self, score: f64) {
+        self.samples += 1;
+        let delta = score - self.mean;
+        self.mean += delta / self.samples as f64;
+        self.m2 += delta * (score - self.mean);
+    }
+
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// 95% confidence interval for the mean score. With fewer than two
+    /// samples nothing is known about the spread, so the interval covers
+    /// the whole score range.
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        if self.samples < 2 {
+            return (0.0, 1.0);
+        }
+        let variance = self.m2 / (self.samples - 1) as f64;
+        let half_width = Z_95 * (variance / self.samples as f64).sqrt();
+        (
+            (self.mean - half_width).max(0.0),
+            (self.mean + half_width).min(1.0),
+        )
+    }
+
+    /// Value genomes are ranked by: the lower confidence bound, so that a
+    /// single lucky island does not outrank a consistently good genome
+    pub fn ranking_score(&self) -> f64 {
+        self.confidence_interval().0
+    }
+
+    /// Public view of the estimate, or None if no outcomes have been observed
+    pub fn summary(&self) -> Option<EmpiricalFitness> {
+        if self.samples == 0 {
+            return None;
+        }
+        let (ci_low, ci_high) = self.confidence_interval();
+        Some(EmpiricalFitness {
+            samples: self.samples,
+            mean: self.mean,
+            ci_low,
+            ci_high,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn result(survived: u32, total_spawned: u32, total_food_eaten: u32) -> SurvivalResult {
+        SurvivalResult {
+            genome_id: Uuid::new_v4(),
+            survived,
+            total_spawned,
+            total_food_eaten,
+        }
+    }
+
+    #[test]
+    fn test_outcome_score_rewards_survival_and_growth() {
+        let extinct = outcome_score(&result(0, 1, 0));
+        let survived = outcome_score(&result(1, 1, 0));
+        let thriving = outcome_score(&result(20, 40, 4000));
+
+        assert!(extinct < survived);
+        assert!(survived < thriving);
+        assert!((0.0..=1.0).contains(&thriving));
+        assert_eq!(outcome_score(&result(0, 0, 0)), 0.0);
+    }
+
+    #[test]
+    fn test_estimate_mean_and_interval() {
+        let mut estimate = FitnessEstimate::default();
+        assert!(estimate.summary().is_none());
+
+        estimate.observe(0.4);
+        assert_eq!(estimate.confidence_interval(), (0.0, 1.0));
+
+        for score in [0.6, 0.4, 0.6] {
+            estimate.observe(score);
+        }
+        let summary = estimate.summary().unwrap();
+        assert_eq!(summary.samples, 4);
+        assert!((summary.mean - 0.5).abs() < 1e-12);
+        // sample sd = 0.1155, half width = 1.96 * 0.1155 / 2
+        assert!((summary.ci_low - 0.3868).abs() < 1e-3);
+        assert!((summary.ci_high - 0.6132).abs() < 1e-3);
+    }
+}
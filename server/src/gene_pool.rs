@@ -1,14 +1,90 @@
+use crate::archive::{ArchivedGenome, GenomeArchive};
+use crate::audit::{unix_millis, AuditLog, AuditRecord, PopulationChangeCause};
+use crate::events::{EventKind, ExperimentEvents};
+use crate::eviction::{CapacityConfig, EvictionPolicy};
+use crate::fitness::{outcome_score, FitnessEstimate};
+use crate::genomes::{
+    AncestryNode, GenomeDetail, GenomeListResponse, GenomeOrigin, GenomeQuery, GenomeSort,
+    GenomeSummary, LineageQuery, LineageSummary, SubmissionRecord, MAX_SUBMISSIONS_PER_GENOME,
+};
+use crate::history::{HistoryQuery, HistoryResponse, StatsHistory, StatsSample};
+use crate::selection::{trait_distance, Candidate, SelectionConfig, SelectionStrategy};
+use crate::validation::LEASE_TTL;
+use rand::{Rng, RngCore};
 use shared::{Genome, GenomeWithFitness, GenomeWithId, GlobalStats, SurvivalResult};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// Upper bound on any genome's virtual population
+const MAX_POPULATION: u32 = 10000;
+
+/// Number of seed genomes handed out with each work assignment
+const SEEDS_PER_ASSIGNMENT: usize = 10;
+
+/// Default mutation applied server-side to seed genomes
+const DEFAULT_MUTATION_RATE: f64 = 0.05;
+
+/// Maximum number of living and of extinct genomes offered to the selection strategy
+const CANDIDATE_WINDOW: usize = 256;
+
+/// Extinct genomes sampled when looking for the least novel one to evict
+const NOVELTY_EVICTION_SAMPLES: usize = 16;
+
+/// Living genomes the novelty of an eviction candidate is measured against
+const NOVELTY_REFERENCE_GENOMES: usize = 32;
+
+/// Default age (seconds) below which genomes are never evicted,
+/// so that new genomes get a chance to be tried before they are judged
+const EVICTION_GRACE_SECS: u64 = 3600;
+
 /// Entry in the population-tracked gene pool
 #[derive(Debug, Clone)]
 struct GenomeEntry {
     genome: Genome,
     population: u32, // Virtual population size
+
+    /// How the genome entered the pool
+    origin: GenomeOrigin,
+
+    /// Pool genomes this one descends from
+    parent_ids: Vec<Uuid>,
+
+    /// Oldest known ancestor (the genome itself for seeded genomes)
+    lineage_root: Uuid,
+
+    /// Uptime (seconds) at which the genome entered the pool
+    created_seconds: u64,
+
+    /// Insertion order, used to find the oldest extinct genomes
+    seq: u64,
+
+    /// Whether the genome is a seed out with a client that has not reported
+    /// its results yet; such seeds are not evicted until the lease expires
+    leased: bool,
+
+    /// Score estimated from the island outcomes of this genome and its seeds
+    empirical: FitnessEstimate,
+
+    /// Most recent client results for this genome
+    submissions: VecDeque<SubmissionRecord>,
+}
+
+impl GenomeEntry {
+    fn summary(&self, genome_id: Uuid) -> GenomeSummary {
+        GenomeSummary {
+            genome_id,
+            genome: self.genome.clone(),
+            population: self.population,
+            fitness: self.genome.fitness_score(),
+            empirical: self.empirical.summary(),
+            origin: self.origin,
+            parent_ids: self.parent_ids.clone(),
+            lineage_root: self.lineage_root,
+            created_seconds: self.created_seconds,
+        }
+    }
 }
 
 /// Manages the global gene pool with population tracking
@@ -21,6 +97,36 @@ struct GenePoolInner {
     /// All genomes tracked by ID with their populations
     genomes: HashMap<Uuid, GenomeEntry>,
 
+    /// Every genome ordered by population, for top-N queries and sampling
+    by_population: BTreeSet<(u32, Uuid)>,
+
+    /// Extinct genomes in insertion order
+    extinct_by_age: BTreeSet<(u64, Uuid)>,
+
+    /// Genomes with island outcomes, ordered by empirical ranking score.
+    /// Scores are non-negative, so their bit patterns sort like the floats.
+    by_empirical: BTreeSet<(u64, Uuid)>,
+
+    /// Sequence number for the next inserted genome
+    next_seq: u64,
+
+    /// Maximum pool size and how to choose genomes to evict
+    capacity: Option<CapacityConfig>,
+
+    /// Genomes younger than this (seconds) are never evicted
+    eviction_grace_secs: u64,
+
+    /// Uptime (seconds) before which no genome can become evictable, so a
+    /// pool of protected genomes is not rescanned on every insertion
+    eviction_blocked_until: u64,
+
+    /// Genomes examined by eviction scans
+    #[cfg(test)]
+    eviction_scanned: usize,
+
+    /// Cold storage for evicted genomes
+    archive: Option<GenomeArchive>,
+
     /// Active clients
     active_clients: std::collections::HashSet<Uuid>,
 
@@ -30,33 +136,477 @@ struct GenePoolInner {
 
     /// Server start time
     start_time: std::time::Instant,
-}
 
-impl GenePool {
-    pub fn new() -> Self {
-        let mut genomes = HashMap::new();
+    /// Optional append-only record of every population change
+    audit_log: Option<AuditLog>,
+
+    /// Live feed of population changes, extinctions and new leaders
+    events: Option<ExperimentEvents>,
 
+    /// Periodic samples of the pool since the server started
+    history: StatsHistory,
+
+    /// Strategy used to pick seed genomes for new work
+    selection: Arc<dyn SelectionStrategy>,
+
+    /// Mutation applied to every seed genome handed out
+    mutation_rate: f64,
+}
+
+impl GenePoolInner {
+    /// Add the initial random genomes
+    fn seed(&mut self) {
         // Start with 10 random genomes with initial populations
         for _ in 0..10 {
-            let genome_id = Uuid::new_v4();
-            let genome = Genome::random();
-            genomes.insert(
-                genome_id,
-                GenomeEntry {
-                    genome,
-                    population: 100,
-                },
+            self.insert_genome(
+                Uuid::new_v4(),
+                Genome::random(),
+                GenomeOrigin::Seeded,
+                Vec::new(),
+                100,
+                None,
             );
         }
+    }
+
+    /// Insert a new genome into the pool, recording its ancestry and initial population.
+    /// Parent IDs that are neither in the pool nor in the archive are dropped.
+    fn insert_genome(
+        &mut self,
+        genome_id: Uuid,
+        genome: Genome,
+        origin: GenomeOrigin,
+        mut parent_ids: Vec<Uuid>,
+        population: u32,
+        client_id: Option<Uuid>,
+    ) {
+        parent_ids.retain(|id| {
+            self.genomes.contains_key(id) || self.archive.as_ref().is_some_and(|a| a.contains(id))
+        });
+        let lineage_root = parent_ids
+            .first()
+            .and_then(|id| match self.genomes.get(id) {
+                Some(parent) => Some(parent.lineage_root),
+                None => self
+                    .archive
+                    .as_ref()?
+                    .get(id)
+                    .map(|a| a.summary.lineage_root),
+            })
+            .unwrap_or(genome_id);
+
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(&AuditRecord::GenomeCreated {
+                timestamp_ms: unix_millis(),
+                genome_id,
+                genome: genome.clone(),
+                origin,
+                parent_ids: parent_ids.clone(),
+                client_id,
+            });
+        }
+
+        let entry = GenomeEntry {
+            genome,
+            population,
+            origin,
+            parent_ids,
+            lineage_root,
+            created_seconds: self.start_time.elapsed().as_secs(),
+            seq: self.next_seq,
+            leased: origin == GenomeOrigin::ServerMutated,
+            empirical: FitnessEstimate::default(),
+            submissions: VecDeque::new(),
+        };
+        self.next_seq += 1;
+        self.eviction_blocked_until = self.eviction_blocked_until.min(self.evictable_at(&entry));
+        self.index(genome_id, entry.seq, None, population);
+        self.genomes.insert(genome_id, entry);
+
+        let cause = match origin {
+            GenomeOrigin::Seeded => PopulationChangeCause::Seeded,
+            GenomeOrigin::ServerMutated => PopulationChangeCause::ServerMutation,
+            GenomeOrigin::ClientEvolved => PopulationChangeCause::Ingested,
+            GenomeOrigin::Injected => PopulationChangeCause::Admin,
+        };
+        self.record_population_change(genome_id, 0, population, cause, client_id);
+        self.enforce_capacity();
+    }
+
+    /// Move a genome between the population indexes
+    fn index(&mut self, genome_id: Uuid, seq: u64, old_population: Option<u32>, population: u32) {
+        if let Some(old_population) = old_population {
+            self.by_population.remove(&(old_population, genome_id));
+            if old_population == 0 {
+                self.extinct_by_age.remove(&(seq, genome_id));
+            }
+        }
+        self.by_population.insert((population, genome_id));
+        if population == 0 {
+            self.extinct_by_age.insert((seq, genome_id));
+        }
+    }
+
+    /// Add an island outcome score to a genome's empirical fitness estimate
+    fn observe_outcome(&mut self, genome_id: Uuid, score: f64) {
+        let Some(entry) = self.genomes.get_mut(&genome_id) else {
+            return;
+        };
+        if entry.empirical.samples() > 0 {
+            self.by_empirical
+                .remove(&(entry.empirical.ranking_score().to_bits(), genome_id));
+        }
+        entry.empirical.observe(score);
+        self.by_empirical
+            .insert((entry.empirical.ranking_score().to_bits(), genome_id));
+    }
+
+    /// Evict genomes until the pool is back within capacity
+    fn enforce_capacity(&mut self) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        while self.genomes.len() > capacity.max_genomes {
+            let victim = match capacity.policy {
+                EvictionPolicy::OldestExtinct => None,
+                EvictionPolicy::LeastNovel => self.least_novel_extinct(),
+            };
+            // Fall back to the oldest extinct genome, then the smallest living one.
+            // If every genome is protected the pool stays over capacity for now.
+            let Some(victim) = victim.or_else(|| self.fallback_victim()) else {
+                break;
+            };
+            self.evict(victim);
+        }
+    }
+
+    /// The oldest evictable extinct genome, else the smallest evictable
+    /// living one. When there is none, no scan is made again until the
+    /// first protected genome could become evictable.
+    fn fallback_victim(&mut self) -> Option<Uuid> {
+        let now = self.start_time.elapsed().as_secs();
+        if now < self.eviction_blocked_until {
+            return None;
+        }
+
+        let mut blocked_until = u64::MAX;
+        let candidates = self.extinct_by_age.iter().map(|(_, id)| id).chain(
+            self.by_population
+                .range((1, Uuid::nil())..)
+                .map(|(_, id)| id),
+        );
+        for id in candidates {
+            #[cfg(test)]
+            {
+                self.eviction_scanned += 1;
+            }
+            let Some(entry) = self.genomes.get(id) else {
+                continue;
+            };
+            let evictable_at = self.evictable_at(entry);
+            if evictable_at <= now {
+                return Some(*id);
+            }
+            blocked_until = blocked_until.min(evictable_at);
+        }
+        self.eviction_blocked_until = blocked_until;
+        None
+    }
+
+    /// Uptime (seconds) from which a genome may be evicted: once it is past
+    /// the grace period and, if it is a seed still out with a client, its
+    /// lease has expired
+    fn evictable_at(&self, entry: &GenomeEntry) -> u64 {
+        let protection = if entry.leased {
+            self.eviction_grace_secs.max(LEASE_TTL.as_secs())
+        } else {
+            self.eviction_grace_secs
+        };
+        entry.created_seconds.saturating_add(protection)
+    }
+
+    /// Whether a genome may be evicted now
+    fn evictable(&self, genome_id: &Uuid, now: u64) -> bool {
+        self.genomes
+            .get(genome_id)
+            .is_some_and(|e| self.evictable_at(e) <= now)
+    }
+
+    /// Mark a seed's results as returned, so it no longer needs protecting
+    fn release_lease(&mut self, genome_id: Uuid) {
+        let Some(entry) = self.genomes.get_mut(&genome_id) else {
+            return;
+        };
+        if !entry.leased {
+            return;
+        }
+        entry.leased = false;
+        let evictable_at = self.evictable_at(&self.genomes[&genome_id]);
+        self.eviction_blocked_until = self.eviction_blocked_until.min(evictable_at);
+    }
+
+    /// Of a random sample of evictable extinct genomes, the one
+    /// closest in trait space to the most populous living genomes
+    fn least_novel_extinct(&self) -> Option<Uuid> {
+        let now = self.start_time.elapsed().as_secs();
+        let reference: Vec<&Genome> = self
+            .by_population
+            .iter()
+            .rev()
+            .take_while(|(population, _)| *population > 0)
+            .take(NOVELTY_REFERENCE_GENOMES)
+            .filter_map(|(_, id)| self.genomes.get(id).map(|e| &e.genome))
+            .collect();
+        if reference.is_empty() {
+            return None;
+        }
+
+        let mut rng = rand::thread_rng();
+        (0..NOVELTY_EVICTION_SAMPLES)
+            .filter_map(|_| self.random_extinct(&mut rng))
+            .filter(|id| self.evictable(id, now))
+            .filter_map(|id| self.genomes.get(&id).map(|e| (id, e)))
+            .map(|(id, e)| {
+                let nearest = reference
+                    .iter()
+                    .map(|g| trait_distance(&e.genome, g))
+                    .fold(f64::INFINITY, f64::min);
+                (id, nearest)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    /// Genomes offered to the selection strategy: the most populous living
+    /// genomes plus a random sample of extinct ones, so the cost of selection
+    /// does not grow with the pool
+    fn selection_window(&self) -> Vec<Uuid> {
+        let mut rng = rand::thread_rng();
+        let mut window: Vec<Uuid> = self
+            .by_population
+            .iter()
+            .rev()
+            .take_while(|(population, _)| *population > 0)
+            .take(CANDIDATE_WINDOW)
+            .map(|(_, id)| *id)
+            .collect();
+        let extinct: HashSet<Uuid> = (0..CANDIDATE_WINDOW)
+            .filter_map(|_| self.random_extinct(&mut rng))
+            .collect();
+        window.extend(extinct);
+        window
+    }
+
+    /// A uniformly random extinct genome, found by probing the population
+    /// index at a random ID
+    fn random_extinct(&self, rng: &mut dyn RngCore) -> Option<Uuid> {
+        let probe = Uuid::from_u128(rng.gen());
+        let max = Uuid::from_u128(u128::MAX);
+        self.by_population
+            .range((0, probe)..=(0, max))
+            .next()
+            .or_else(|| self.by_population.range((0, Uuid::nil())..=(0, max)).next())
+            .map(|(_, id)| *id)
+    }
+
+    /// Remove a genome from the pool, moving it to the archive if there is one
+    fn evict(&mut self, genome_id: Uuid) {
+        let Some(entry) = self.genomes.remove(&genome_id) else {
+            return;
+        };
+        self.by_population.remove(&(entry.population, genome_id));
+        self.extinct_by_age.remove(&(entry.seq, genome_id));
+        self.by_empirical
+            .remove(&(entry.empirical.ranking_score().to_bits(), genome_id));
+
+        let archived = match &mut self.archive {
+            Some(archive) => {
+                let record = ArchivedGenome {
+                    summary: entry.summary(genome_id),
+                    archived_seconds: self.start_time.elapsed().as_secs(),
+                    submissions: entry.submissions.into(),
+                };
+                match archive.store(&record) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::warn!(%genome_id, error = %e, "Failed to archive genome");
+                        false
+                    }
+                }
+            }
+            None => false,
+        };
+
+        tracing::debug!(
+            %genome_id,
+            population = entry.population,
+            archived,
+            "Evicted genome"
+        );
+
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(&AuditRecord::GenomeEvicted {
+                timestamp_ms: unix_millis(),
+                genome_id,
+                population: entry.population,
+                archived,
+            });
+        }
+    }
+
+    /// Build the tree of ancestors (`ancestors == true`) or descendants of a genome
+    fn lineage_tree(
+        &self,
+        genome_id: Uuid,
+        depth: usize,
+        ancestors: bool,
+        children: &HashMap<Uuid, Vec<Uuid>>,
+    ) -> Option<AncestryNode> {
+        // Evicted ancestors are read back from the archive
+        let summary = match self.genomes.get(&genome_id) {
+            Some(entry) => entry.summary(genome_id),
+            None => self.archive.as_ref()?.get(&genome_id)?.summary,
+        };
+        let next: &[Uuid] = if ancestors {
+            &summary.parent_ids
+        } else {
+            children.get(&genome_id).map(Vec::as_slice).unwrap_or(&[])
+        };
+        let related = if depth == 0 {
+            Vec::new()
+        } else {
+            next.iter()
+                .filter_map(|id| self.lineage_tree(*id, depth - 1, ancestors, children))
+                .collect()
+        };
+
+        Some(AncestryNode {
+            genome_id,
+            origin: summary.origin,
+            population: summary.population,
+            created_seconds: summary.created_seconds,
+            related,
+        })
+    }
+
+    /// Change the population of an existing genome.
+    /// Returns the previous population, or None if the genome is unknown.
+    fn set_population(
+        &mut self,
+        genome_id: Uuid,
+        population: u32,
+        cause: PopulationChangeCause,
+        client_id: Option<Uuid>,
+    ) -> Option<u32> {
+        let entry = self.genomes.get_mut(&genome_id)?;
+        let old_population = entry.population;
+        let seq = entry.seq;
+        entry.population = population;
+        self.index(genome_id, seq, Some(old_population), population);
+        self.record_population_change(genome_id, old_population, population, cause, client_id);
+        Some(old_population)
+    }
+
+    fn record_population_change(
+        &self,
+        genome_id: Uuid,
+        old_population: u32,
+        new_population: u32,
+        cause: PopulationChangeCause,
+        client_id: Option<Uuid>,
+    ) {
+        tracing::debug!(
+            %genome_id,
+            old_population,
+            new_population,
+            ?cause,
+            client_id = client_id.map(tracing::field::display),
+            "Population changed"
+        );
+
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(&AuditRecord::PopulationChanged {
+                timestamp_ms: unix_millis(),
+                genome_id,
+                old_population,
+                new_population,
+                cause,
+                client_id,
+            });
+        }
+
+        if let Some(events) = &self.events {
+            events.emit(EventKind::PopulationChanged {
+                genome_id,
+                old_population,
+                new_population,
+                cause,
+            });
+            if old_population > 0 && new_population == 0 {
+                events.emit(EventKind::Extinction { genome_id });
+            }
+        }
+    }
+
+    /// Genome leading the empirical fitness ranking
+    fn best_empirical(&self) -> Option<Uuid> {
+        self.by_empirical.last().map(|(_, id)| *id)
+    }
+}
+
+/// Construction options for a [`GenePool`]
+#[derive(Default)]
+pub struct GenePoolOptions {
+    /// Append every population change to this log
+    pub audit_log: Option<AuditLog>,
+
+    /// Strategy used to pick seed genomes for new work
+    pub selection: SelectionConfig,
+
+    /// Limit on the number of genomes kept in memory (unbounded if None)
+    pub capacity: Option<CapacityConfig>,
+
+    /// Where evicted genomes are kept; without one they are discarded
+    pub archive: Option<GenomeArchive>,
+
+    /// Publish population changes to the live event feed
+    pub events: Option<ExperimentEvents>,
+}
+
+impl GenePool {
+    /// Create a gene pool with default options
+    #[allow(dead_code)]
+    pub fn new() -> Self {
+        Self::with_options(GenePoolOptions::default())
+    }
+
+    pub fn with_options(options: GenePoolOptions) -> Self {
+        let mut inner = GenePoolInner {
+            genomes: HashMap::new(),
+            by_population: BTreeSet::new(),
+            extinct_by_age: BTreeSet::new(),
+            by_empirical: BTreeSet::new(),
+            next_seq: 0,
+            capacity: options.capacity,
+            eviction_grace_secs: EVICTION_GRACE_SECS,
+            eviction_blocked_until: 0,
+            #[cfg(test)]
+            eviction_scanned: 0,
+            archive: options.archive,
+            active_clients: std::collections::HashSet::new(),
+            total_work_units: 0,
+            total_simulations: 0,
+            start_time: std::time::Instant::now(),
+            audit_log: options.audit_log,
+            events: options.events,
+            history: StatsHistory::default(),
+            selection: options.selection.build(),
+            mutation_rate: DEFAULT_MUTATION_RATE,
+        };
+        inner.seed();
 
         Self {
-            inner: Arc::new(RwLock::new(GenePoolInner {
-                genomes,
-                active_clients: std::collections::HashSet::new(),
-                total_work_units: 0,
-                total_simulations: 0,
-                start_time: std::time::Instant::now(),
-            })),
+            inner: Arc::new(RwLock::new(inner)),
         }
     }
 
@@ -65,59 +615,55 @@ impl GenePool {
     pub async fn get_seed_genomes_spatial(&self) -> Vec<GenomeWithId> {
         let inner = self.inner.read().await;
 
-        // Separate living and extinct genomes
-        let mut living: Vec<_> = inner
-            .genomes
-            .values()
-            .filter(|e| e.population > 0)
-            .collect();
-        let extinct: Vec<_> = inner
-            .genomes
-            .values()
-            .filter(|e| e.population == 0)
-            .collect();
+        let window = inner.selection_window();
 
-        // Sort living by population
-        living.sort_by(|a, b| b.population.cmp(&a.population));
+        // Population is the selection signal: it grows with every surviving island
+        let candidates: Vec<Candidate<'_>> = window
+            .iter()
+            .filter_map(|id| inner.genomes.get(id).map(|e| (id, e)))
+            .map(|(id, e)| Candidate {
+                genome_id: *id,
+                genome: &e.genome,
+                population: e.population,
+                fitness: e.population as f64,
+            })
+            .collect();
 
-        println!(
-            "Gene pool: {} living (pop range: {}-{}), {} extinct, {} total",
-            living.len(),
-            living.last().map(|e| e.population).unwrap_or(0),
-            living.first().map(|e| e.population).unwrap_or(0),
-            extinct.len(),
-            inner.genomes.len()
+        let picks =
+            inner
+                .selection
+                .select(&candidates, SEEDS_PER_ASSIGNMENT, &mut rand::thread_rng());
+
+        tracing::debug!(
+            strategy = inner.selection.name(),
+            living = candidates.iter().filter(|c| c.population > 0).count(),
+            total = candidates.len(),
+            picked = picks.len(),
+            "Selected seed genomes"
         );
 
-        // Build base selection: 5 living + 5 extinct
-        let mut base: Vec<Genome> = living.iter().take(5).map(|e| e.genome.clone()).collect();
-        use rand::seq::SliceRandom;
-        let mut extinct_pick: Vec<Genome> = {
-            let mut rng = rand::thread_rng();
-            extinct
-                .choose_multiple(&mut rng, 5)
-                .map(|e| e.genome.clone())
-                .collect()
-        };
-        base.append(&mut extinct_pick);
+        // Remember which pool genome each seed came from
+        let mut base: Vec<(Option<Uuid>, Genome)> = picks
+            .into_iter()
+            .filter_map(|id| inner.genomes.get(&id).map(|e| (Some(id), e.genome.clone())))
+            .collect();
+        let mutation_rate = inner.mutation_rate;
         drop(inner); // release read lock
 
-        while base.len() < 10 {
-            base.push(Genome::random());
-        }
-        if base.len() > 10 {
-            base.truncate(10);
+        while base.len() < SEEDS_PER_ASSIGNMENT {
+            base.push((None, Genome::random()));
         }
+        base.truncate(SEEDS_PER_ASSIGNMENT);
 
         // Mutate seeds server-side
-        const SERVER_MUT_RATE: f64 = 0.05;
-        let mut out: Vec<GenomeWithId> = Vec::with_capacity(10);
-        let mut new_entries: Vec<(Uuid, Genome)> = Vec::with_capacity(10);
-        for g in base.into_iter() {
+        let mut out: Vec<GenomeWithId> = Vec::with_capacity(SEEDS_PER_ASSIGNMENT);
+        let mut new_entries: Vec<(Uuid, Option<Uuid>, Genome)> =
+            Vec::with_capacity(SEEDS_PER_ASSIGNMENT);
+        for (parent_id, g) in base.into_iter() {
             let mut mg = g.clone();
-            mg.mutate(SERVER_MUT_RATE);
+            mg.mutate(mutation_rate);
             let id = Uuid::new_v4();
-            new_entries.push((id, mg.clone()));
+            new_entries.push((id, parent_id, mg.clone()));
             out.push(GenomeWithId {
                 genome_id: id,
                 genome: mg,
@@ -126,13 +672,14 @@ impl GenePool {
 
         // Insert mutated genomes into pool with population 0
         let mut inner_write = self.inner.write().await;
-        for (genome_id, genome) in new_entries {
-            inner_write.genomes.insert(
+        for (genome_id, parent_id, genome) in new_entries {
+            inner_write.insert_genome(
                 genome_id,
-                GenomeEntry {
-                    genome,
-                    population: 0,
-                },
+                genome,
+                GenomeOrigin::ServerMutated,
+                parent_id.into_iter().collect(),
+                0,
+                None,
             );
         }
 
@@ -147,15 +694,15 @@ impl GenePool {
         let mut seeds = Vec::new();
 
         // Take living genomes sorted by population
-        let mut living: Vec<_> = inner
-            .genomes
-            .values()
-            .filter(|e| e.population > 0)
-            .collect();
-
-        living.sort_by(|a, b| b.population.cmp(&a.population));
+        let living = inner
+            .by_population
+            .iter()
+            .rev()
+            .take_while(|(population, _)| *population > 0)
+            .take(count)
+            .filter_map(|(_, id)| inner.genomes.get(id));
 
-        for entry in living.iter().take(count) {
+        for entry in living {
             seeds.push(entry.genome.clone());
         }
 
@@ -174,94 +721,139 @@ impl GenePool {
         survival_results: Vec<SurvivalResult>,
         steps_completed: u32,
         best_genomes: Vec<GenomeWithFitness>,
+        trust: f64,
     ) {
         let mut inner = self.inner.write().await;
 
         inner.total_work_units += 1;
         inner.total_simulations += steps_completed as u64;
         inner.active_clients.insert(client_id);
+        let previous_best = inner.best_empirical();
 
         // Update populations based on survival
-        let mut population_changes = Vec::new();
         for result in survival_results {
-            if let Some(entry) = inner.genomes.get_mut(&result.genome_id) {
-                let old_pop = entry.population;
-                if result.survived > 0 {
-                    // Survivors: boost population
-                    entry.population = entry.population.saturating_add(result.survived * 10);
-                    population_changes.push((
-                        result.genome_id,
-                        old_pop,
-                        entry.population,
-                        result.survived,
-                    ));
-                } else {
-                    // Extinct: reduce population
-                    entry.population = entry.population.saturating_sub(20);
-                    population_changes.push((result.genome_id, old_pop, entry.population, 0));
-                }
-            } else {
+            let Some(old_population) = inner.genomes.get(&result.genome_id).map(|e| e.population)
+            else {
                 // Unknown genome - this shouldn't happen but handle gracefully
-                eprintln!(
-                    "Warning: Received results for unknown genome {}",
-                    result.genome_id
+                tracing::warn!(
+                    genome_id = %result.genome_id,
+                    %client_id,
+                    "Received results for unknown genome"
                 );
+                continue;
+            };
+
+            // Changes are scaled by how much the reporting client is trusted
+            let (new_population, cause) = if result.survived > 0 {
+                // Survivors: boost population
+                (
+                    old_population
+                        .saturating_add(scaled(result.survived.saturating_mul(10), trust)),
+                    PopulationChangeCause::Survived,
+                )
+            } else {
+                // Extinct: reduce population
+                (
+                    old_population.saturating_sub(scaled(20, trust)),
+                    PopulationChangeCause::Extinct,
+                )
+            };
+
+            inner.set_population(result.genome_id, new_population, cause, Some(client_id));
+
+            // Limit max population to prevent overflow
+            if new_population > MAX_POPULATION {
+                inner.set_population(
+                    result.genome_id,
+                    MAX_POPULATION,
+                    PopulationChangeCause::PopulationCap,
+                    Some(client_id),
+                );
+            }
+
+            // Seeds are lightly mutated copies of a pool genome, so each island
+            // outcome also counts toward the estimate of the genome it came from
+            let score = outcome_score(&result);
+            let parent_ids = inner
+                .genomes
+                .get(&result.genome_id)
+                .map(|e| e.parent_ids.clone())
+                .unwrap_or_default();
+            inner.observe_outcome(result.genome_id, score);
+            for parent_id in parent_ids {
+                inner.observe_outcome(parent_id, score);
+            }
+
+            inner.release_lease(result.genome_id);
+            if let Some(entry) = inner.genomes.get_mut(&result.genome_id) {
+                if entry.submissions.len() >= MAX_SUBMISSIONS_PER_GENOME {
+                    entry.submissions.pop_front();
+                }
+                entry.submissions.push_back(SubmissionRecord {
+                    timestamp_ms: unix_millis(),
+                    client_id,
+                    survived: result.survived,
+                    total_spawned: result.total_spawned,
+                    total_food_eaten: result.total_food_eaten,
+                    old_population,
+                    new_population: new_population.min(MAX_POPULATION),
+                    trust,
+                });
             }
         }
 
-        if !population_changes.is_empty() {
-            println!("Population updates:");
-            for (id, old_pop, new_pop, survived) in population_changes {
-                println!(
-                    "  {} {} → {} (survived: {})",
-                    &id.to_string()[..8],
-                    old_pop,
-                    new_pop,
-                    survived
-                );
+        if let (Some(events), Some(best)) = (&inner.events, inner.best_empirical()) {
+            if previous_best != Some(best) {
+                if let Some(empirical) =
+                    inner.genomes.get(&best).and_then(|e| e.empirical.summary())
+                {
+                    events.emit(EventKind::NewBest {
+                        genome_id: best,
+                        empirical,
+                    });
+                }
             }
         }
 
         // Ingest reported best genomes as new entries
         // Start them at 150 (above initial 100) since they're proven performers from simulations
         // This ensures they enter the top-5 selection pool immediately
+        // (less for clients that aren't fully trusted)
+        let evolved_population = scaled(150, trust).max(1);
         if !best_genomes.is_empty() {
             let min_fitness = best_genomes
                 .iter()
                 .map(|g| g.fitness)
-                .min_by(|a, b| a.partial_cmp(b).unwrap())
+                .min_by(|a, b| a.total_cmp(b))
                 .unwrap_or(0.0);
             let max_fitness = best_genomes
                 .iter()
                 .map(|g| g.fitness)
-                .max_by(|a, b| a.partial_cmp(b).unwrap())
+                .max_by(|a, b| a.total_cmp(b))
                 .unwrap_or(0.0);
 
-            println!(
-                "Ingested {} evolved genomes with population=150 (fitness range: {:.3}-{:.3})",
-                best_genomes.len(),
+            tracing::info!(
+                count = best_genomes.len(),
+                population = evolved_population,
                 min_fitness,
-                max_fitness
+                max_fitness,
+                %client_id,
+                "Ingested evolved genomes"
             );
         }
 
         for gwf in best_genomes {
-            let id = Uuid::new_v4();
-            inner.genomes.insert(
-                id,
-                GenomeEntry {
-                    genome: gwf.genome,
-                    population: 150, // Start above initial (100) since these are proven performers
-                },
+            // Start above initial (100) since these are proven performers
+            // The reported ID names the seed lineage the genome evolved from
+            inner.insert_genome(
+                Uuid::new_v4(),
+                gwf.genome,
+                GenomeOrigin::ClientEvolved,
+                gwf.genome_id.into_iter().collect(),
+                evolved_population,
+                Some(client_id),
             );
         }
-
-        // Limit max population to prevent overflow
-        for entry in inner.genomes.values_mut() {
-            if entry.population > 10000 {
-                entry.population = 10000;
-            }
-        }
     }
 
     /// Submit results (legacy method for backwards compatibility)
@@ -284,16 +876,29 @@ impl GenePool {
     pub async fn get_stats(&self) -> GlobalStats {
         let inner = self.inner.read().await;
 
-        // Get top genomes by population
-        let mut entries: Vec<_> = inner.genomes.values().collect();
-        entries.sort_by(|a, b| b.population.cmp(&a.population));
-
-        let best_genomes: Vec<GenomeWithFitness> = entries
+        // Rank genomes by empirical fitness, filling any remaining places with
+        // the most populous genomes that have no island outcomes yet
+        let ranked = inner.by_empirical.iter().rev().map(|(_, id)| id);
+        let unranked = inner
+            .by_population
             .iter()
+            .rev()
+            .map(|(_, id)| id)
+            .filter(|id| {
+                inner
+                    .genomes
+                    .get(id)
+                    .is_some_and(|e| e.empirical.samples() == 0)
+            });
+        let best_genomes: Vec<GenomeWithFitness> = ranked
+            .chain(unranked)
             .take(10)
-            .map(|e| GenomeWithFitness {
+            .filter_map(|id| inner.genomes.get(id).map(|e| (id, e)))
+            .map(|(id, e)| GenomeWithFitness {
                 genome: e.genome.clone(),
-                fitness: e.genome.fitness_score(), // For display purposes
+                fitness: e.genome.fitness_score(), // For comparison with the empirical estimate
+                genome_id: Some(*id),
+                empirical: e.empirical.summary(),
             })
             .collect();
 
@@ -308,16 +913,229 @@ impl GenePool {
         }
     }
 
+    /// Record a snapshot of the current pool into the stats history
+    pub async fn record_sample(&self) {
+        let mut inner = self.inner.write().await;
+        let sample = StatsSample::from_entries(
+            inner.start_time.elapsed().as_secs(),
+            unix_millis(),
+            inner
+                .genomes
+                .iter()
+                .map(|(id, e)| (id, &e.genome, e.population)),
+        );
+        inner.history.push(sample);
+    }
+
+    /// Read back recorded samples
+    pub async fn get_history(&self, query: &HistoryQuery) -> HistoryResponse {
+        let inner = self.inner.read().await;
+        inner.history.query(query)
+    }
+
+    /// List genomes in the pool with filtering, sorting and paging
+    pub async fn list_genomes(&self, query: &GenomeQuery) -> GenomeListResponse {
+        let inner = self.inner.read().await;
+
+        let mut summaries: Vec<GenomeSummary> = inner
+            .genomes
+            .iter()
+            .filter(|(_, e)| query.status.matches(e.population))
+            .map(|(id, e)| e.summary(*id))
+            .collect();
+
+        match query.sort {
+            GenomeSort::Population => {
+                summaries.sort_by_key(|s| std::cmp::Reverse(s.population));
+            }
+            GenomeSort::Fitness => {
+                summaries.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+            }
+            GenomeSort::Empirical => {
+                // Genomes without island outcomes sort last
+                let key = |s: &GenomeSummary| s.empirical.map_or(-1.0, |e| e.ci_low);
+                summaries.sort_by(|a, b| key(b).total_cmp(&key(a)));
+            }
+        }
+
+        let per_page = query.per_page();
+        let total = summaries.len();
+        let genomes = summaries
+            .into_iter()
+            .skip(query.page.saturating_mul(per_page))
+            .take(per_page)
+            .collect();
+
+        GenomeListResponse {
+            total,
+            page: query.page,
+            per_page,
+            genomes,
+        }
+    }
+
+    /// Full detail for a single genome, or None if it is neither in the pool
+    /// nor in the archive
+    pub async fn get_genome(&self, genome_id: Uuid) -> Option<GenomeDetail> {
+        let inner = self.inner.read().await;
+        let Some(entry) = inner.genomes.get(&genome_id) else {
+            let archived = inner.archive.as_ref()?.get(&genome_id)?;
+            return Some(GenomeDetail {
+                population_history: inner
+                    .history
+                    .population_history(genome_id, archived.summary.created_seconds),
+                summary: archived.summary,
+                submissions: archived.submissions,
+                archived: true,
+            });
+        };
+
+        Some(GenomeDetail {
+            summary: entry.summary(genome_id),
+            population_history: inner
+                .history
+                .population_history(genome_id, entry.created_seconds),
+            submissions: entry.submissions.iter().cloned().collect(),
+            archived: false,
+        })
+    }
+
+    /// Ancestors of a genome up to `depth` generations back
+    pub async fn get_ancestry(&self, genome_id: Uuid, depth: usize) -> Option<AncestryNode> {
+        let inner = self.inner.read().await;
+        inner.lineage_tree(genome_id, depth, true, &HashMap::new())
+    }
+
+    /// Descendants of a genome up to `depth` generations forward
+    pub async fn get_descendants(&self, genome_id: Uuid, depth: usize) -> Option<AncestryNode> {
+        let inner = self.inner.read().await;
+
+        let mut children: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (id, entry) in &inner.genomes {
+            for parent_id in &entry.parent_ids {
+                children.entry(*parent_id).or_default().push(*id);
+            }
+        }
+
+        inner.lineage_tree(genome_id, depth, false, &children)
+    }
+
+    /// Lineages (genomes grouped by their oldest ancestor), largest population first
+    pub async fn get_lineages(&self, query: &LineageQuery) -> Vec<LineageSummary> {
+        let inner = self.inner.read().await;
+
+        let mut lineages: HashMap<Uuid, LineageSummary> = HashMap::new();
+        let mut pool_population: u64 = 0;
+        for entry in inner.genomes.values() {
+            pool_population += entry.population as u64;
+            let lineage = lineages
+                .entry(entry.lineage_root)
+                .or_insert_with(|| LineageSummary {
+                    lineage_root: entry.lineage_root,
+                    genomes: 0,
+                    living_genomes: 0,
+                    total_population: 0,
+                    population_share: 0.0,
+                });
+            lineage.genomes += 1;
+            lineage.total_population += entry.population as u64;
+            if entry.population > 0 {
+                lineage.living_genomes += 1;
+            }
+        }
+
+        let mut lineages: Vec<LineageSummary> = lineages
+            .into_values()
+            .filter(|l| query.include_extinct || l.living_genomes > 0)
+            .map(|mut l| {
+                if pool_population > 0 {
+                    l.population_share = l.total_population as f64 / pool_population as f64;
+                }
+                l
+            })
+            .collect();
+        lineages.sort_by_key(|l| std::cmp::Reverse(l.total_population));
+        lineages.truncate(query.limit.unwrap_or(20));
+        lineages
+    }
+
     /// Register a client as active
     pub async fn register_client(&self, client_id: Uuid) {
         let mut inner = self.inner.write().await;
         inner.active_clients.insert(client_id);
     }
+
+    /// Add a hand-written genome to the pool
+    pub async fn inject_genome(&self, genome: Genome, population: u32) -> Uuid {
+        let mut inner = self.inner.write().await;
+        let genome_id = Uuid::new_v4();
+        inner.insert_genome(
+            genome_id,
+            genome,
+            GenomeOrigin::Injected,
+            Vec::new(),
+            population.min(MAX_POPULATION),
+            None,
+        );
+        genome_id
+    }
+
+    /// Set a genome's population directly (culling or boosting it).
+    /// Returns the previous and new population, or None if the genome is not in the pool.
+    pub async fn set_population(&self, genome_id: Uuid, population: u32) -> Option<(u32, u32)> {
+        let mut inner = self.inner.write().await;
+        let population = population.min(MAX_POPULATION);
+        inner
+            .set_population(genome_id, population, PopulationChangeCause::Admin, None)
+            .map(|old_population| (old_population, population))
+    }
+
+    /// Work units completed since the pool started or was reset
+    pub async fn total_work_units(&self) -> u64 {
+        self.inner.read().await.total_work_units
+    }
+
+    /// Mutation rate applied to seed genomes
+    pub async fn mutation_rate(&self) -> f64 {
+        self.inner.read().await.mutation_rate
+    }
+
+    pub async fn set_mutation_rate(&self, mutation_rate: f64) {
+        self.inner.write().await.mutation_rate = mutation_rate;
+    }
+
+    /// Discard every genome and all statistics, then reseed the pool.
+    /// Archived genomes are kept.
+    pub async fn reset(&self) {
+        let mut inner = self.inner.write().await;
+        inner.genomes.clear();
+        inner.by_population.clear();
+        inner.extinct_by_age.clear();
+        inner.by_empirical.clear();
+        inner.active_clients.clear();
+        inner.total_work_units = 0;
+        inner.total_simulations = 0;
+        inner.history = StatsHistory::default();
+        inner.seed();
+    }
+
+    /// Append a record to the pool's audit log, if it has one
+    pub async fn audit(&self, record: &AuditRecord) {
+        if let Some(audit_log) = &self.inner.read().await.audit_log {
+            audit_log.record(record);
+        }
+    }
+}
+
+/// Scale a population change by client trust (0 to 1)
+fn scaled(change: u32, trust: f64) -> u32 {
+    (change as f64 * trust.clamp(0.0, 1.0)).round() as u32
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::genomes::GenomeStatus;
 
     #[tokio::test]
     async fn test_gene_pool_creation() {
@@ -352,7 +1170,7 @@ mod tests {
             total_food_eaten: 500,
         }];
 
-        pool.submit_survival_results(client_id, results, 3000, Vec::new())
+        pool.submit_survival_results(client_id, results, 3000, Vec::new(), 1.0)
             .await;
 
         let stats = pool.get_stats().await;
@@ -382,7 +1200,7 @@ mod tests {
             total_food_eaten: 300,
         }];
 
-        pool.submit_survival_results(client_id, results, 3000, Vec::new())
+        pool.submit_survival_results(client_id, results, 3000, Vec::new(), 1.0)
             .await;
 
         // Check population increased
@@ -394,6 +1212,43 @@ mod tests {
         assert!(new_pop > initial_pop);
     }
 
+    #[tokio::test]
+    async fn test_population_change_scaled_by_trust() {
+        let pool = GenePool::new();
+        let seeds = pool.get_seed_genomes_spatial().await;
+        let population = |pool: &GenePool, id: Uuid| {
+            let pool = pool.clone();
+            async move { pool.inner.read().await.genomes.get(&id).unwrap().population }
+        };
+        let before = population(&pool, seeds[0].genome_id).await;
+
+        let results = vec![
+            SurvivalResult {
+                genome_id: seeds[0].genome_id,
+                survived: 3,
+                total_spawned: 5,
+                total_food_eaten: 300,
+            },
+            SurvivalResult {
+                genome_id: seeds[1].genome_id,
+                survived: 0,
+                total_spawned: 1,
+                total_food_eaten: 0,
+            },
+        ];
+        pool.submit_survival_results(Uuid::new_v4(), results, 3000, Vec::new(), 0.5)
+            .await;
+
+        // Half of the +30 and -20 a fully trusted client would cause
+        assert_eq!(population(&pool, seeds[0].genome_id).await, before + 15);
+        let detail = pool.get_genome(seeds[1].genome_id).await.unwrap();
+        assert_eq!(
+            detail.summary.population,
+            detail.submissions[0].old_population.saturating_sub(10)
+        );
+        assert_eq!(detail.submissions[0].trust, 0.5);
+    }
+
     #[tokio::test]
     async fn test_extinction() {
         let pool = GenePool::new();
@@ -410,7 +1265,7 @@ mod tests {
             total_food_eaten: 0,
         }];
 
-        pool.submit_survival_results(client_id, results, 3000, Vec::new())
+        pool.submit_survival_results(client_id, results, 3000, Vec::new(), 1.0)
             .await;
 
         // Check population decreased
@@ -422,4 +1277,460 @@ mod tests {
         // Population should have decreased by 20
         assert!(new_pop < 100);
     }
+
+    #[tokio::test]
+    async fn test_audit_log_records_population_changes() {
+        let path = std::env::temp_dir().join(format!("evo-pool-audit-{}.jsonl", Uuid::new_v4()));
+        let pool = GenePool::with_options(GenePoolOptions {
+            audit_log: Some(AuditLog::open(&path).unwrap()),
+            ..Default::default()
+        });
+        let client_id = Uuid::new_v4();
+
+        let seeds = pool.get_seed_genomes_spatial().await;
+        let results = vec![SurvivalResult {
+            genome_id: seeds[0].genome_id,
+            survived: 2,
+            total_spawned: 4,
+            total_food_eaten: 100,
+        }];
+        pool.submit_survival_results(client_id, results, 3000, Vec::new(), 1.0)
+            .await;
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let (created, records): (Vec<serde_json::Value>, Vec<serde_json::Value>) = contents
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+            .partition(|r| r["event"] == "genome_created");
+
+        assert_eq!(created.len(), 20);
+        // 10 seeded + 10 server-mutated + 1 survival update
+        assert_eq!(records.len(), 21);
+        assert_eq!(records[0]["cause"], "seeded");
+        let last = records.last().unwrap();
+        assert_eq!(last["cause"], "survived");
+        assert_eq!(last["old_population"], 0);
+        assert_eq!(last["new_population"], 20);
+        assert_eq!(last["client_id"], client_id.to_string());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_record_sample() {
+        let pool = GenePool::new();
+        pool.record_sample().await;
+        pool.record_sample().await;
+
+        let history = pool
+            .get_history(&HistoryQuery {
+                populations: true,
+                ..Default::default()
+            })
+            .await;
+
+        assert_eq!(history.total_samples, 2);
+        let sample = &history.samples[0];
+        assert_eq!(sample.living, 10);
+        assert_eq!(sample.total_population, 1000);
+        assert_eq!(sample.populations.as_ref().unwrap().len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_genome_detail_tracks_parent_and_submissions() {
+        let pool = GenePool::new();
+        let client_id = Uuid::new_v4();
+
+        let seeds = pool.get_seed_genomes_spatial().await;
+        let genome_id = seeds[0].genome_id;
+        let results = vec![SurvivalResult {
+            genome_id,
+            survived: 1,
+            total_spawned: 3,
+            total_food_eaten: 40,
+        }];
+        pool.submit_survival_results(client_id, results, 3000, Vec::new(), 1.0)
+            .await;
+        pool.record_sample().await;
+
+        let detail = pool.get_genome(genome_id).await.unwrap();
+        assert_eq!(detail.summary.population, 10);
+        // The top living genomes are seeded first, so the first seed has a parent
+        assert_eq!(detail.summary.origin, GenomeOrigin::ServerMutated);
+        let parent_id = detail.summary.parent_ids[0];
+        assert!(pool.get_genome(parent_id).await.is_some());
+        assert_eq!(detail.submissions.len(), 1);
+        assert_eq!(detail.submissions[0].client_id, client_id);
+        assert_eq!(detail.population_history.last().unwrap().population, 10);
+
+        assert!(pool.get_genome(Uuid::new_v4()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_genomes_filters_and_pages() {
+        let pool = GenePool::new();
+        pool.get_seed_genomes_spatial().await;
+
+        let living = pool
+            .list_genomes(&GenomeQuery {
+                status: GenomeStatus::Living,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(living.total, 10);
+        assert!(living.genomes.iter().all(|g| g.population > 0));
+
+        let extinct_page = pool
+            .list_genomes(&GenomeQuery {
+                status: GenomeStatus::Extinct,
+                per_page: Some(4),
+                page: 2,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(extinct_page.total, 10);
+        assert_eq!(extinct_page.genomes.len(), 2);
+
+        let by_fitness = pool
+            .list_genomes(&GenomeQuery {
+                sort: GenomeSort::Fitness,
+                ..Default::default()
+            })
+            .await;
+        assert!(by_fitness
+            .genomes
+            .windows(2)
+            .all(|w| w[0].fitness >= w[1].fitness));
+    }
+
+    #[tokio::test]
+    async fn test_ancestry_and_lineages() {
+        let pool = GenePool::new();
+        let client_id = Uuid::new_v4();
+
+        let seeds = pool.get_seed_genomes_spatial().await;
+        let seed_id = seeds[0].genome_id;
+        let evolved = GenomeWithFitness {
+            genome: seeds[0].genome.clone(),
+            fitness: 1.0,
+            empirical: None,
+            genome_id: Some(seed_id),
+        };
+        pool.submit_survival_results(client_id, Vec::new(), 3000, vec![evolved], 1.0)
+            .await;
+
+        let evolved_id = {
+            let inner = pool.inner.read().await;
+            *inner
+                .genomes
+                .iter()
+                .find(|(_, e)| e.origin == GenomeOrigin::ClientEvolved)
+                .unwrap()
+                .0
+        };
+
+        // evolved -> server-mutated seed -> seeded root
+        let ancestry = pool.get_ancestry(evolved_id, 10).await.unwrap();
+        assert_eq!(ancestry.origin, GenomeOrigin::ClientEvolved);
+        let seed_node = &ancestry.related[0];
+        assert_eq!(seed_node.genome_id, seed_id);
+        let root_node = &seed_node.related[0];
+        assert_eq!(root_node.origin, GenomeOrigin::Seeded);
+        assert!(root_node.related.is_empty());
+
+        let descendants = pool.get_descendants(root_node.genome_id, 10).await.unwrap();
+        assert_eq!(descendants.related[0].genome_id, seed_id);
+        assert_eq!(descendants.related[0].related[0].genome_id, evolved_id);
+
+        let detail = pool.get_genome(evolved_id).await.unwrap();
+        assert_eq!(detail.summary.lineage_root, root_node.genome_id);
+
+        let lineages = pool.get_lineages(&LineageQuery::default()).await;
+        assert_eq!(lineages.len(), 10);
+        assert_eq!(lineages[0].lineage_root, root_node.genome_id);
+        let share: f64 = lineages.iter().map(|l| l.population_share).sum();
+        assert!((share - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_parent_is_dropped() {
+        let pool = GenePool::new();
+        let evolved = GenomeWithFitness {
+            genome: Genome::random(),
+            fitness: 1.0,
+            empirical: None,
+            genome_id: Some(Uuid::new_v4()),
+        };
+        pool.submit_survival_results(Uuid::new_v4(), Vec::new(), 3000, vec![evolved], 1.0)
+            .await;
+
+        let inner = pool.inner.read().await;
+        let (id, entry) = inner
+            .genomes
+            .iter()
+            .find(|(_, e)| e.origin == GenomeOrigin::ClientEvolved)
+            .unwrap();
+        assert!(entry.parent_ids.is_empty());
+        assert_eq!(entry.lineage_root, *id);
+    }
+
+    #[tokio::test]
+    async fn test_configured_selection_strategy() {
+        let pool = GenePool::with_options(GenePoolOptions {
+            selection: SelectionConfig::Tournament { size: 2 },
+            ..Default::default()
+        });
+
+        let seeds = pool.get_seed_genomes_spatial().await;
+        assert_eq!(seeds.len(), SEEDS_PER_ASSIGNMENT);
+
+        // Tournament always picks from the pool, so every seed has a parent
+        for seed in seeds {
+            let detail = pool.get_genome(seed.genome_id).await.unwrap();
+            assert_eq!(detail.summary.parent_ids.len(), 1);
+        }
+    }
+
+    impl GenePoolInner {
+        /// Let genomes be evicted as soon as they are no longer leased
+        fn without_grace_period(&mut self) {
+            self.eviction_grace_secs = 0;
+            self.eviction_blocked_until = 0;
+        }
+    }
+
+    /// Results for the given seeds, alive or extinct
+    fn seed_results(seeds: &[GenomeWithId], survived: u32) -> Vec<SurvivalResult> {
+        seeds
+            .iter()
+            .map(|s| SurvivalResult {
+                genome_id: s.genome_id,
+                survived,
+                total_spawned: 1,
+                total_food_eaten: 10,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_oldest_extinct_to_archive() {
+        let path = std::env::temp_dir().join(format!("evo-pool-archive-{}.jsonl", Uuid::new_v4()));
+        let pool = GenePool::with_options(GenePoolOptions {
+            capacity: Some(CapacityConfig {
+                max_genomes: 15,
+                policy: EvictionPolicy::OldestExtinct,
+            }),
+            archive: Some(GenomeArchive::open(&path).unwrap()),
+            ..Default::default()
+        });
+
+        // 10 seeded + 10 new seeds: nothing is old enough to be evicted yet
+        let seeds = pool.get_seed_genomes_spatial().await;
+        {
+            let mut inner = pool.inner.write().await;
+            assert_eq!(inner.genomes.len(), 20);
+            assert_eq!(inner.archive.as_ref().unwrap().len(), 0);
+            inner.without_grace_period();
+        }
+
+        // The first 5 seeds come back extinct and are evicted, oldest first
+        pool.submit_survival_results(
+            Uuid::new_v4(),
+            seed_results(&seeds[..5], 0),
+            3000,
+            Vec::new(),
+            1.0,
+        )
+        .await;
+        {
+            let mut inner = pool.inner.write().await;
+            inner.enforce_capacity();
+            assert_eq!(inner.genomes.len(), 15);
+            assert_eq!(inner.by_population.len(), 15);
+            assert_eq!(inner.extinct_by_age.len(), 5);
+            assert_eq!(inner.archive.as_ref().unwrap().len(), 5);
+        }
+
+        let evicted = pool.get_genome(seeds[0].genome_id).await.unwrap();
+        assert!(evicted.archived);
+        assert_eq!(evicted.summary.origin, GenomeOrigin::ServerMutated);
+        assert!(!pool.get_genome(seeds[5].genome_id).await.unwrap().archived);
+
+        // A genome evolved from an evicted seed keeps its ancestry through the archive
+        let evolved = GenomeWithFitness {
+            genome: seeds[0].genome.clone(),
+            fitness: 1.0,
+            empirical: None,
+            genome_id: Some(seeds[0].genome_id),
+        };
+        pool.submit_survival_results(Uuid::new_v4(), Vec::new(), 3000, vec![evolved], 1.0)
+            .await;
+        let evolved_id = pool
+            .inner
+            .read()
+            .await
+            .genomes
+            .iter()
+            .find(|(_, e)| e.origin == GenomeOrigin::ClientEvolved)
+            .map(|(id, _)| *id)
+            .unwrap();
+        let ancestry = pool.get_ancestry(evolved_id, 10).await.unwrap();
+        assert_eq!(ancestry.related[0].genome_id, seeds[0].genome_id);
+        assert_eq!(ancestry.related[0].related.len(), 1);
+        assert_eq!(
+            pool.get_genome(evolved_id)
+                .await
+                .unwrap()
+                .summary
+                .lineage_root,
+            evicted.summary.lineage_root
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_smallest_living_when_none_extinct() {
+        let pool = GenePool::with_options(GenePoolOptions {
+            capacity: Some(CapacityConfig {
+                max_genomes: 12,
+                policy: EvictionPolicy::LeastNovel,
+            }),
+            ..Default::default()
+        });
+
+        let seeds = pool.get_seed_genomes_spatial().await;
+        pool.inner.write().await.without_grace_period();
+        pool.submit_survival_results(
+            Uuid::new_v4(),
+            seed_results(&seeds[8..], 1),
+            3000,
+            Vec::new(),
+            1.0,
+        )
+        .await;
+
+        // The only extinct genomes are seeds still out with clients, so the
+        // two returned seeds and then the smallest seeded genomes go instead
+        let mut inner = pool.inner.write().await;
+        inner.enforce_capacity();
+        assert_eq!(inner.genomes.len(), 12);
+        assert_eq!(inner.extinct_by_age.len(), 8);
+        assert!(seeds[..8]
+            .iter()
+            .all(|s| inner.genomes.contains_key(&s.genome_id)));
+        assert!(seeds[8..]
+            .iter()
+            .all(|s| !inner.genomes.contains_key(&s.genome_id)));
+        assert_eq!(inner.by_population.last().unwrap().0, 100);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_scans_stay_bounded_while_all_protected() {
+        let pool = GenePool::with_options(GenePoolOptions {
+            capacity: Some(CapacityConfig {
+                max_genomes: 10,
+                policy: EvictionPolicy::OldestExtinct,
+            }),
+            ..Default::default()
+        });
+
+        // Every genome is inside the grace period, and the seeds are leased
+        for _ in 0..20 {
+            pool.get_seed_genomes_spatial().await;
+        }
+        let inner = pool.inner.read().await;
+        assert_eq!(inner.genomes.len(), 210);
+        // One scan when the pool first went over capacity, none after it
+        assert_eq!(inner.eviction_scanned, 11);
+        assert_eq!(inner.eviction_blocked_until, EVICTION_GRACE_SECS);
+    }
+
+    #[tokio::test]
+    async fn test_population_cap_applied_per_submission() {
+        let pool = GenePool::new();
+        let genome_id = pool.get_stats().await.best_genomes[0].genome_id.unwrap();
+
+        let results = vec![SurvivalResult {
+            genome_id,
+            survived: MAX_POPULATION,
+            total_spawned: MAX_POPULATION,
+            total_food_eaten: 0,
+        }];
+        pool.submit_survival_results(Uuid::new_v4(), results, 3000, Vec::new(), 1.0)
+            .await;
+
+        let detail = pool.get_genome(genome_id).await.unwrap();
+        assert_eq!(detail.summary.population, MAX_POPULATION);
+        assert_eq!(detail.submissions[0].new_population, MAX_POPULATION);
+    }
+
+    #[tokio::test]
+    async fn test_empirical_fitness_ranks_best_genomes() {
+        let pool = GenePool::new();
+        let client_id = Uuid::new_v4();
+
+        let seeds = pool.get_seed_genomes_spatial().await;
+        let results = vec![SurvivalResult {
+            genome_id: seeds[0].genome_id,
+            survived: 20,
+            total_spawned: 40,
+            total_food_eaten: 4000,
+        }];
+        pool.submit_survival_results(client_id, results, 3000, Vec::new(), 1.0)
+            .await;
+
+        // The outcome counts for the seed and for the pool genome it came from
+        let seed = pool.get_genome(seeds[0].genome_id).await.unwrap();
+        assert_eq!(seed.summary.empirical.unwrap().samples, 1);
+        let parent = pool.get_genome(seed.summary.parent_ids[0]).await.unwrap();
+        assert_eq!(parent.summary.empirical.unwrap().samples, 1);
+
+        // A consistently good record outranks everything else
+        let consistent = seeds[5].genome_id;
+        {
+            let mut inner = pool.inner.write().await;
+            for _ in 0..10 {
+                inner.observe_outcome(consistent, 0.8);
+            }
+        }
+        let stats = pool.get_stats().await;
+        assert_eq!(stats.best_genomes.len(), 10);
+        assert_eq!(stats.best_genomes[0].genome_id, Some(consistent));
+        let empirical = stats.best_genomes[0].empirical.unwrap();
+        assert!((empirical.mean - 0.8).abs() < 1e-9);
+        // Genomes without outcomes fill the remaining places
+        assert!(stats.best_genomes[3..]
+            .iter()
+            .all(|g| g.empirical.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_inject_set_population_and_reset() {
+        let pool = GenePool::new();
+
+        let genome_id = pool.inject_genome(Genome::random(), 500).await;
+        let detail = pool.get_genome(genome_id).await.unwrap();
+        assert_eq!(detail.summary.origin, GenomeOrigin::Injected);
+        assert_eq!(detail.summary.population, 500);
+
+        assert_eq!(pool.set_population(genome_id, 0).await, Some((500, 0)));
+        assert_eq!(pool.set_population(Uuid::new_v4(), 10).await, None);
+        assert_eq!(
+            pool.set_population(genome_id, u32::MAX).await,
+            Some((0, MAX_POPULATION))
+        );
+        assert_eq!(
+            pool.get_genome(genome_id).await.unwrap().summary.population,
+            MAX_POPULATION
+        );
+
+        pool.set_mutation_rate(0.2).await;
+        pool.get_seed_genomes_spatial().await;
+        pool.reset().await;
+        let stats = pool.get_stats().await;
+        assert_eq!(stats.gene_pool_size, 10);
+        assert!(pool.get_genome(genome_id).await.is_none());
+        assert_eq!(pool.mutation_rate().await, 0.2);
+    }
 }
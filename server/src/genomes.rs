@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use shared::{EmpiricalFitness, Genome};
+use uuid::Uuid;
+
+/// Maximum number of submissions remembered per genome
+pub const MAX_SUBMISSIONS_PER_GENOME: usize = 100;
+
+/// Default and maximum page sizes for the genome listing
+const DEFAULT_PER_PAGE: usize = 50;
+const MAX_PER_PAGE: usize = 500;
+
+/// One client result that touched a genome's population
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionRecord {
+    pub timestamp_ms: u64,
+    pub client_id: Uuid,
+    pub survived: u32,
+    pub total_spawned: u32,
+    pub total_food_eaten: u32,
+    pub old_population: u32,
+    pub new_population: u32,
+
+    /// Trust in the client when the result was accepted; scales the change
+    #[serde(default = "full_trust")]
+    pub trust: f64,
+}
+
+fn full_trust() -> f64 {
+    1.0
+}
+
+/// How a genome entered the gene pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenomeOrigin {
+    /// Random genome created when the pool was initialised
+    Seeded,
+    /// Mutated copy of a pool genome, made when handing out work
+    ServerMutated,
+    /// Evolved on a client island and reported back with the results
+    ClientEvolved,
+    /// Hand-written genome added through the admin API
+    Injected,
+}
+
+/// Population of a genome at a point in the stats history
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PopulationPoint {
+    pub uptime_seconds: u64,
+    pub population: u32,
+}
+
+/// Ordering for the genome listing
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GenomeSort {
+    #[default]
+    Population,
+    /// Analytic score computed from the traits
+    Fitness,
+    /// Lower confidence bound of the fitness measured from island outcomes
+    Empirical,
+}
+
+/// Filter for the genome listing
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GenomeStatus {
+    #[default]
+    All,
+    Living,
+    Extinct,
+}
+
+impl GenomeStatus {
+    pub fn matches(self, population: u32) -> bool {
+        match self {
+            GenomeStatus::All => true,
+            GenomeStatus::Living => population > 0,
+            GenomeStatus::Extinct => population == 0,
+        }
+    }
+}
+
+/// Query parameters for `GET /api/genomes`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GenomeQuery {
+    /// Zero-based page index
+    #[serde(default)]
+    pub page: usize,
+
+    pub per_page: Option<usize>,
+
+    #[serde(default)]
+    pub sort: GenomeSort,
+
+    #[serde(default)]
+    pub status: GenomeStatus,
+}
+
+impl GenomeQuery {
+    pub fn per_page(&self) -> usize {
+        self.per_page
+            .unwrap_or(DEFAULT_PER_PAGE)
+            .clamp(1, MAX_PER_PAGE)
+    }
+}
+
+/// Summary of a genome in the pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenomeSummary {
+    pub genome_id: Uuid,
+    pub genome: Genome,
+    pub population: u32,
+
+    /// Analytic score computed from the traits
+    pub fitness: f64,
+
+    /// Fitness measured from client survival results, once any have arrived
+    #[serde(default)]
+    pub empirical: Option<EmpiricalFitness>,
+
+    pub origin: GenomeOrigin,
+
+    /// Pool genomes this one descends from (empty for seeded genomes)
+    pub parent_ids: Vec<Uuid>,
+
+    /// Oldest known ancestor, identifying the lineage this genome belongs to
+    pub lineage_root: Uuid,
+
+    /// Uptime (seconds) at which the genome entered the pool
+    pub created_seconds: u64,
+}
+
+/// Response body for `GET /api/genomes`
+#[derive(Debug, Clone, Serialize)]
+pub struct GenomeListResponse {
+    /// Number of genomes matching the filter
+    pub total: usize,
+    pub page: usize,
+    pub per_page: usize,
+    pub genomes: Vec<GenomeSummary>,
+}
+
+/// Response body for `GET /api/genomes/{id}`
+#[derive(Debug, Clone, Serialize)]
+pub struct GenomeDetail {
+    #[serde(flatten)]
+    pub summary: GenomeSummary,
+
+    /// Population at each stats sample since the genome was created
+    pub population_history: Vec<PopulationPoint>,
+
+    /// Most recent client results for this genome, oldest first
+    pub submissions: Vec<SubmissionRecord>,
+
+    /// The genome has been evicted from the pool and was read from the archive
+    pub archived: bool,
+}
+
+/// Query parameters for the ancestry endpoints
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AncestryQuery {
+    /// How many generations to walk (default 10, max 100)
+    pub depth: Option<usize>,
+}
+
+impl AncestryQuery {
+    pub fn depth(&self) -> usize {
+        self.depth.unwrap_or(10).min(100)
+    }
+}
+
+/// A node in an ancestry or descendant tree
+#[derive(Debug, Clone, Serialize)]
+pub struct AncestryNode {
+    pub genome_id: Uuid,
+    pub origin: GenomeOrigin,
+    pub population: u32,
+    pub created_seconds: u64,
+
+    /// Parents (for ancestry trees) or children (for descendant trees)
+    pub related: Vec<AncestryNode>,
+}
+
+/// Aggregate statistics for all genomes sharing a lineage root
+#[derive(Debug, Clone, Serialize)]
+pub struct LineageSummary {
+    pub lineage_root: Uuid,
+
+    /// Number of genomes in the lineage
+    pub genomes: usize,
+
+    /// Genomes in the lineage with a non-zero population
+    pub living_genomes: usize,
+
+    /// Combined population of every genome in the lineage
+    pub total_population: u64,
+
+    /// Share of the whole pool population held by this lineage
+    pub population_share: f64,
+}
+
+/// Query parameters for `GET /api/lineages`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LineageQuery {
+    /// Maximum number of lineages to return (default 20)
+    pub limit: Option<usize>,
+
+    /// Include lineages with no living genomes
+    #[serde(default)]
+    pub include_extinct: bool,
+}
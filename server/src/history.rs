@@ -0,0 +1,284 @@
+use crate::genomes::PopulationPoint;
+use serde::{Deserialize, Serialize};
+use shared::{Genome, GENE_COUNT};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Default number of samples kept before the history is compacted
+pub const DEFAULT_MAX_SAMPLES: usize = 4096;
+
+/// A periodic snapshot of the gene pool
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsSample {
+    /// Seconds since the server started
+    pub uptime_seconds: u64,
+
+    /// Wall clock time of the sample (milliseconds since the Unix epoch)
+    pub timestamp_ms: u64,
+
+    /// Number of genomes with a non-zero population
+    pub living: usize,
+
+    /// Number of genomes with zero population
+    pub extinct: usize,
+
+    /// Sum of all virtual populations
+    pub total_population: u64,
+
+    /// Population-weighted mean trait values of the living genomes
+    pub mean_traits: Genome,
+
+    /// Population-weighted mean `fitness_score` of the living genomes
+    pub mean_fitness: f64,
+
+    /// Mean per-trait standard deviation across the living population
+    pub diversity: f64,
+
+    /// Population of every living genome at the time of the sample
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub populations: Option<HashMap<Uuid, u32>>,
+}
+
+impl StatsSample {
+    /// Build a sample from `(genome_id, genome, population)` triples
+    pub fn from_entries<'a>(
+        uptime_seconds: u64,
+        timestamp_ms: u64,
+        entries: impl Iterator<Item = (&'a Uuid, &'a Genome, u32)>,
+    ) -> Self {
+        let mut populations = HashMap::new();
+        let mut living_genomes: Vec<(&Genome, f64)> = Vec::new();
+        let mut extinct = 0;
+
+        for (id, genome, population) in entries {
+            if population > 0 {
+                populations.insert(*id, population);
+                living_genomes.push((genome, population as f64));
+            } else {
+                extinct += 1;
+            }
+        }
+
+        let total_population: f64 = living_genomes.iter().map(|(_, w)| w).sum();
+        let weighted_mean = |f: &dyn Fn(&Genome) -> f64| -> f64 {
+            if total_population == 0.0 {
+                return 0.0;
+            }
+            living_genomes.iter().map(|(g, w)| f(g) * w).sum::<f64>() / total_population
+        };
+        let weighted_std = |f: &dyn Fn(&Genome) -> f64, mean: f64| -> f64 {
+            if total_population == 0.0 {
+                return 0.0;
+            }
+            let var = living_genomes
+                .iter()
+                .map(|(g, w)| (f(g) - mean).powi(2) * w)
+                .sum::<f64>()
+                / total_population;
+            var.sqrt()
+        };
+
+        let means: [f64; GENE_COUNT] =
+            std::array::from_fn(|i| weighted_mean(&|g: &Genome| g.values()[i]));
+        let diversity = means
+            .iter()
+            .enumerate()
+            .map(|(i, mean)| weighted_std(&|g: &Genome| g.values()[i], *mean))
+            .sum::<f64>()
+            / GENE_COUNT as f64;
+
+        Self {
+            uptime_seconds,
+            timestamp_ms,
+            living: living_genomes.len(),
+            extinct,
+            total_population: total_population as u64,
+            mean_traits: Genome::from_values(means),
+            mean_fitness: weighted_mean(&|g| g.fitness_score()),
+            diversity,
+            populations: Some(populations),
+        }
+    }
+}
+
+/// Query parameters for reading back the history
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HistoryQuery {
+    /// Only include samples at or after this uptime (seconds)
+    pub from: Option<u64>,
+
+    /// Only include samples at or before this uptime (seconds)
+    pub to: Option<u64>,
+
+    /// Downsample so that at most this many samples are returned
+    pub max_points: Option<usize>,
+
+    /// Include the per-genome population maps (can be large)
+    #[serde(default)]
+    pub populations: bool,
+}
+
+/// Response body for the history endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryResponse {
+    /// Number of recorded samples matching the time range before downsampling
+    pub total_samples: usize,
+
+    pub samples: Vec<StatsSample>,
+}
+
+/// Time series of gene pool samples covering the whole server lifetime.
+///
+/// When the buffer fills up every other sample is dropped, so the history
+/// always spans the full run at a progressively coarser resolution.
+#[derive(Debug, Clone)]
+pub struct StatsHistory {
+    samples: Vec<StatsSample>,
+    max_samples: usize,
+}
+
+impl StatsHistory {
+    pub fn new(max_samples: usize) -> Self {
+        Self {
+            samples: Vec::new(),
+            max_samples: max_samples.max(2),
+        }
+    }
+
+    pub fn push(&mut self, sample: StatsSample) {
+        if self.samples.len() >= self.max_samples {
+            self.compact();
+        }
+        self.samples.push(sample);
+    }
+
+    /// Halve the resolution of the stored history, always keeping the first sample
+    fn compact(&mut self) {
+        let mut index = 0;
+        self.samples.retain(|_| {
+            let keep = index % 2 == 0;
+            index += 1;
+            keep
+        });
+    }
+
+    /// Population of one genome at every sample taken since `since_seconds`
+    pub fn population_history(&self, genome_id: Uuid, since_seconds: u64) -> Vec<PopulationPoint> {
+        self.samples
+            .iter()
+            .filter(|s| s.uptime_seconds >= since_seconds)
+            .map(|s| PopulationPoint {
+                uptime_seconds: s.uptime_seconds,
+                population: s
+                    .populations
+                    .as_ref()
+                    .and_then(|p| p.get(&genome_id).copied())
+                    .unwrap_or(0),
+            })
+            .collect()
+    }
+
+    /// Samples within the query's time range, evenly downsampled to `max_points`
+    pub fn query(&self, query: &HistoryQuery) -> HistoryResponse {
+        let in_range: Vec<&StatsSample> = self
+            .samples
+            .iter()
+            .filter(|s| query.from.is_none_or(|from| s.uptime_seconds >= from))
+            .filter(|s| query.to.is_none_or(|to| s.uptime_seconds <= to))
+            .collect();
+        let total_samples = in_range.len();
+
+        let max_points = query.max_points.unwrap_or(total_samples).max(1);
+        let selected: Vec<&StatsSample> = if total_samples > max_points {
+            // Evenly spaced picks that always include the first and last samples
+            (0..max_points)
+                .map(|i| in_range[i * (total_samples - 1) / (max_points - 1).max(1)])
+                .collect()
+        } else {
+            in_range
+        };
+
+        let samples = selected
+            .into_iter()
+            .map(|s| {
+                let mut s = s.clone();
+                if !query.populations {
+                    s.populations = None;
+                }
+                s
+            })
+            .collect();
+
+        HistoryResponse {
+            total_samples,
+            samples,
+        }
+    }
+}
+
+impl Default for StatsHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SAMPLES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_at(uptime_seconds: u64) -> StatsSample {
+        let id = Uuid::new_v4();
+        let genome = Genome::default();
+        StatsSample::from_entries(uptime_seconds, 0, [(&id, &genome, 10)].into_iter())
+    }
+
+    #[test]
+    fn test_sample_statistics() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let g1 = Genome::new(1.0, 0.0, 0.5, 0.5, 0.5, 0.0, 0.5);
+        let g2 = Genome::new(0.0, 1.0, 0.5, 0.5, 0.5, 0.0, 0.5);
+        let entries = [(&a, &g1, 100), (&b, &g2, 100), (&c, &g2, 0)];
+
+        let sample = StatsSample::from_entries(5, 0, entries.into_iter());
+
+        assert_eq!(sample.living, 2);
+        assert_eq!(sample.extinct, 1);
+        assert_eq!(sample.total_population, 200);
+        assert!((sample.mean_traits.strength - sample.mean_traits.speed).abs() < 1e-9);
+        assert!(sample.diversity > 0.0);
+        assert_eq!(sample.populations.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_history_compacts_but_keeps_full_span() {
+        let mut history = StatsHistory::new(8);
+        for t in 0..100 {
+            history.push(sample_at(t));
+        }
+
+        assert!(history.samples.len() <= 8);
+        assert_eq!(history.samples[0].uptime_seconds, 0);
+        assert_eq!(history.samples.last().unwrap().uptime_seconds, 99);
+    }
+
+    #[test]
+    fn test_history_query_range_and_downsampling() {
+        let mut history = StatsHistory::new(1000);
+        for t in 0..100 {
+            history.push(sample_at(t));
+        }
+
+        let response = history.query(&HistoryQuery {
+            from: Some(10),
+            to: Some(59),
+            max_points: Some(5),
+            populations: false,
+        });
+
+        assert_eq!(response.total_samples, 50);
+        assert_eq!(response.samples.len(), 5);
+        assert_eq!(response.samples[0].uptime_seconds, 10);
+        assert_eq!(response.samples[4].uptime_seconds, 59);
+        assert!(response.samples[0].populations.is_none());
+    }
+}
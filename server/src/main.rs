@@ -1,5 +1,19 @@
+mod admin;
+mod archive;
+mod audit;
+mod auth;
+mod events;
+mod eviction;
+mod experiment;
+mod fitness;
 mod gene_pool;
+mod genomes;
+mod history;
+mod maps;
+mod selection;
 mod server;
+mod spectate;
+mod validation;
 mod web;
 
 use anyhow::Result;
@@ -0,0 +1,111 @@
+//! Hand-authored terrain maps for experiments.
+//!
+//! Text maps have one line per row of tiles and one symbol per tile:
+//! `.` grassland, `*` fertile soil, `~` water and `^` mountain. PNG maps
+//! have one pixel per tile, each read as the terrain whose colour
+//! (`Terrain::color`) it is closest to. Either is stretched to the size of
+//! the world.
+
+use shared::{Terrain, TerrainConfig};
+use std::fs::File;
+use std::path::Path;
+
+/// Load a map, as PNG if the file name ends in `.png` and as text otherwise
+pub fn load(path: &Path) -> Result<TerrainConfig, String> {
+    let is_png = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+    let rows = if is_png {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        png_rows(file)?
+    } else {
+        text_rows(&std::fs::read_to_string(path).map_err(|e| e.to_string())?)
+    };
+    let terrain = TerrainConfig::Map { rows };
+    terrain.validate()?;
+    Ok(terrain)
+}
+
+/// Rows of a text map, ignoring blank lines and trailing whitespace
+fn text_rows(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rows of a PNG map, classifying each pixel by its nearest terrain colour
+fn png_rows(input: impl std::io::Read) -> Result<Vec<String>, String> {
+    let mut decoder = png::Decoder::new(input);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).map_err(|e| e.to_string())?;
+
+    let samples = info.color_type.samples();
+    let rgb = |pixel: &[u8]| match info.color_type {
+        png::ColorType::Grayscale | png::ColorType::GrayscaleAlpha => [pixel[0]; 3],
+        _ => [pixel[0], pixel[1], pixel[2]],
+    };
+    Ok(pixels[..info.buffer_size()]
+        .chunks(info.line_size)
+        .map(|line| {
+            line.chunks(samples)
+                .take(info.width as usize)
+                .map(|pixel| nearest_terrain(rgb(pixel)).symbol())
+                .collect()
+        })
+        .collect())
+}
+
+fn nearest_terrain(pixel: [u8; 3]) -> Terrain {
+    let distance = |terrain: &Terrain| -> i32 {
+        terrain
+            .color()
+            .iter()
+            .zip(pixel)
+            .map(|(a, b)| (*a as i32 - b as i32).pow(2))
+            .sum()
+    };
+    Terrain::ALL
+        .into_iter()
+        .min_by_key(distance)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_map() {
+        let rows = text_rows("~~~~\n~..~\r\n~*^~  \n\n");
+        assert_eq!(rows, vec!["~~~~", "~..~", "~*^~"]);
+        assert!(TerrainConfig::Map { rows }.validate().is_ok());
+    }
+
+    #[test]
+    fn test_png_map() {
+        // A 3x2 image: water, grassland, mountain over fertile, near-water, white
+        let pixels: [[u8; 3]; 6] = [
+            Terrain::Water.color(),
+            Terrain::Grassland.color(),
+            Terrain::Mountain.color(),
+            Terrain::Fertile.color(),
+            [30, 80, 220],
+            [255, 255, 255],
+        ];
+        let mut image = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut image, 3, 2);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(pixels.as_flattened()).unwrap();
+        }
+
+        let rows = png_rows(image.as_slice()).unwrap();
+        assert_eq!(rows, vec!["~.^", "*~^"]);
+    }
+}
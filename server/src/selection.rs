@@ -0,0 +1,499 @@
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use shared::{Genome, GENES};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A gene pool genome offered to a selection strategy
+#[derive(Debug, Clone)]
+pub struct Candidate<'a> {
+    pub genome_id: Uuid,
+    pub genome: &'a Genome,
+    pub population: u32,
+
+    /// Selection fitness (higher is better)
+    pub fitness: f64,
+}
+
+/// Chooses which pool genomes seed the next work assignment.
+///
+/// Strategies return the IDs of the chosen candidates, possibly with repeats.
+/// They may return fewer than `count` IDs; the gene pool tops up the seeds
+/// with random genomes.
+pub trait SelectionStrategy: Send + Sync {
+    /// Short identifier used in logs and the API
+    fn name(&self) -> &'static str;
+
+    fn select(
+        &self,
+        candidates: &[Candidate<'_>],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Uuid>;
+}
+
+/// Serializable description of a selection strategy and its parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SelectionConfig {
+    /// Top living genomes by population plus random extinct genomes
+    Heuristic {
+        #[serde(default = "default_top_living")]
+        top_living: usize,
+        #[serde(default = "default_random_extinct")]
+        random_extinct: usize,
+    },
+    /// Roulette-wheel selection proportional to fitness
+    FitnessProportionate,
+    /// Best of `size` uniformly drawn candidates, repeated per seed
+    Tournament {
+        #[serde(default = "default_tournament_size")]
+        size: usize,
+    },
+    /// Linear ranking with selection pressure in [1.0, 2.0]
+    RankBased {
+        #[serde(default = "default_rank_pressure")]
+        pressure: f64,
+    },
+    /// Genomes furthest from their `k` nearest neighbours in trait space
+    Novelty {
+        #[serde(default = "default_novelty_k")]
+        k: usize,
+    },
+    /// Elites of a `bins` x `bins` grid over strength and speed
+    MapElites {
+        #[serde(default = "default_map_elites_bins")]
+        bins: usize,
+    },
+}
+
+fn default_top_living() -> usize {
+    5
+}
+fn default_random_extinct() -> usize {
+    5
+}
+fn default_tournament_size() -> usize {
+    3
+}
+fn default_rank_pressure() -> f64 {
+    1.5
+}
+fn default_novelty_k() -> usize {
+    5
+}
+fn default_map_elites_bins() -> usize {
+    5
+}
+
+impl Default for SelectionConfig {
+    fn default() -> Self {
+        SelectionConfig::Heuristic {
+            top_living: default_top_living(),
+            random_extinct: default_random_extinct(),
+        }
+    }
+}
+
+impl SelectionConfig {
+    /// Parse either a bare strategy name (using default parameters) or a JSON object
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if value.starts_with('{') {
+            return serde_json::from_str(value).map_err(|e| e.to_string());
+        }
+        serde_json::from_value(serde_json::json!({ "type": value }))
+            .map_err(|_| format!("Unknown selection strategy: {}", value))
+    }
+
+    pub fn build(&self) -> Arc<dyn SelectionStrategy> {
+        match *self {
+            SelectionConfig::Heuristic {
+                top_living,
+                random_extinct,
+            } => Arc::new(HeuristicSelection {
+                top_living,
+                random_extinct,
+            }),
+            SelectionConfig::FitnessProportionate => Arc::new(FitnessProportionateSelection),
+            SelectionConfig::Tournament { size } => {
+                Arc::new(TournamentSelection { size: size.max(1) })
+            }
+            SelectionConfig::RankBased { pressure } => Arc::new(RankBasedSelection {
+                pressure: pressure.clamp(1.0, 2.0),
+            }),
+            SelectionConfig::Novelty { k } => Arc::new(NoveltySelection { k: k.max(1) }),
+            SelectionConfig::MapElites { bins } => {
+                Arc::new(MapElitesSelection { bins: bins.max(1) })
+            }
+        }
+    }
+}
+
+/// Pick `count` indices with replacement, with probability proportional to `weights`.
+/// Falls back to uniform picks when every weight is zero.
+fn weighted_picks(weights: &[f64], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+    if weights.is_empty() {
+        return Vec::new();
+    }
+    let total: f64 = weights.iter().sum();
+    (0..count)
+        .map(|_| {
+            if total <= 0.0 {
+                return rng.gen_range(0..weights.len());
+            }
+            let mut target = rng.gen::<f64>() * total;
+            for (i, w) in weights.iter().enumerate() {
+                if target < *w {
+                    return i;
+                }
+                target -= w;
+            }
+            weights.len() - 1
+        })
+        .collect()
+}
+
+/// Euclidean distance between two genomes in trait space, with every trait
+/// scaled to its gene's range
+pub fn trait_distance(a: &Genome, b: &Genome) -> f64 {
+    a.values()
+        .iter()
+        .zip(b.values())
+        .zip(GENES)
+        .map(|((a, b), gene)| ((a - b) / (gene.max - gene.min)).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// The original seed heuristic: most populous living genomes plus a random
+/// sample of extinct genomes to keep lost variation in circulation
+pub struct HeuristicSelection {
+    pub top_living: usize,
+    pub random_extinct: usize,
+}
+
+impl SelectionStrategy for HeuristicSelection {
+    fn name(&self) -> &'static str {
+        "heuristic"
+    }
+
+    fn select(
+        &self,
+        candidates: &[Candidate<'_>],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Uuid> {
+        let mut living: Vec<_> = candidates.iter().filter(|c| c.population > 0).collect();
+        living.sort_by_key(|c| std::cmp::Reverse(c.population));
+        let extinct: Vec<_> = candidates.iter().filter(|c| c.population == 0).collect();
+
+        let mut picks: Vec<Uuid> = living
+            .iter()
+            .take(self.top_living)
+            .map(|c| c.genome_id)
+            .collect();
+        picks.extend(
+            extinct
+                .choose_multiple(rng, self.random_extinct)
+                .map(|c| c.genome_id),
+        );
+        picks.truncate(count);
+        picks
+    }
+}
+
+/// Roulette-wheel selection: each seed is drawn with probability proportional to fitness
+pub struct FitnessProportionateSelection;
+
+impl SelectionStrategy for FitnessProportionateSelection {
+    fn name(&self) -> &'static str {
+        "fitness_proportionate"
+    }
+
+    fn select(
+        &self,
+        candidates: &[Candidate<'_>],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Uuid> {
+        let weights: Vec<f64> = candidates.iter().map(|c| c.fitness.max(0.0)).collect();
+        weighted_picks(&weights, count, rng)
+            .into_iter()
+            .map(|i| candidates[i].genome_id)
+            .collect()
+    }
+}
+
+/// Tournament selection: each seed is the fittest of `size` random candidates
+pub struct TournamentSelection {
+    pub size: usize,
+}
+
+impl SelectionStrategy for TournamentSelection {
+    fn name(&self) -> &'static str {
+        "tournament"
+    }
+
+    fn select(
+        &self,
+        candidates: &[Candidate<'_>],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Uuid> {
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+        (0..count)
+            .filter_map(|_| {
+                (0..self.size)
+                    .map(|_| &candidates[rng.gen_range(0..candidates.len())])
+                    .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+                    .map(|c| c.genome_id)
+            })
+            .collect()
+    }
+}
+
+/// Linear ranking: selection probability depends only on fitness rank, which
+/// keeps pressure constant regardless of how far apart the fitness values are
+pub struct RankBasedSelection {
+    pub pressure: f64,
+}
+
+impl SelectionStrategy for RankBasedSelection {
+    fn name(&self) -> &'static str {
+        "rank_based"
+    }
+
+    fn select(
+        &self,
+        candidates: &[Candidate<'_>],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Uuid> {
+        let mut ranked: Vec<_> = candidates.iter().collect();
+        ranked.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+
+        let n = ranked.len();
+        let weights: Vec<f64> = (0..n)
+            .map(|rank| {
+                if n == 1 {
+                    return 1.0;
+                }
+                let s = self.pressure;
+                (2.0 - s) + 2.0 * (s - 1.0) * (n - 1 - rank) as f64 / (n - 1) as f64
+            })
+            .collect();
+
+        weighted_picks(&weights, count, rng)
+            .into_iter()
+            .map(|i| ranked[i].genome_id)
+            .collect()
+    }
+}
+
+/// Novelty search: ignores fitness and picks the genomes whose traits are
+/// furthest from their nearest neighbours in the pool
+pub struct NoveltySelection {
+    pub k: usize,
+}
+
+impl SelectionStrategy for NoveltySelection {
+    fn name(&self) -> &'static str {
+        "novelty"
+    }
+
+    fn select(
+        &self,
+        candidates: &[Candidate<'_>],
+        count: usize,
+        _rng: &mut dyn RngCore,
+    ) -> Vec<Uuid> {
+        let mut scored: Vec<(f64, Uuid)> = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let mut distances: Vec<f64> = candidates
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, other)| trait_distance(c.genome, other.genome))
+                    .collect();
+                distances.sort_by(|a, b| a.total_cmp(b));
+                let k = self.k.min(distances.len());
+                let novelty = if k == 0 {
+                    0.0
+                } else {
+                    distances[..k].iter().sum::<f64>() / k as f64
+                };
+                (novelty, c.genome_id)
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.into_iter().take(count).map(|(_, id)| id).collect()
+    }
+}
+
+/// MAP-Elites: the trait space (strength x speed) is divided into niches and
+/// seeds are drawn from the fittest genome of randomly chosen occupied niches
+pub struct MapElitesSelection {
+    pub bins: usize,
+}
+
+impl MapElitesSelection {
+    fn niche(&self, genome: &Genome) -> (usize, usize) {
+        let bin = |v: f64| ((v.clamp(0.0, 1.0) * self.bins as f64) as usize).min(self.bins - 1);
+        (bin(genome.strength), bin(genome.speed))
+    }
+}
+
+impl SelectionStrategy for MapElitesSelection {
+    fn name(&self) -> &'static str {
+        "map_elites"
+    }
+
+    fn select(
+        &self,
+        candidates: &[Candidate<'_>],
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Uuid> {
+        let mut elites: HashMap<(usize, usize), &Candidate<'_>> = HashMap::new();
+        for candidate in candidates {
+            let niche = self.niche(candidate.genome);
+            let replace = elites
+                .get(&niche)
+                .is_none_or(|elite| candidate.fitness > elite.fitness);
+            if replace {
+                elites.insert(niche, candidate);
+            }
+        }
+
+        let mut elites: Vec<_> = elites.into_values().collect();
+        if elites.is_empty() {
+            return Vec::new();
+        }
+        elites.shuffle(rng);
+        (0..count)
+            .map(|i| elites[i % elites.len()].genome_id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> Vec<(Uuid, Genome, u32)> {
+        (0..20u32)
+            .map(|i| {
+                let v = i as f64 / 20.0;
+                (
+                    Uuid::new_v4(),
+                    Genome::new(v, 1.0 - v, 0.5, 0.5, 0.5, 0.0, 0.5),
+                    if i < 10 { 0 } else { i * 10 },
+                )
+            })
+            .collect()
+    }
+
+    fn candidates(pool: &[(Uuid, Genome, u32)]) -> Vec<Candidate<'_>> {
+        pool.iter()
+            .map(|(id, genome, population)| Candidate {
+                genome_id: *id,
+                genome,
+                population: *population,
+                fitness: *population as f64,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_selection_config() {
+        assert_eq!(
+            SelectionConfig::parse("tournament").unwrap(),
+            SelectionConfig::Tournament { size: 3 }
+        );
+        assert_eq!(
+            SelectionConfig::parse(r#"{"type": "map_elites", "bins": 8}"#).unwrap(),
+            SelectionConfig::MapElites { bins: 8 }
+        );
+        assert!(SelectionConfig::parse("nonsense").is_err());
+    }
+
+    #[test]
+    fn test_heuristic_matches_original_behaviour() {
+        let pool = pool();
+        let candidates = candidates(&pool);
+        let picks =
+            SelectionConfig::default()
+                .build()
+                .select(&candidates, 10, &mut rand::thread_rng());
+
+        assert_eq!(picks.len(), 10);
+        // First five are the most populous living genomes, in order
+        let expected: Vec<Uuid> = pool.iter().rev().take(5).map(|(id, _, _)| *id).collect();
+        assert_eq!(&picks[..5], &expected[..]);
+        // Remainder are extinct
+        for id in &picks[5..] {
+            assert!(pool.iter().any(|(pid, _, pop)| pid == id && *pop == 0));
+        }
+    }
+
+    #[test]
+    fn test_all_strategies_return_pool_ids() {
+        let pool = pool();
+        let candidates = candidates(&pool);
+        let configs = [
+            SelectionConfig::default(),
+            SelectionConfig::FitnessProportionate,
+            SelectionConfig::Tournament { size: 3 },
+            SelectionConfig::RankBased { pressure: 2.0 },
+            SelectionConfig::Novelty { k: 3 },
+            SelectionConfig::MapElites { bins: 4 },
+        ];
+
+        for config in configs {
+            let strategy = config.build();
+            let picks = strategy.select(&candidates, 10, &mut rand::thread_rng());
+            assert_eq!(
+                picks.len(),
+                10,
+                "{} returned too few seeds",
+                strategy.name()
+            );
+            for id in picks {
+                assert!(pool.iter().any(|(pid, _, _)| *pid == id));
+            }
+            assert!(strategy.select(&[], 10, &mut rand::thread_rng()).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_fitness_proportionate_ignores_zero_fitness() {
+        let pool = pool();
+        let candidates = candidates(&pool);
+        let picks = FitnessProportionateSelection.select(&candidates, 200, &mut rand::thread_rng());
+
+        for id in picks {
+            assert!(pool.iter().any(|(pid, _, pop)| *pid == id && *pop > 0));
+        }
+    }
+
+    #[test]
+    fn test_novelty_prefers_outliers() {
+        let mut pool: Vec<(Uuid, Genome, u32)> = (0..5)
+            .map(|_| (Uuid::new_v4(), Genome::default(), 100))
+            .collect();
+        let outlier = Uuid::new_v4();
+        pool.push((outlier, Genome::new(1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.5), 1));
+
+        let candidates = candidates(&pool);
+        let picks = NoveltySelection { k: 2 }.select(&candidates, 1, &mut rand::thread_rng());
+        assert_eq!(picks, vec![outlier]);
+    }
+}
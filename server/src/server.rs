@@ -1,33 +1,287 @@
-use crate::gene_pool::GenePool;
+use crate::admin::{self, AdminState};
+use crate::archive::GenomeArchive;
+use crate::audit::{unix_millis, AuditLog, AuditRecord};
+use crate::auth::{
+    self, ApiKey, AuthConfig, KeyRegistry, Principal, Scope, ADMIN_TOKEN_KEY_ID,
+    WORKER_TOKEN_KEY_ID,
+};
+use crate::events::{self, EventBus, EventKind, CLIENT_TIMEOUT};
+use crate::eviction::{CapacityConfig, EvictionPolicy};
+use crate::experiment::{
+    Experiment, ExperimentConfig, ExperimentQuery, ExperimentSummary, Experiments, IslandSettings,
+    DEFAULT_EXPERIMENT,
+};
+use crate::gene_pool::{GenePool, GenePoolOptions};
+use crate::genomes::{
+    AncestryNode, AncestryQuery, GenomeDetail, GenomeListResponse, GenomeQuery, LineageQuery,
+    LineageSummary,
+};
+use crate::history::{HistoryQuery, HistoryResponse};
+use crate::selection::SelectionConfig;
+use crate::spectate::{self, SpectatorHub};
+use crate::validation::{ClientStanding, LeaderboardQuery, Lease, Validator};
 use crate::web;
+use anyhow::Context;
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware,
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
-use shared::{GlobalStats, WorkAssignment, WorkRequest, WorkResult};
+use shared::auth::{KEY_ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use shared::{GeneSpec, GlobalStats, ServerError, WorkAssignment, WorkRequest, WorkResult, GENES};
 use std::net::SocketAddr;
-use tower_http::cors::CorsLayer;
+use std::path::PathBuf;
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tower_http::services::ServeDir;
+use uuid::Uuid;
+
+/// Default limit on the number of genomes kept in memory
+const DEFAULT_GENE_POOL_CAPACITY: usize = 100_000;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub gene_pool: GenePool,
+    pub experiments: Experiments,
+    pub admin: AdminState,
+    pub auth: AuthConfig,
+    pub validator: Validator,
+    pub events: EventBus,
+    pub spectators: SpectatorHub,
+}
+
+impl AppState {
+    /// Gene pool of the experiment named in the query, or of the default experiment
+    fn gene_pool(&self, scope: &ExperimentQuery) -> Result<&GenePool, StatusCode> {
+        self.experiments
+            .get(scope.experiment.as_deref())
+            .map(|e| &e.gene_pool)
+            .ok_or(StatusCode::NOT_FOUND)
+    }
 }
 
 pub async fn run() -> anyhow::Result<()> {
+    // Seed selection strategy: a bare name or a JSON object with parameters
+    let selection = match std::env::var("SELECTION_STRATEGY") {
+        Ok(value) => SelectionConfig::parse(&value).map_err(anyhow::Error::msg)?,
+        Err(_) => SelectionConfig::default(),
+    };
+    tracing::info!("Using seed selection strategy {:?}", selection);
+
+    // Bound the number of genomes kept in memory; 0 disables the limit
+    let max_genomes = std::env::var("GENE_POOL_CAPACITY")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_GENE_POOL_CAPACITY);
+    let capacity = if max_genomes > 0 {
+        let policy = match std::env::var("EVICTION_POLICY") {
+            Ok(value) => EvictionPolicy::parse(&value).map_err(anyhow::Error::msg)?,
+            Err(_) => EvictionPolicy::default(),
+        };
+        tracing::info!(
+            "Gene pool capacity {} genomes, evicting by {:?}",
+            max_genomes,
+            policy
+        );
+        Some(CapacityConfig {
+            max_genomes,
+            policy,
+        })
+    } else {
+        None
+    };
+
+    // Named experiments, each with its own gene pool; a single default one otherwise
+    let configs = match std::env::var("EXPERIMENTS_CONFIG") {
+        Ok(path) => {
+            let json = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read experiments config {}", path))?;
+            let mut configs = ExperimentConfig::parse_list(&json)
+                .map_err(anyhow::Error::msg)
+                .with_context(|| format!("Invalid experiments config {}", path))?;
+            let config_dir = std::path::Path::new(&path)
+                .parent()
+                .unwrap_or(std::path::Path::new("."));
+            for config in &mut configs {
+                config
+                    .load_terrain_map(config_dir)
+                    .map_err(anyhow::Error::msg)?;
+            }
+            Some(configs)
+        }
+        Err(_) => None,
+    };
+    // With several experiments, each keeps its own audit log and archive file
+    let per_experiment_files = configs.is_some();
+    let configs = configs.unwrap_or_else(|| {
+        vec![ExperimentConfig {
+            name: DEFAULT_EXPERIMENT.to_string(),
+            weight: 1.0,
+            island: IslandSettings::default(),
+            selection: None,
+            terrain_map: None,
+        }]
+    });
+
+    let events = EventBus::default();
+    let audit_log_path = std::env::var("AUDIT_LOG_PATH").ok();
+    let archive_path = std::env::var("ARCHIVE_PATH").ok();
+    let mut experiments = Vec::with_capacity(configs.len());
+    for config in configs {
+        let file_path = |path: &str| {
+            if per_experiment_files {
+                experiment_path(path, &config.name)
+            } else {
+                PathBuf::from(path)
+            }
+        };
+
+        // Optional JSON Lines audit log of every gene pool population change
+        let audit_log = match &audit_log_path {
+            Some(path) => {
+                let path = file_path(path);
+                let audit_log = AuditLog::open(&path)
+                    .with_context(|| format!("Failed to open audit log at {}", path.display()))?;
+                tracing::info!("Recording gene pool audit log to {}", path.display());
+                Some(audit_log)
+            }
+            None => None,
+        };
+
+        // Optional JSON Lines archive of evicted genomes
+        let archive = match &archive_path {
+            Some(path) => {
+                let path = file_path(path);
+                let archive = GenomeArchive::open(&path).with_context(|| {
+                    format!("Failed to open genome archive at {}", path.display())
+                })?;
+                tracing::info!(
+                    "Archiving evicted genomes to {} ({} already archived)",
+                    path.display(),
+                    archive.len()
+                );
+                Some(archive)
+            }
+            None => None,
+        };
+
+        let selection = config.selection.unwrap_or_else(|| selection.clone());
+        tracing::info!(
+            experiment = %config.name,
+            weight = config.weight,
+            ?selection,
+            "Starting experiment"
+        );
+        let gene_pool = GenePool::with_options(GenePoolOptions {
+            audit_log,
+            selection: selection.clone(),
+            capacity,
+            archive,
+            events: Some(events.for_experiment(&config.name)),
+        });
+        experiments.push(Experiment::new(
+            config.name,
+            config.weight,
+            config.island,
+            selection,
+            gene_pool,
+        ));
+    }
+    let experiments = Experiments::new(experiments);
+
+    // Periodically sample the gene pools for the history API
+    let sample_secs = std::env::var("HISTORY_SAMPLE_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|&v| v > 0)
+        .unwrap_or(10);
+    let sampled = experiments.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(sample_secs));
+        loop {
+            interval.tick().await;
+            for experiment in sampled.all() {
+                experiment.gene_pool.record_sample().await;
+            }
+        }
+    });
+
+    // Announce clients that stopped requesting work
+    let presence = events.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            presence.expire_clients(CLIENT_TIMEOUT);
+        }
+    });
+
+    // API keys, optionally persisted to a JSON file
+    let keys_path = std::env::var("API_KEYS_PATH").ok().map(PathBuf::from);
+    let registry = KeyRegistry::load(keys_path)?;
+
+    // Shared secrets, registered as keys that are never persisted
+    for (var, key_id, scope) in [
+        ("ADMIN_TOKEN", ADMIN_TOKEN_KEY_ID, Scope::Admin),
+        ("WORKER_TOKEN", WORKER_TOKEN_KEY_ID, Scope::Worker),
+    ] {
+        if let Some(token) = std::env::var(var).ok().filter(|t| !t.is_empty()) {
+            registry.insert_ephemeral(ApiKey {
+                key_id: key_id.to_string(),
+                secret: token,
+                scopes: vec![scope],
+                description: var.to_string(),
+                revoked: false,
+                created_ms: unix_millis(),
+            });
+        }
+    }
+
+    // Which endpoint groups need a key besides the admin API
+    let required = match std::env::var("AUTH_REQUIRED") {
+        Ok(value) => value
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(Scope::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(anyhow::Error::msg)
+            .context("Invalid AUTH_REQUIRED")?,
+        Err(_) => Vec::new(),
+    };
+    let auth = AuthConfig::new(registry, required);
+    tracing::info!(
+        keys = auth.registry.len(),
+        worker = auth.requires(Scope::Worker),
+        read_only = auth.requires(Scope::ReadOnly),
+        "Authentication configured"
+    );
+
+    // Browser build of the simulation, produced by wasm-pack from the wasm crate
+    let wasm_dir = std::env::var("WASM_DIR")
+        .unwrap_or_else(|_| concat!(env!("CARGO_MANIFEST_DIR"), "/static/wasm").to_string());
+    if !std::path::Path::new(&wasm_dir).join("sim_wasm.js").exists() {
+        tracing::warn!(
+            "No simulation WebAssembly build in {}; the dashboard preview is disabled",
+            wasm_dir
+        );
+    }
+
+    let cors = cors_layer(std::env::var("CORS_ALLOWED_ORIGINS").ok().as_deref())?;
+
     let state = AppState {
-        gene_pool: GenePool::new(),
+        experiments,
+        admin: AdminState::default(),
+        auth,
+        validator: Validator::default(),
+        events,
+        spectators: SpectatorHub::default(),
     };
 
-    let app = Router::new()
-        .route("/api/work/request", post(handle_work_request))
-        .route("/api/work/submit", post(handle_work_submit))
-        .route("/api/stats", get(handle_stats))
-        .route("/health", get(web::health))
-        .route("/healthz", get(web::health))
-        .route("/", get(web::index))
-        .layer(CorsLayer::permissive())
+    let app = api_router(&state)
+        .merge(web::router())
+        .nest_service("/wasm", ServeDir::new(wasm_dir))
+        .layer(cors)
         .with_state(state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
@@ -39,51 +293,240 @@ pub async fn run() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Worker, read and admin API routes, each behind its authentication layer
+fn api_router(state: &AppState) -> Router<AppState> {
+    let work = Router::new()
+        .route("/api/work/request", post(handle_work_request))
+        .route("/api/work/submit", post(handle_work_submit))
+        .route("/api/spectate/frames", post(spectate::handle_frame))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_worker,
+        ));
+
+    let read = Router::new()
+        .route("/api/experiments", get(handle_experiments))
+        .route("/api/stats", get(handle_stats))
+        .route("/api/history", get(handle_history))
+        .route("/api/genomes", get(handle_list_genomes))
+        .route("/api/genomes/:id", get(handle_get_genome))
+        .route("/api/genomes/:id/ancestry", get(handle_get_ancestry))
+        .route("/api/genomes/:id/descendants", get(handle_get_descendants))
+        .route("/api/lineages", get(handle_lineages))
+        .route("/api/genes", get(handle_genes))
+        .route("/api/clients", get(handle_clients))
+        .route("/api/events", get(events::handle_events))
+        .route("/api/spectate", get(spectate::handle_islands))
+        .route("/api/spectate/:work_id", get(spectate::handle_latest))
+        .route(
+            "/api/spectate/:work_id/stream",
+            get(spectate::handle_stream),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_read,
+        ));
+
+    Router::new()
+        .merge(work)
+        .merge(read)
+        .nest("/api/admin", admin::router(state.clone()))
+}
+
+/// CORS policy from `CORS_ALLOWED_ORIGINS`: unset allows no cross-origin
+/// requests, `*` allows any origin, otherwise a comma-separated origin list
+fn cors_layer(origins: Option<&str>) -> anyhow::Result<CorsLayer> {
+    let origins = match origins.map(str::trim) {
+        None | Some("") => return Ok(CorsLayer::new()),
+        Some("*") => {
+            return Ok(CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods(Any)
+                .allow_headers(Any))
+        }
+        Some(origins) => origins
+            .split(',')
+            .map(|o| HeaderValue::from_str(o.trim()))
+            .collect::<Result<Vec<_>, _>>()
+            .context("Invalid CORS_ALLOWED_ORIGINS")?,
+    };
+
+    Ok(CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::GET, Method::POST, Method::PUT])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            HeaderName::from_static(KEY_ID_HEADER),
+            HeaderName::from_static(TIMESTAMP_HEADER),
+            HeaderName::from_static(SIGNATURE_HEADER),
+        ]))
+}
+
+/// Per-experiment variant of a file path: `audit.jsonl` becomes `audit-<experiment>.jsonl`
+fn experiment_path(path: &str, experiment: &str) -> PathBuf {
+    let path = std::path::Path::new(path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let file_name = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, experiment, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, experiment),
+    };
+    path.with_file_name(file_name)
+}
+
 /// Handle work request from client
 #[axum::debug_handler]
 async fn handle_work_request(
     State(state): State<AppState>,
     Json(request): Json<WorkRequest>,
-) -> Json<WorkAssignment> {
+) -> Result<Json<WorkAssignment>, (StatusCode, Json<ServerError>)> {
+    let experiment = state
+        .experiments
+        .assign(request.experiment.as_deref(), &mut rand::thread_rng())
+        .ok_or_else(|| match request.experiment.as_deref() {
+            Some(name) => (
+                StatusCode::BAD_REQUEST,
+                Json(ServerError::InvalidRequest(format!(
+                    "Unknown experiment: {}",
+                    name
+                ))),
+            ),
+            // Every experiment is paused
+            None => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ServerError::ServerOverloaded),
+            ),
+        })?;
+
+    if experiment.is_paused() {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ServerError::ServerOverloaded),
+        ));
+    }
+
     // Register client as active for stats
-    state.gene_pool.register_client(request.client_id).await;
+    experiment
+        .gene_pool
+        .register_client(request.client_id)
+        .await;
+    state.events.client_seen(request.client_id);
     // Get seed genomes for spatial simulation (Version 2)
-    let seed_genomes_v2 = state.gene_pool.get_seed_genomes_spatial().await;
-
-    // Create work assignment for spatial simulation
-    // Both server and clients mutate to encourage exploration
-    let assignment = WorkAssignment::new_spatial(
-        seed_genomes_v2,
-        300,  // grid width
-        300,  // grid height
-        3000, // max steps
-        0.05, // client-side mutation enabled
+    let seed_genomes_v2 = experiment.gene_pool.get_seed_genomes_spatial().await;
+
+    // Create work assignment for spatial simulation, leased to this client
+    let assignment = experiment.assignment(seed_genomes_v2).await;
+    state.validator.issue(
+        assignment.work_id,
+        Lease::new(request.client_id, &experiment.name, &assignment),
+    );
+    tracing::debug!(
+        work_id = %assignment.work_id,
+        outstanding = state.validator.outstanding_leases(),
+        "Leased work"
+    );
+    state.events.emit(
+        Some(&experiment.name),
+        EventKind::WorkIssued {
+            work_id: assignment.work_id,
+            client_id: request.client_id,
+            seeds: assignment.seed_genomes_v2.len(),
+        },
     );
 
-    Json(assignment)
+    Ok(Json(assignment))
 }
 
 /// Handle work result submission from client
 async fn handle_work_submit(
     State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
     Json(result): Json<WorkResult>,
-) -> StatusCode {
+) -> Result<StatusCode, (StatusCode, Json<ServerError>)> {
+    state.events.client_seen(result.client_id);
+
+    // Check the result against the assignment it answers
+    // Trust follows the API key when workers need one, so a new client ID
+    // doesn't wipe a client's record
+    let key_id = principal.as_ref().map(|p| p.key_id.as_str());
+    let (experiment_name, trust) = match state.validator.validate(&result, key_id) {
+        Ok(accepted) => accepted,
+        Err(reason) => {
+            tracing::warn!(
+                client_id = %result.client_id,
+                work_id = %result.work_id,
+                reason,
+                "Rejected work result"
+            );
+            // The experiment named in the result, if any, is the best place to record it
+            if let Some(experiment) = state.experiments.get(result.experiment.as_deref()) {
+                experiment
+                    .gene_pool
+                    .audit(&AuditRecord::ResultRejected {
+                        timestamp_ms: unix_millis(),
+                        client_id: result.client_id,
+                        work_id: result.work_id,
+                        reason: reason.clone(),
+                    })
+                    .await;
+            }
+            state.events.emit(
+                result.experiment.as_deref(),
+                EventKind::WorkRejected {
+                    work_id: result.work_id,
+                    client_id: result.client_id,
+                    reason: reason.clone(),
+                },
+            );
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ServerError::InvalidRequest(reason)),
+            ));
+        }
+    };
+    let Some(experiment) = state.experiments.get(Some(&experiment_name)) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ServerError::InvalidRequest(format!(
+                "Unknown experiment: {}",
+                experiment_name
+            ))),
+        ));
+    };
+
+    state.spectators.finish(result.work_id);
+    state.events.emit(
+        Some(&experiment.name),
+        EventKind::WorkCompleted {
+            work_id: result.work_id,
+            client_id: result.client_id,
+            genomes: result.survival_results.len(),
+            survivors: result.survival_results.iter().map(|r| r.survived).sum(),
+            trust,
+        },
+    );
+
     // Check if this is spatial simulation results (Version 2)
     if !result.survival_results.is_empty() {
         tracing::info!(
-            "Spatial simulation result from client {} ({} steps)",
+            "Spatial simulation result from client {} for experiment {} ({} steps)",
             result.client_id,
+            experiment.name,
             result.steps_completed
         );
 
         // Submit survival results to gene pool
-        state
+        experiment
             .gene_pool
             .submit_survival_results(
                 result.client_id,
                 result.survival_results,
                 result.steps_completed,
                 result.best_genomes,
+                trust,
             )
             .await;
     } else {
@@ -94,7 +537,7 @@ async fn handle_work_submit(
             result.generations_completed
         );
 
-        state
+        experiment
             .gene_pool
             .submit_results(
                 result.client_id,
@@ -104,13 +547,120 @@ async fn handle_work_submit(
             .await;
     }
 
-    StatusCode::OK
+    Ok(StatusCode::OK)
+}
+
+/// List the experiments running on this server
+async fn handle_experiments(State(state): State<AppState>) -> Json<Vec<ExperimentSummary>> {
+    let mut summaries = Vec::with_capacity(state.experiments.all().len());
+    for (i, experiment) in state.experiments.all().iter().enumerate() {
+        let stats = experiment.gene_pool.get_stats().await;
+        summaries.push(ExperimentSummary {
+            name: experiment.name.clone(),
+            weight: experiment.weight,
+            default: i == 0,
+            paused: experiment.is_paused(),
+            island: experiment.island().await,
+            selection: experiment.selection.clone(),
+            gene_pool_size: stats.gene_pool_size,
+            active_clients: stats.active_clients,
+            total_work_units: stats.total_work_units,
+        });
+    }
+    Json(summaries)
 }
 
 /// Get global statistics
-async fn handle_stats(State(state): State<AppState>) -> Json<GlobalStats> {
-    let stats = state.gene_pool.get_stats().await;
-    Json(stats)
+async fn handle_stats(
+    State(state): State<AppState>,
+    Query(scope): Query<ExperimentQuery>,
+) -> Result<Json<GlobalStats>, StatusCode> {
+    let stats = state.gene_pool(&scope)?.get_stats().await;
+    Ok(Json(stats))
+}
+
+/// Get the recorded time series of gene pool samples
+async fn handle_history(
+    State(state): State<AppState>,
+    Query(scope): Query<ExperimentQuery>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<HistoryResponse>, StatusCode> {
+    Ok(Json(state.gene_pool(&scope)?.get_history(&query).await))
+}
+
+/// List genomes in the pool with paging, sorting and filtering
+async fn handle_list_genomes(
+    State(state): State<AppState>,
+    Query(scope): Query<ExperimentQuery>,
+    Query(query): Query<GenomeQuery>,
+) -> Result<Json<GenomeListResponse>, StatusCode> {
+    Ok(Json(state.gene_pool(&scope)?.list_genomes(&query).await))
+}
+
+/// Get the full detail of a single genome
+async fn handle_get_genome(
+    State(state): State<AppState>,
+    Path(genome_id): Path<Uuid>,
+    Query(scope): Query<ExperimentQuery>,
+) -> Result<Json<GenomeDetail>, StatusCode> {
+    state
+        .gene_pool(&scope)?
+        .get_genome(genome_id)
+        .await
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Walk a genome's ancestors
+async fn handle_get_ancestry(
+    State(state): State<AppState>,
+    Path(genome_id): Path<Uuid>,
+    Query(scope): Query<ExperimentQuery>,
+    Query(query): Query<AncestryQuery>,
+) -> Result<Json<AncestryNode>, StatusCode> {
+    state
+        .gene_pool(&scope)?
+        .get_ancestry(genome_id, query.depth())
+        .await
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Walk a genome's descendants
+async fn handle_get_descendants(
+    State(state): State<AppState>,
+    Path(genome_id): Path<Uuid>,
+    Query(scope): Query<ExperimentQuery>,
+    Query(query): Query<AncestryQuery>,
+) -> Result<Json<AncestryNode>, StatusCode> {
+    state
+        .gene_pool(&scope)?
+        .get_descendants(genome_id, query.depth())
+        .await
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Lineages ranked by their share of the pool population
+async fn handle_lineages(
+    State(state): State<AppState>,
+    Query(scope): Query<ExperimentQuery>,
+    Query(query): Query<LineageQuery>,
+) -> Result<Json<Vec<LineageSummary>>, StatusCode> {
+    Ok(Json(state.gene_pool(&scope)?.get_lineages(&query).await))
+}
+
+/// The gene schema genomes are built from
+async fn handle_genes() -> Json<&'static [GeneSpec]> {
+    Json(GENES)
+}
+
+/// Clients ranked by accepted results
+async fn handle_clients(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Json<Vec<ClientStanding>> {
+    Json(state.validator.leaderboard(query.limit()))
 }
 
 // Legacy error types are no longer used; handler returns only success paths.
@@ -125,7 +675,7 @@ mod tests {
     // #[tokio::test]
     // async fn test_work_request_handler() {
     //     let state = AppState {
-    //         gene_pool: GenePool::new(),
+    //         experiments: Experiments::new(vec![...]),
     //     };
     //
     //     let request = WorkRequest::new(Uuid::new_v4(), PROTOCOL_VERSION);
@@ -139,4 +689,73 @@ mod tests {
         let stats = pool.get_stats().await;
         assert!(stats.gene_pool_size > 0);
     }
+
+    #[tokio::test]
+    async fn test_signed_admin_request_through_router() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use shared::auth::sign_request;
+        use tower::ServiceExt;
+
+        let registry = KeyRegistry::default();
+        let key = ApiKey::generate(vec![Scope::Admin], "admin".to_string());
+        registry.insert_ephemeral(key.clone());
+        let state = AppState {
+            experiments: Experiments::new(vec![Experiment::new(
+                "control".to_string(),
+                1.0,
+                IslandSettings::default(),
+                SelectionConfig::default(),
+                GenePool::new(),
+            )]),
+            admin: AdminState::default(),
+            auth: AuthConfig::new(registry, []),
+            validator: Validator::default(),
+            events: EventBus::default(),
+            spectators: SpectatorHub::default(),
+        };
+        let app = api_router(&state).with_state(state.clone());
+
+        let now = unix_millis() / 1000;
+        let pause = |signed_path: &str| {
+            Request::post("/api/admin/pause")
+                .header(KEY_ID_HEADER, &key.key_id)
+                .header(TIMESTAMP_HEADER, now.to_string())
+                .header(
+                    SIGNATURE_HEADER,
+                    sign_request(&key.secret, now, "POST", signed_path, b""),
+                )
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // A signature over the path without the nest prefix is not accepted
+        let response = app.clone().oneshot(pause("/pause")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(!state.experiments.get(None).unwrap().is_paused());
+
+        let response = app
+            .clone()
+            .oneshot(pause("/api/admin/pause"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(state.experiments.get(None).unwrap().is_paused());
+
+        // The same signed request can't be replayed
+        let response = app.oneshot(pause("/api/admin/pause")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_experiment_path() {
+        assert_eq!(
+            experiment_path("/data/audit.jsonl", "control"),
+            PathBuf::from("/data/audit-control.jsonl")
+        );
+        assert_eq!(
+            experiment_path("archive", "variant"),
+            PathBuf::from("archive-variant")
+        );
+    }
 }
@@ -0,0 +1,404 @@
+use crate::audit::unix_millis;
+use crate::experiment::ExperimentQuery;
+use crate::server::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use futures_util::stream::{self, Stream};
+use serde::Serialize;
+use shared::{ServerError, SpectatorFrame, Terrain};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Frames of one island closer together than this are dropped
+const MIN_FRAME_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Islands that haven't sent a frame for this long are no longer live
+const ISLAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most creatures a frame may list
+const MAX_FRAME_CREATURES: usize = 5000;
+
+/// Most food blocks a frame may carry
+const MAX_FRAME_FOOD_BLOCKS: usize = 200 * 200;
+
+/// Updates buffered per viewer; slow viewers just miss frames
+const UPDATE_BUFFER: usize = 64;
+
+/// Something viewers of an island need to know
+#[derive(Debug, Clone)]
+pub enum SpectatorUpdate {
+    Frame(Arc<SpectatorFrame>),
+
+    /// The island's result was submitted, so no more frames will follow
+    Finished(Uuid),
+}
+
+impl SpectatorUpdate {
+    fn work_id(&self) -> Uuid {
+        match self {
+            SpectatorUpdate::Frame(frame) => frame.work_id,
+            SpectatorUpdate::Finished(work_id) => *work_id,
+        }
+    }
+}
+
+/// Entry in the response body for `GET /api/spectate`
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveIsland {
+    pub work_id: Uuid,
+    pub client_id: Uuid,
+    pub experiment: Option<String>,
+    pub step: u32,
+    pub max_steps: u32,
+    pub world_width: usize,
+    pub world_height: usize,
+    pub population: usize,
+    pub updated_ms: u64,
+}
+
+struct Island {
+    frame: Arc<SpectatorFrame>,
+    received: Instant,
+    received_ms: u64,
+}
+
+/// Latest frame of every island clients are streaming, relayed to viewers
+#[derive(Clone)]
+pub struct SpectatorHub {
+    islands: Arc<Mutex<HashMap<Uuid, Island>>>,
+    sender: broadcast::Sender<SpectatorUpdate>,
+}
+
+impl Default for SpectatorHub {
+    fn default() -> Self {
+        Self {
+            islands: Arc::default(),
+            sender: broadcast::channel(UPDATE_BUFFER).0,
+        }
+    }
+}
+
+impl SpectatorHub {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, Island>> {
+        match self.islands.lock() {
+            Ok(islands) => islands,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Store and relay a frame. Returns false if the island sent one too recently.
+    pub fn publish(&self, frame: SpectatorFrame) -> bool {
+        let frame = Arc::new(frame);
+        {
+            let mut islands = self.lock();
+            islands.retain(|_, island| island.received.elapsed() < ISLAND_TIMEOUT);
+            if islands
+                .get(&frame.work_id)
+                .is_some_and(|island| island.received.elapsed() < MIN_FRAME_INTERVAL)
+            {
+                return false;
+            }
+            islands.insert(
+                frame.work_id,
+                Island {
+                    frame: frame.clone(),
+                    received: Instant::now(),
+                    received_ms: unix_millis(),
+                },
+            );
+        }
+        // Sending only fails when nobody is watching
+        let _ = self.sender.send(SpectatorUpdate::Frame(frame));
+        true
+    }
+
+    /// Forget an island whose result came in and tell its viewers
+    pub fn finish(&self, work_id: Uuid) {
+        if self.lock().remove(&work_id).is_some() {
+            let _ = self.sender.send(SpectatorUpdate::Finished(work_id));
+        }
+    }
+
+    /// Islands that sent a frame recently, most recently updated first
+    pub fn islands(&self, experiment: Option<&str>) -> Vec<LiveIsland> {
+        let mut islands: Vec<LiveIsland> = self
+            .lock()
+            .values()
+            .filter(|island| island.received.elapsed() < ISLAND_TIMEOUT)
+            .filter(|island| {
+                experiment.is_none() || island.frame.experiment.as_deref() == experiment
+            })
+            .map(|island| LiveIsland {
+                work_id: island.frame.work_id,
+                client_id: island.frame.client_id,
+                experiment: island.frame.experiment.clone(),
+                step: island.frame.step,
+                max_steps: island.frame.max_steps,
+                world_width: island.frame.world_width,
+                world_height: island.frame.world_height,
+                population: island.frame.population,
+                updated_ms: island.received_ms,
+            })
+            .collect();
+        islands.sort_by_key(|island| std::cmp::Reverse(island.updated_ms));
+        islands
+    }
+
+    pub fn latest(&self, work_id: Uuid) -> Option<Arc<SpectatorFrame>> {
+        self.lock()
+            .get(&work_id)
+            .filter(|island| island.received.elapsed() < ISLAND_TIMEOUT)
+            .map(|island| island.frame.clone())
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<SpectatorUpdate> {
+        self.sender.subscribe()
+    }
+}
+
+/// Check that a frame is internally consistent and small enough to relay
+#[allow(clippy::manual_is_multiple_of)]
+pub fn check_frame_shape(frame: &SpectatorFrame) -> Result<(), String> {
+    if frame.creatures.len() % 3 != 0 {
+        return Err("Creatures must be [x, y, lineage] triples".to_string());
+    }
+    let listed = frame.creatures.len() / 3;
+    if listed > MAX_FRAME_CREATURES {
+        return Err(format!(
+            "Frame lists {} creatures, more than {}",
+            listed, MAX_FRAME_CREATURES
+        ));
+    }
+    if listed > frame.population {
+        return Err(format!(
+            "Frame lists {} creatures but reports a population of {}",
+            listed, frame.population
+        ));
+    }
+    let in_world = frame.creatures.chunks(3).all(|c| {
+        (c[0] as usize) < frame.world_width
+            && (c[1] as usize) < frame.world_height
+            && (c[2] as usize) <= frame.lineages.len()
+    });
+    if !in_world {
+        return Err("Creature outside the world or of an unknown lineage".to_string());
+    }
+
+    if frame.food_cell == 0 {
+        return Err("food_cell must be positive".to_string());
+    }
+    let blocks = frame.world_width.div_ceil(frame.food_cell) as u128
+        * frame.world_height.div_ceil(frame.food_cell) as u128;
+    if blocks > MAX_FRAME_FOOD_BLOCKS as u128 {
+        return Err(format!(
+            "Frame has {} food blocks, more than {}",
+            blocks, MAX_FRAME_FOOD_BLOCKS
+        ));
+    }
+    if frame.food.len() as u128 != blocks {
+        return Err(format!(
+            "Expected {} food blocks, got {}",
+            blocks,
+            frame.food.len()
+        ));
+    }
+    if !frame.terrain.is_empty() && frame.terrain.len() as u128 != blocks {
+        return Err(format!(
+            "Expected {} terrain blocks, got {}",
+            blocks,
+            frame.terrain.len()
+        ));
+    }
+    if frame
+        .terrain
+        .iter()
+        .any(|&t| t as usize >= Terrain::ALL.len())
+    {
+        return Err("Unknown terrain in frame".to_string());
+    }
+    Ok(())
+}
+
+/// Accept a frame from the client running the island
+pub async fn handle_frame(
+    State(state): State<AppState>,
+    Json(mut frame): Json<SpectatorFrame>,
+) -> Result<StatusCode, (StatusCode, Json<ServerError>)> {
+    let experiment = check_frame_shape(&frame)
+        .and_then(|()| state.validator.check_frame(&frame))
+        .map_err(|reason| {
+            tracing::debug!(
+                client_id = %frame.client_id,
+                work_id = %frame.work_id,
+                reason,
+                "Rejected spectator frame"
+            );
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ServerError::InvalidRequest(reason)),
+            )
+        })?;
+    frame.experiment = Some(experiment);
+
+    if state.spectators.publish(frame) {
+        Ok(StatusCode::OK)
+    } else {
+        Ok(StatusCode::TOO_MANY_REQUESTS)
+    }
+}
+
+/// List islands currently being streamed
+pub async fn handle_islands(
+    State(state): State<AppState>,
+    Query(scope): Query<ExperimentQuery>,
+) -> Json<Vec<LiveIsland>> {
+    Json(state.spectators.islands(scope.experiment.as_deref()))
+}
+
+/// Latest frame of one island
+pub async fn handle_latest(
+    State(state): State<AppState>,
+    Path(work_id): Path<Uuid>,
+) -> Result<Json<SpectatorFrame>, StatusCode> {
+    state
+        .spectators
+        .latest(work_id)
+        .map(|frame| Json((*frame).clone()))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Stream one island's frames as Server-Sent Events: `frame` events carrying a
+/// frame, starting with the latest one, then a `finished` event once it's done
+pub async fn handle_stream(
+    State(state): State<AppState>,
+    Path(work_id): Path<Uuid>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = state.spectators.subscribe();
+    let latest = state.spectators.latest(work_id);
+
+    let stream = stream::unfold(
+        (receiver, latest, false),
+        move |(mut receiver, latest, done)| async move {
+            if done {
+                return None;
+            }
+            if let Some(frame) = latest {
+                return Some((Ok(frame_event(&frame)), (receiver, None, false)));
+            }
+            loop {
+                match receiver.recv().await {
+                    Ok(update) if update.work_id() != work_id => continue,
+                    Ok(SpectatorUpdate::Frame(frame)) => {
+                        return Some((Ok(frame_event(&frame)), (receiver, None, false)));
+                    }
+                    Ok(SpectatorUpdate::Finished(_)) => {
+                        let event = Event::default().event("finished").data(work_id.to_string());
+                        return Some((Ok(event), (receiver, None, true)));
+                    }
+                    // Frames are snapshots, so missed ones don't matter
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        },
+    );
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+fn frame_event(frame: &SpectatorFrame) -> Event {
+    Event::default()
+        .event("frame")
+        .json_data(frame)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(work_id: Uuid) -> SpectatorFrame {
+        SpectatorFrame {
+            work_id,
+            client_id: Uuid::new_v4(),
+            experiment: Some("default".to_string()),
+            step: 1,
+            max_steps: 10,
+            world_width: 10,
+            world_height: 5,
+            lineages: vec![Uuid::new_v4()],
+            creatures: vec![9, 4, 0, 0, 0, 1],
+            population: 2,
+            food_cell: 3,
+            food: vec![0; 8],
+            terrain: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_frame_shape() {
+        let work_id = Uuid::new_v4();
+        assert!(check_frame_shape(&frame(work_id)).is_ok());
+
+        let mut f = frame(work_id);
+        f.creatures.push(1);
+        assert!(check_frame_shape(&f).is_err());
+
+        f = frame(work_id);
+        f.creatures[0] = 10;
+        assert!(check_frame_shape(&f).unwrap_err().contains("outside"));
+
+        f = frame(work_id);
+        f.population = 1;
+        assert!(check_frame_shape(&f).unwrap_err().contains("population"));
+
+        f = frame(work_id);
+        f.food.pop();
+        assert!(check_frame_shape(&f).unwrap_err().contains("food blocks"));
+
+        f = frame(work_id);
+        f.terrain = vec![0; f.food.len()];
+        assert!(check_frame_shape(&f).is_ok());
+        f.terrain.pop();
+        assert!(check_frame_shape(&f)
+            .unwrap_err()
+            .contains("terrain blocks"));
+        f.terrain.push(Terrain::ALL.len() as u8);
+        assert!(check_frame_shape(&f).unwrap_err().contains("terrain"));
+
+        f = frame(work_id);
+        f.world_width = usize::MAX;
+        f.food_cell = 1;
+        assert!(check_frame_shape(&f).is_err());
+    }
+
+    #[test]
+    fn test_hub_rate_limits_and_finishes() {
+        let hub = SpectatorHub::default();
+        let mut receiver = hub.subscribe();
+        let work_id = Uuid::new_v4();
+
+        assert!(hub.publish(frame(work_id)));
+        assert!(!hub.publish(frame(work_id)));
+        assert!(hub.publish(frame(Uuid::new_v4())));
+        assert_eq!(hub.islands(None).len(), 2);
+        assert_eq!(hub.islands(Some("other")).len(), 0);
+        assert_eq!(hub.latest(work_id).unwrap().work_id, work_id);
+
+        hub.finish(work_id);
+        assert!(hub.latest(work_id).is_none());
+        let updates: Vec<Uuid> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|u| u.work_id())
+            .collect();
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates[2], work_id);
+    }
+}
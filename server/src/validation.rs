@@ -0,0 +1,717 @@
+use serde::{Deserialize, Serialize};
+use shared::{SpectatorFrame, WorkAssignment, WorkResult};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How long a client has to submit the result of an assignment
+pub(crate) const LEASE_TTL: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// Most leases kept at once; the oldest are dropped first
+const MAX_LEASES: usize = 100_000;
+
+/// Most evolved genomes a client reports per result
+const MAX_EVOLVED_GENOMES: usize = 10;
+
+/// Trust of an API key the server hasn't seen results from before
+const INITIAL_TRUST: f64 = 0.5;
+
+/// Lowest trust a client can fall to, and the trust of unauthenticated
+/// clients the server hasn't seen before, so a client gains nothing by
+/// reporting under a new ID
+const MIN_TRUST: f64 = 0.05;
+
+/// Fraction of the remaining distance to full trust gained per accepted result
+const TRUST_GAIN: f64 = 0.05;
+
+/// Factor applied to a client's trust for every rejected result
+const TRUST_PENALTY: f64 = 0.5;
+
+/// Most clients whose trust is remembered. When full, the least recently
+/// seen client is forgotten if it has been idle for longer than a lease
+/// lasts; otherwise new clients are not remembered and keep `MIN_TRUST`.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// What the server handed out for one work assignment
+#[derive(Debug, Clone)]
+pub struct Lease {
+    pub client_id: Uuid,
+    pub experiment: String,
+    pub seed_ids: HashSet<Uuid>,
+    pub max_steps: u32,
+    pub population_limit: u32,
+    issued: Instant,
+}
+
+impl Lease {
+    pub fn new(client_id: Uuid, experiment: &str, assignment: &WorkAssignment) -> Self {
+        let seed_ids: HashSet<Uuid> = assignment
+            .seed_genomes_v2
+            .iter()
+            .map(|g| g.genome_id)
+            .collect();
+        // Islands cap reproduction at half the world area, but never remove
+        // seeds; every island of an archipelago starts from all the seeds
+        let population_limit = ((assignment.grid_width * assignment.grid_height / 2)
+            .max(seed_ids.len())
+            * assignment.migration.islands.max(1))
+        .min(u32::MAX as usize) as u32;
+
+        Self {
+            client_id,
+            experiment: experiment.to_string(),
+            seed_ids,
+            max_steps: assignment.max_steps,
+            population_limit,
+            issued: Instant::now(),
+        }
+    }
+
+    /// Check a result against the invariants of the assignment it answers
+    pub fn check(&self, result: &WorkResult) -> Result<(), String> {
+        if result.client_id != self.client_id {
+            return Err(format!(
+                "Work {} was assigned to a different client",
+                result.work_id
+            ));
+        }
+        if let Some(experiment) = &result.experiment {
+            if *experiment != self.experiment {
+                return Err(format!(
+                    "Work {} belongs to experiment {}, not {}",
+                    result.work_id, self.experiment, experiment
+                ));
+            }
+        }
+        if !self.seed_ids.is_empty() && result.survival_results.is_empty() {
+            return Err("Missing survival results".to_string());
+        }
+        if !result.survival_results.is_empty() && result.steps_completed != self.max_steps {
+            return Err(format!(
+                "steps_completed {} does not match the assigned {} steps",
+                result.steps_completed, self.max_steps
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut total_survivors: u64 = 0;
+        for survival in &result.survival_results {
+            if !self.seed_ids.contains(&survival.genome_id) {
+                return Err(format!(
+                    "Genome {} was not in the assignment",
+                    survival.genome_id
+                ));
+            }
+            if !seen.insert(survival.genome_id) {
+                return Err(format!(
+                    "Duplicate result for genome {}",
+                    survival.genome_id
+                ));
+            }
+            if survival.survived > survival.total_spawned {
+                return Err(format!(
+                    "Genome {}: survived {} exceeds total_spawned {}",
+                    survival.genome_id, survival.survived, survival.total_spawned
+                ));
+            }
+            total_survivors += survival.survived as u64;
+        }
+        if total_survivors > self.population_limit as u64 {
+            return Err(format!(
+                "{} survivors exceed the island population limit of {}",
+                total_survivors, self.population_limit
+            ));
+        }
+
+        if result.best_genomes.len() > MAX_EVOLVED_GENOMES {
+            return Err(format!(
+                "At most {} evolved genomes may be reported",
+                MAX_EVOLVED_GENOMES
+            ));
+        }
+        for evolved in &result.best_genomes {
+            if let Some(lineage) = evolved.genome_id {
+                if !self.seed_ids.contains(&lineage) {
+                    return Err(format!(
+                        "Evolved genome lineage {} was not in the assignment",
+                        lineage
+                    ));
+                }
+            }
+            if !evolved.genome.is_valid() {
+                return Err("Evolved genome has traits outside their gene ranges".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whose results a trust score follows: the API key that authenticated
+/// them, or else the client ID they were reported under
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum TrustKey {
+    Key(String),
+    Client(Uuid),
+}
+
+/// Trust the server places in one client's results
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientTrust {
+    /// Client ID of the most recent result
+    pub client_id: Uuid,
+
+    /// API key the results were authenticated with, if requests need one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+
+    /// From 0 to 1; scales how much the client's results move populations
+    pub trust: f64,
+
+    pub accepted: u64,
+    pub rejected: u64,
+
+    /// Reason the most recent rejected result was refused
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_rejection: Option<String>,
+
+    #[serde(skip)]
+    last_seen: Instant,
+}
+
+impl ClientTrust {
+    fn new(client_id: Uuid, key_id: Option<&str>) -> Self {
+        Self {
+            client_id,
+            key_id: key_id.map(str::to_string),
+            // Keys are handed out by an admin; client IDs are made up by the client
+            trust: if key_id.is_some() {
+                INITIAL_TRUST
+            } else {
+                MIN_TRUST
+            },
+            accepted: 0,
+            rejected: 0,
+            last_rejection: None,
+            last_seen: Instant::now(),
+        }
+    }
+}
+
+/// Entry in the response body for `GET /api/clients`
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientStanding {
+    /// 1 for the client with the most accepted results
+    pub rank: usize,
+    pub client_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub trust: f64,
+
+    /// Seconds since the client last submitted a result
+    pub idle_seconds: u64,
+}
+
+/// Query parameters for `GET /api/clients`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LeaderboardQuery {
+    /// Maximum number of clients to return (default 50, max 1000)
+    pub limit: Option<usize>,
+}
+
+impl LeaderboardQuery {
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(50).min(1000)
+    }
+}
+
+#[derive(Default)]
+struct ValidatorInner {
+    leases: HashMap<Uuid, Lease>,
+
+    /// Work IDs in the order their leases were issued, for expiry
+    issued: VecDeque<Uuid>,
+
+    clients: HashMap<TrustKey, ClientTrust>,
+
+    /// Remembered clients ordered by when they were last seen, for eviction
+    by_last_seen: BTreeSet<(Instant, TrustKey)>,
+}
+
+/// Outstanding work leases and per-client trust, shared by all experiments
+#[derive(Clone, Default)]
+pub struct Validator {
+    inner: Arc<Mutex<ValidatorInner>>,
+}
+
+impl Validator {
+    fn lock(&self) -> std::sync::MutexGuard<'_, ValidatorInner> {
+        match self.inner.lock() {
+            Ok(inner) => inner,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Remember an assignment so its result can be checked
+    pub fn issue(&self, work_id: Uuid, lease: Lease) {
+        let mut inner = self.lock();
+        inner.expire_leases();
+        while inner.leases.len() >= MAX_LEASES {
+            let Some(oldest) = inner.issued.pop_front() else {
+                break;
+            };
+            inner.leases.remove(&oldest);
+        }
+        inner.issued.push_back(work_id);
+        inner.leases.insert(work_id, lease);
+    }
+
+    /// Check a result against its lease and update the trust of the key it
+    /// was authenticated with, or of its client without one.
+    /// The lease is consumed either way, so a result can't be submitted twice.
+    /// Returns the experiment the result belongs to and the client's trust.
+    pub fn validate(
+        &self,
+        result: &WorkResult,
+        key_id: Option<&str>,
+    ) -> Result<(String, f64), String> {
+        let mut inner = self.lock();
+        inner.expire_leases();
+
+        let Some(lease) = inner.leases.get(&result.work_id) else {
+            return Err(format!("Unknown or expired work {}", result.work_id));
+        };
+        // Only the client holding the lease can use it up or lose trust over it,
+        // so nobody can hurt another client by sending results in its name
+        if lease.client_id != result.client_id {
+            return Err(format!(
+                "Work {} was assigned to a different client",
+                result.work_id
+            ));
+        }
+        let lease = inner
+            .leases
+            .remove(&result.work_id)
+            .expect("lease looked up above");
+        let outcome = lease.check(result).map(|()| lease.experiment);
+
+        // A client that can't be remembered is judged as a new one
+        let mut untracked;
+        let client = match inner.client(result.client_id, key_id, Instant::now()) {
+            Some(client) => client,
+            None => {
+                untracked = ClientTrust::new(result.client_id, key_id);
+                &mut untracked
+            }
+        };
+        match outcome {
+            Ok(experiment) => {
+                client.accepted += 1;
+                client.trust += (1.0 - client.trust) * TRUST_GAIN;
+                Ok((experiment, client.trust))
+            }
+            Err(reason) => {
+                client.rejected += 1;
+                client.trust = (client.trust * TRUST_PENALTY).max(MIN_TRUST);
+                client.last_rejection = Some(reason.clone());
+                Err(reason)
+            }
+        }
+    }
+
+    /// Check that a spectator frame comes from the client running the work it
+    /// names, without consuming the lease. Returns the work's experiment.
+    pub fn check_frame(&self, frame: &SpectatorFrame) -> Result<String, String> {
+        let inner = self.lock();
+        let Some(lease) = inner.leases.get(&frame.work_id) else {
+            return Err(format!("Unknown or expired work {}", frame.work_id));
+        };
+        if lease.client_id != frame.client_id {
+            return Err(format!(
+                "Work {} was assigned to a different client",
+                frame.work_id
+            ));
+        }
+        if frame.step > lease.max_steps || frame.max_steps != lease.max_steps {
+            return Err(format!(
+                "Step {} of {} doesn't match the assignment's {} steps",
+                frame.step, frame.max_steps, lease.max_steps
+            ));
+        }
+        if let Some(id) = frame
+            .lineages
+            .iter()
+            .find(|id| !lease.seed_ids.contains(id))
+        {
+            return Err(format!("Lineage {} was not in the assignment", id));
+        }
+        Ok(lease.experiment.clone())
+    }
+
+    /// Trust scores of all remembered clients, least trusted first
+    pub fn clients(&self) -> Vec<ClientTrust> {
+        let mut clients: Vec<ClientTrust> = self.lock().clients.values().cloned().collect();
+        clients.sort_by(|a, b| a.trust.total_cmp(&b.trust));
+        clients
+    }
+
+    /// Clients ranked by accepted results, then by trust
+    pub fn leaderboard(&self, limit: usize) -> Vec<ClientStanding> {
+        let mut clients: Vec<ClientTrust> = self.lock().clients.values().cloned().collect();
+        clients.sort_by(|a, b| {
+            b.accepted
+                .cmp(&a.accepted)
+                .then(b.trust.total_cmp(&a.trust))
+        });
+        clients
+            .into_iter()
+            .take(limit)
+            .enumerate()
+            .map(|(i, client)| ClientStanding {
+                rank: i + 1,
+                client_id: client.client_id,
+                key_id: client.key_id,
+                accepted: client.accepted,
+                rejected: client.rejected,
+                trust: client.trust,
+                idle_seconds: client.last_seen.elapsed().as_secs(),
+            })
+            .collect()
+    }
+
+    pub fn outstanding_leases(&self) -> usize {
+        self.lock().leases.len()
+    }
+}
+
+impl ValidatorInner {
+    fn expire_leases(&mut self) {
+        while let Some(work_id) = self.issued.front() {
+            match self.leases.get(work_id) {
+                Some(lease) if lease.issued.elapsed() < LEASE_TTL => break,
+                _ => {
+                    // Expired, or already consumed by a submission
+                    let work_id = *work_id;
+                    self.issued.pop_front();
+                    self.leases.remove(&work_id);
+                }
+            }
+        }
+    }
+
+    /// The remembered trust of a result's key or client, marking it seen at
+    /// `now`. None if it is new and there is no room to remember it.
+    fn client(
+        &mut self,
+        client_id: Uuid,
+        key_id: Option<&str>,
+        now: Instant,
+    ) -> Option<&mut ClientTrust> {
+        let key = match key_id {
+            Some(key_id) => TrustKey::Key(key_id.to_string()),
+            None => TrustKey::Client(client_id),
+        };
+        if !self.clients.contains_key(&key) && self.clients.len() >= MAX_TRACKED_CLIENTS {
+            // Only clients idle for longer than a lease lasts make room, so
+            // new IDs can't push out clients that are still working
+            let (last_seen, stale) = self.by_last_seen.first()?.clone();
+            if now.saturating_duration_since(last_seen) < LEASE_TTL {
+                return None;
+            }
+            self.by_last_seen.remove(&(last_seen, stale.clone()));
+            self.clients.remove(&stale);
+        }
+
+        let client = self
+            .clients
+            .entry(key.clone())
+            .or_insert_with(|| ClientTrust::new(client_id, key_id));
+        self.by_last_seen.remove(&(client.last_seen, key.clone()));
+        client.last_seen = now;
+        client.client_id = client_id;
+        self.by_last_seen.insert((now, key));
+        Some(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{Genome, GenomeWithFitness, GenomeWithId, SurvivalResult};
+
+    fn assignment() -> WorkAssignment {
+        let seeds = (0..3)
+            .map(|_| GenomeWithId {
+                genome_id: Uuid::new_v4(),
+                genome: Genome::random(),
+            })
+            .collect();
+        WorkAssignment::new_spatial(seeds, 10, 10, 500, 0.05)
+    }
+
+    fn result(client_id: Uuid, assignment: &WorkAssignment) -> WorkResult {
+        WorkResult {
+            work_id: assignment.work_id,
+            client_id,
+            survival_results: assignment
+                .seed_genomes_v2
+                .iter()
+                .map(|g| SurvivalResult {
+                    genome_id: g.genome_id,
+                    survived: 5,
+                    total_spawned: 8,
+                    total_food_eaten: 40,
+                })
+                .collect(),
+            steps_completed: assignment.max_steps,
+            experiment: Some("default".to_string()),
+            best_genomes: Vec::new(),
+            generations_completed: 0,
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn test_lease_invariants() {
+        let client_id = Uuid::new_v4();
+        let assignment = assignment();
+        let lease = Lease::new(client_id, "default", &assignment);
+        assert_eq!(lease.population_limit, 50);
+        assert!(lease.check(&result(client_id, &assignment)).is_ok());
+
+        let mut r = result(Uuid::new_v4(), &assignment);
+        assert!(lease.check(&r).unwrap_err().contains("different client"));
+
+        r = result(client_id, &assignment);
+        r.survival_results[0].survived = 9;
+        assert!(lease
+            .check(&r)
+            .unwrap_err()
+            .contains("exceeds total_spawned"));
+
+        r = result(client_id, &assignment);
+        for s in &mut r.survival_results {
+            s.survived = 20;
+            s.total_spawned = 20;
+        }
+        assert!(lease.check(&r).unwrap_err().contains("population limit"));
+
+        // Each island of an archipelago may hold a full population
+        let mut archipelago = assignment.clone();
+        archipelago.migration.islands = 2;
+        let archipelago_lease = Lease::new(client_id, "default", &archipelago);
+        assert_eq!(archipelago_lease.population_limit, 100);
+        assert!(archipelago_lease.check(&r).is_ok());
+
+        r = result(client_id, &assignment);
+        r.steps_completed = 10;
+        assert!(lease.check(&r).unwrap_err().contains("steps_completed"));
+
+        r = result(client_id, &assignment);
+        r.survival_results[1].genome_id = Uuid::new_v4();
+        assert!(lease
+            .check(&r)
+            .unwrap_err()
+            .contains("not in the assignment"));
+
+        r = result(client_id, &assignment);
+        r.survival_results[1].genome_id = r.survival_results[0].genome_id;
+        assert!(lease.check(&r).unwrap_err().contains("Duplicate"));
+
+        r = result(client_id, &assignment);
+        r.experiment = Some("other".to_string());
+        assert!(lease.check(&r).unwrap_err().contains("experiment"));
+
+        r = result(client_id, &assignment);
+        let mut genome = Genome::random();
+        genome.speed = 3.0;
+        r.best_genomes.push(GenomeWithFitness {
+            genome,
+            fitness: 0.0,
+            genome_id: Some(assignment.seed_genomes_v2[0].genome_id),
+            empirical: None,
+        });
+        assert!(lease.check(&r).unwrap_err().contains("traits"));
+    }
+
+    #[test]
+    fn test_trust_and_single_use_leases() {
+        let validator = Validator::default();
+        let client_id = Uuid::new_v4();
+
+        let assignment = assignment();
+        validator.issue(
+            assignment.work_id,
+            Lease::new(client_id, "default", &assignment),
+        );
+        let (experiment, trust) = validator
+            .validate(&result(client_id, &assignment), Some("worker"))
+            .unwrap();
+        assert_eq!(experiment, "default");
+        assert!(trust > INITIAL_TRUST);
+        assert_eq!(validator.outstanding_leases(), 0);
+
+        // Replays are rejected without touching trust
+        let err = validator
+            .validate(&result(client_id, &assignment), Some("worker"))
+            .unwrap_err();
+        assert!(err.contains("Unknown or expired"));
+
+        // An implausible result uses up the lease and halves the client's trust
+        let assignment = self::assignment();
+        validator.issue(
+            assignment.work_id,
+            Lease::new(client_id, "default", &assignment),
+        );
+        let mut bad = result(client_id, &assignment);
+        bad.survival_results[0].survived = 100;
+        assert!(validator
+            .validate(&result(Uuid::new_v4(), &assignment), None)
+            .is_err());
+        assert!(validator.validate(&bad, Some("worker")).is_err());
+        assert_eq!(validator.outstanding_leases(), 0);
+
+        let clients = validator.clients();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].accepted, 1);
+        assert_eq!(clients[0].rejected, 1);
+        assert!((clients[0].trust - trust * TRUST_PENALTY).abs() < 1e-12);
+
+        // A second client with more accepted results leads the leaderboard
+        let other = Uuid::new_v4();
+        for _ in 0..2 {
+            let assignment = self::assignment();
+            validator.issue(
+                assignment.work_id,
+                Lease::new(other, "default", &assignment),
+            );
+            validator
+                .validate(&result(other, &assignment), None)
+                .unwrap();
+        }
+        let leaderboard = validator.leaderboard(10);
+        assert_eq!(leaderboard.len(), 2);
+        assert_eq!((leaderboard[0].rank, leaderboard[0].client_id), (1, other));
+        assert_eq!(leaderboard[1].client_id, client_id);
+        assert_eq!(validator.leaderboard(1).len(), 1);
+    }
+
+    #[test]
+    fn test_frames_need_a_held_lease() {
+        let validator = Validator::default();
+        let client_id = Uuid::new_v4();
+        let assignment = assignment();
+        validator.issue(
+            assignment.work_id,
+            Lease::new(client_id, "default", &assignment),
+        );
+
+        let mut frame = SpectatorFrame {
+            work_id: assignment.work_id,
+            client_id,
+            experiment: None,
+            step: 10,
+            max_steps: assignment.max_steps,
+            world_width: 10,
+            world_height: 10,
+            lineages: assignment
+                .seed_genomes_v2
+                .iter()
+                .map(|g| g.genome_id)
+                .collect(),
+            creatures: Vec::new(),
+            population: 0,
+            food_cell: 1,
+            food: vec![0; 100],
+            terrain: Vec::new(),
+        };
+        assert_eq!(validator.check_frame(&frame).unwrap(), "default");
+        assert_eq!(validator.outstanding_leases(), 1);
+
+        frame.lineages.push(Uuid::new_v4());
+        assert!(validator.check_frame(&frame).is_err());
+        frame.lineages.pop();
+
+        frame.client_id = Uuid::new_v4();
+        assert!(validator
+            .check_frame(&frame)
+            .unwrap_err()
+            .contains("different client"));
+        frame.client_id = client_id;
+
+        validator
+            .validate(&result(client_id, &assignment), None)
+            .unwrap();
+        assert!(validator.check_frame(&frame).is_err());
+    }
+
+    #[test]
+    fn test_trust_follows_key_and_new_ids_start_low() {
+        let validator = Validator::default();
+        let submit = |client_id: Uuid, key_id: Option<&str>, honest: bool| {
+            let assignment = assignment();
+            validator.issue(
+                assignment.work_id,
+                Lease::new(client_id, "default", &assignment),
+            );
+            let mut r = result(client_id, &assignment);
+            if !honest {
+                r.survival_results[0].survived = 100;
+            }
+            validator.validate(&r, key_id).map(|(_, trust)| trust)
+        };
+
+        // A penalized client gains nothing by reporting under a fresh ID
+        let cheat = Uuid::new_v4();
+        assert!(submit(cheat, None, false).is_err());
+        assert!(submit(cheat, None, false).is_err());
+        let fresh = submit(Uuid::new_v4(), None, true).unwrap();
+        let clients = validator.clients();
+        assert_eq!(clients[0].client_id, cheat);
+        assert_eq!(clients[0].trust, MIN_TRUST);
+        assert!(fresh < MIN_TRUST + TRUST_GAIN);
+
+        // With a key, trust follows the key whatever client ID it reports
+        let trusted = submit(Uuid::new_v4(), Some("worker-1"), true).unwrap();
+        assert!(trusted > INITIAL_TRUST);
+        assert!(submit(Uuid::new_v4(), Some("worker-1"), false).is_err());
+        let after = submit(Uuid::new_v4(), Some("worker-1"), true).unwrap();
+        assert!(after < trusted);
+        let keyed: Vec<ClientTrust> = validator
+            .clients()
+            .into_iter()
+            .filter(|c| c.key_id.is_some())
+            .collect();
+        assert_eq!(keyed.len(), 1);
+        assert_eq!((keyed[0].accepted, keyed[0].rejected), (2, 1));
+    }
+
+    #[test]
+    fn test_full_client_table_keeps_active_clients() {
+        let mut inner = ValidatorInner::default();
+        let now = Instant::now();
+        let first = Uuid::new_v4();
+        inner.client(first, None, now).unwrap();
+        for _ in 1..MAX_TRACKED_CLIENTS {
+            inner.client(Uuid::new_v4(), None, now).unwrap();
+        }
+
+        // New IDs can't push out clients seen within a lease's lifetime
+        assert!(inner.client(Uuid::new_v4(), None, now).is_none());
+        assert!(inner.client(first, None, now).is_some());
+        assert_eq!(inner.clients.len(), MAX_TRACKED_CLIENTS);
+
+        // Once idle for longer than that, the least recently seen makes room
+        let later = now + LEASE_TTL;
+        inner.client(first, None, later).unwrap();
+        let newcomer = Uuid::new_v4();
+        assert!(inner.client(newcomer, None, later).is_some());
+        assert_eq!(inner.clients.len(), MAX_TRACKED_CLIENTS);
+        assert_eq!(inner.by_last_seen.len(), MAX_TRACKED_CLIENTS);
+        assert!(inner.clients.contains_key(&TrustKey::Client(first)));
+        assert!(inner.clients.contains_key(&TrustKey::Client(newcomer)));
+    }
+}
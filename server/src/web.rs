@@ -1,17 +1,258 @@
-use axum::{http::StatusCode, response::Html, Json};
+use crate::server::AppState;
+use axum::{
+    body::Bytes,
+    extract::Path,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
 use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::LazyLock;
 
 #[derive(Serialize)]
 pub struct HealthResponse {
     status: &'static str,
 }
 
-/// Serve the main web UI
-pub async fn index() -> Html<&'static str> {
-    Html(include_str!("../static/index.html"))
+/// A file from `static/`, compiled into the binary
+struct Embedded {
+    path: &'static str,
+    body: &'static [u8],
+}
+
+macro_rules! embed {
+    ($($path:literal),* $(,)?) => {
+        &[$(Embedded {
+            path: $path,
+            body: include_bytes!(concat!("../static/", $path)),
+        }),*]
+    };
+}
+
+/// Dashboard pages and the CSS and JS modules they load
+const EMBEDDED: &[Embedded] = embed![
+    "index.html",
+    "genomes.html",
+    "genome.html",
+    "lineages.html",
+    "clients.html",
+    "history.html",
+    "assets/css/app.css",
+    "assets/js/common.js",
+    "assets/js/dashboard.js",
+    "assets/js/genomes.js",
+    "assets/js/genome.js",
+    "assets/js/lineages.js",
+    "assets/js/clients.js",
+    "assets/js/history.js",
+];
+
+/// Replaced in pages with the versioned URL prefix of the assets
+const ASSETS_PLACEHOLDER: &str = "{{assets}}";
+
+/// Assets are versioned by content, so browsers may keep them indefinitely
+const ASSET_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Pages are revalidated on every load so they always point at current assets
+const PAGE_CACHE_CONTROL: &str = "no-cache";
+
+struct Asset {
+    content_type: &'static str,
+    body: Bytes,
+    etag: String,
+}
+
+impl Asset {
+    fn new(path: &str, body: Bytes) -> Self {
+        let digest = hex::encode(Sha256::digest(&body));
+        Self {
+            content_type: content_type(path),
+            etag: format!("\"{}\"", &digest[..16]),
+            body,
+        }
+    }
+
+    /// The asset, or 304 Not Modified if the client has this version cached
+    fn respond(&self, cache_control: &'static str, headers: &HeaderMap) -> Response {
+        let cached = headers
+            .get(header::IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|tags| {
+                tags.split(',')
+                    .map(|tag| tag.trim().trim_start_matches("W/"))
+                    .any(|tag| tag == self.etag || tag == "*")
+            });
+        let cache_headers = [
+            (header::ETAG, self.etag.clone()),
+            (header::CACHE_CONTROL, cache_control.to_string()),
+        ];
+        if cached {
+            return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+        }
+        (
+            cache_headers,
+            [(header::CONTENT_TYPE, self.content_type)],
+            self.body.clone(),
+        )
+            .into_response()
+    }
+}
+
+fn content_type(path: &str) -> &'static str {
+    match path.rsplit('.').next() {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Embedded files, with pages pointing at the current asset version
+struct AssetTable {
+    /// Hash of every asset, used in asset URLs so a new build is fetched fresh
+    version: String,
+    pages: HashMap<&'static str, Asset>,
+    assets: HashMap<&'static str, Asset>,
+}
+
+impl AssetTable {
+    fn build() -> Self {
+        let mut hasher = Sha256::new();
+        for file in EMBEDDED.iter().filter(|f| !f.path.ends_with(".html")) {
+            hasher.update(file.path.as_bytes());
+            hasher.update(file.body);
+        }
+        let version = hex::encode(hasher.finalize())[..12].to_string();
+        let prefix = format!("/assets/{}", version);
+
+        let mut pages = HashMap::new();
+        let mut assets = HashMap::new();
+        for file in EMBEDDED {
+            if let Some(path) = file.path.strip_prefix("assets/") {
+                assets.insert(path, Asset::new(path, Bytes::from_static(file.body)));
+            } else {
+                let html = String::from_utf8_lossy(file.body).replace(ASSETS_PLACEHOLDER, &prefix);
+                pages.insert(file.path, Asset::new(file.path, Bytes::from(html)));
+            }
+        }
+
+        Self {
+            version,
+            pages,
+            assets,
+        }
+    }
+}
+
+static ASSETS: LazyLock<AssetTable> = LazyLock::new(AssetTable::build);
+
+/// Dashboard pages, their assets and health checks
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(|headers| page("index.html", headers)))
+        .route("/genomes", get(|headers| page("genomes.html", headers)))
+        .route("/genomes/:id", get(|headers| page("genome.html", headers)))
+        .route("/lineages", get(|headers| page("lineages.html", headers)))
+        .route("/clients", get(|headers| page("clients.html", headers)))
+        .route("/history", get(|headers| page("history.html", headers)))
+        .route("/assets/:version/*path", get(asset))
+        .route("/health", get(health))
+        .route("/healthz", get(health))
+}
+
+/// Serve a dashboard page; the data comes from the JSON API
+async fn page(name: &'static str, headers: HeaderMap) -> Response {
+    match ASSETS.pages.get(name) {
+        Some(page) => page.respond(PAGE_CACHE_CONTROL, &headers),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Serve a CSS or JS module. Requests for another version, from a page loaded
+/// before a deploy, get the current file without long-term caching.
+async fn asset(Path((version, path)): Path<(String, String)>, headers: HeaderMap) -> Response {
+    let Some(asset) = ASSETS.assets.get(path.as_str()) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let cache_control = if version == ASSETS.version {
+        ASSET_CACHE_CONTROL
+    } else {
+        PAGE_CACHE_CONTROL
+    };
+    asset.respond(cache_control, &headers)
 }
 
 /// Health check endpoint for Kubernetes readiness/liveness probes
 pub async fn health() -> (StatusCode, Json<HealthResponse>) {
     (StatusCode::OK, Json(HealthResponse { status: "healthy" }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_pages_reference_embedded_assets() {
+        let prefix = format!("/assets/{}/", ASSETS.version);
+        for file in EMBEDDED.iter().filter(|f| f.path.ends_with(".html")) {
+            let raw = String::from_utf8_lossy(file.body);
+            for reference in raw.split(ASSETS_PLACEHOLDER).skip(1) {
+                let path = &reference[1..reference.find('"').unwrap()];
+                assert!(
+                    ASSETS.assets.contains_key(path),
+                    "{} loads missing asset {}",
+                    file.path,
+                    path
+                );
+            }
+
+            let page = String::from_utf8_lossy(&ASSETS.pages[file.path].body).into_owned();
+            assert!(!page.contains(ASSETS_PLACEHOLDER));
+            assert!(page.contains(&prefix));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_asset_caching() {
+        let path = "js/common.js".to_string();
+        let response = asset(
+            Path((ASSETS.version.clone(), path.clone())),
+            HeaderMap::new(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            ASSET_CACHE_CONTROL
+        );
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/javascript; charset=utf-8"
+        );
+        let etag = response.headers()[header::ETAG].clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag);
+        let response = asset(Path((ASSETS.version.clone(), path.clone())), headers).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = asset(Path(("stale".to_string(), path)), HeaderMap::new()).await;
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            PAGE_CACHE_CONTROL
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"other\""));
+        let response = page("genomes.html", headers).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            PAGE_CACHE_CONTROL
+        );
+    }
+}
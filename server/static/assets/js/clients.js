@@ -0,0 +1,30 @@
+// Client leaderboard: clients ranked by accepted island results
+import { fetchJson, formatNumber, formatUptime, setupExperimentPicker } from './common.js';
+
+async function loadClients() {
+    const rows = document.getElementById('client-rows');
+    try {
+        const clients = await fetchJson('/api/clients?limit=100');
+        if (clients.length === 0) {
+            rows.innerHTML = '<tr><td colspan="6" class="muted">No client has submitted results yet</td></tr>';
+            return;
+        }
+        rows.innerHTML = clients.map(c => `
+            <tr>
+                <td class="num">${c.rank === 1 ? '🥇' : c.rank === 2 ? '🥈' : c.rank === 3 ? '🥉' : c.rank}</td>
+                <td><code>${c.client_id}</code></td>
+                <td class="num">${formatNumber(c.accepted)}</td>
+                <td class="num">${formatNumber(c.rejected)}</td>
+                <td class="num">${c.trust.toFixed(2)}</td>
+                <td class="num">${c.idle_seconds < 60 ? 'just now' : `${formatUptime(c.idle_seconds)} ago`}</td>
+            </tr>
+        `).join('');
+    } catch (error) {
+        console.error('Failed to fetch clients:', error);
+    }
+}
+
+// Trust is tracked across experiments, so the picker only keeps the navigation scoped
+setupExperimentPicker(() => {});
+loadClients();
+setInterval(loadClients, 10000);
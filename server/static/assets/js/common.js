@@ -0,0 +1,253 @@
+// Helpers shared by every dashboard page
+
+const GENE_ICONS = {
+    strength: '💪',
+    speed: '⚡',
+    size: '📏',
+    efficiency: '⚙️',
+    reproduction: '🧬',
+    diet: '🍖',
+    mate_preference: '💞',
+};
+
+// The server's gene schema, so new genes show up without touching the pages
+async function loadGenes() {
+    try {
+        const response = await fetch('/api/genes');
+        if (!response.ok) throw new Error(`status ${response.status}`);
+        const genes = await response.json();
+        return genes.map(gene => ({
+            ...gene,
+            key: gene.name,
+            name: gene.name.charAt(0).toUpperCase() + gene.name.slice(1).replace(/_/g, ' '),
+            icon: GENE_ICONS[gene.name] || '🧬',
+        }));
+    } catch (error) {
+        console.error('Failed to fetch gene schema:', error);
+        return [];
+    }
+}
+
+export const GENES = await loadGenes();
+
+export function formatNumber(num) {
+    if (num >= 1000000) return (num / 1000000).toFixed(1) + 'M';
+    if (num >= 1000) return (num / 1000).toFixed(1) + 'K';
+    return num.toString();
+}
+
+export function formatUptime(seconds) {
+    const hours = Math.floor(seconds / 3600);
+    const minutes = Math.floor((seconds % 3600) / 60);
+    if (hours > 0) return `${hours}h ${minutes}m`;
+    return `${minutes}m`;
+}
+
+export function shortId(uuid) {
+    return uuid.slice(0, 8);
+}
+
+export function escapeHtml(text) {
+    return String(text).replace(/[&<>"']/g, c => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;', "'": '&#39;' })[c]);
+}
+
+// Experiment the page is showing (null = server default)
+let currentExperiment = new URLSearchParams(location.search).get('experiment');
+
+export function experiment() {
+    return currentExperiment;
+}
+
+// Add the current experiment to an API or page URL
+export function scoped(url) {
+    if (!currentExperiment) return url;
+    const sep = url.includes('?') ? '&' : '?';
+    return `${url}${sep}experiment=${encodeURIComponent(currentExperiment)}`;
+}
+
+export async function fetchJson(url) {
+    const response = await fetch(url);
+    if (!response.ok) throw new Error(`${url} failed with status ${response.status}`);
+    return response.json();
+}
+
+// Link to another page, keeping the experiment
+export function pageLink(path, text) {
+    return `<a href="${scoped(path)}">${escapeHtml(text)}</a>`;
+}
+
+export function genomeLink(genomeId) {
+    return pageLink(`/genomes/${genomeId}`, shortId(genomeId));
+}
+
+// Highlight the current page in the navigation and keep the experiment in its links
+function setupNav() {
+    for (const link of document.querySelectorAll('.nav a')) {
+        const path = link.getAttribute('href');
+        const current = path === '/' ? location.pathname === '/' : location.pathname.startsWith(path);
+        link.classList.toggle('active', current);
+        link.href = scoped(path);
+    }
+}
+
+// Show the experiment picker when the server runs several experiments.
+// `onChange` runs after the user switches to another experiment.
+export async function setupExperimentPicker(onChange) {
+    setupNav();
+    try {
+        const experiments = await fetchJson('/api/experiments');
+        if (experiments.length < 2) return;
+
+        const select = document.getElementById('experiment-select');
+        select.innerHTML = experiments
+            .map(e => `<option value="${escapeHtml(e.name)}">${escapeHtml(e.name)} (weight ${e.weight})</option>`)
+            .join('');
+        if (!currentExperiment) {
+            currentExperiment = experiments.find(e => e.default).name;
+        }
+        select.value = currentExperiment;
+        select.addEventListener('change', () => {
+            currentExperiment = select.value;
+            const url = new URL(location.href);
+            url.searchParams.set('experiment', currentExperiment);
+            history.replaceState(null, '', url);
+            setupNav();
+            onChange();
+        });
+        document.getElementById('experiment-picker').style.display = 'block';
+        setupNav();
+    } catch (error) {
+        console.error('Failed to fetch experiments:', error);
+    }
+}
+
+export function creatureSVG(genome) {
+    const size = 50 + genome.size * 50;
+    const hue = genome.strength * 360;
+    const lightness = 50 + genome.efficiency * 20;
+
+    return `
+        <svg class="creature-svg" viewBox="0 0 100 100" style="width: ${size}px; height: ${size}px;">
+            <!-- Body -->
+            <ellipse cx="50" cy="60" rx="${20 + genome.size * 15}" ry="${15 + genome.size * 10}"
+                     fill="hsl(${hue}, 70%, ${lightness}%)" opacity="0.9"/>
+
+            <!-- Head -->
+            <circle cx="50" cy="35" r="${10 + genome.size * 5}"
+                    fill="hsl(${hue}, 70%, ${lightness - 10}%)"/>
+
+            <!-- Eyes -->
+            <circle cx="45" cy="33" r="2" fill="white"/>
+            <circle cx="55" cy="33" r="2" fill="white"/>
+
+            <!-- Fangs for carnivores -->
+            ${genome.diet >= 0.5 ? `
+            <path d="M46 40 L47.5 45 L49 40 Z M51 40 L52.5 45 L54 40 Z" fill="white"/>` : ''}
+
+            <!-- Speed indicators (legs) -->
+            <line x1="40" y1="70" x2="${35 - genome.speed * 10}" y2="85"
+                  stroke="hsl(${hue}, 70%, ${lightness}%)" stroke-width="${2 + genome.speed * 2}"/>
+            <line x1="60" y1="70" x2="${65 + genome.speed * 10}" y2="85"
+                  stroke="hsl(${hue}, 70%, ${lightness}%)" stroke-width="${2 + genome.speed * 2}"/>
+
+            <!-- Strength indicator (arms) -->
+            <line x1="35" y1="55" x2="${25 - genome.strength * 8}" y2="60"
+                  stroke="hsl(${hue}, 70%, ${lightness - 20}%)" stroke-width="${3 + genome.strength * 3}"/>
+            <line x1="65" y1="55" x2="${75 + genome.strength * 8}" y2="60"
+                  stroke="hsl(${hue}, 70%, ${lightness - 20}%)" stroke-width="${3 + genome.strength * 3}"/>
+        </svg>
+    `;
+}
+
+// Position of a gene value within its range, from 0 to 1
+export function geneFraction(gene, value) {
+    return (value - gene.min) / (gene.max - gene.min);
+}
+
+export function geneBars(genome) {
+    return GENES.map(gene => `
+        <div class="gene-bar" title="${escapeHtml(gene.description)}">
+            <div class="gene-label">
+                <span>${gene.icon} ${gene.name}</span>
+                <span>${(geneFraction(gene, genome[gene.key]) * 100).toFixed(0)}%</span>
+            </div>
+            <div class="gene-progress">
+                <div class="gene-fill" style="width: ${geneFraction(gene, genome[gene.key]) * 100}%"></div>
+            </div>
+        </div>
+    `).join('');
+}
+
+export function describeEmpirical(empirical) {
+    if (!empirical) return 'no islands yet';
+    return `${empirical.mean.toFixed(3)} (95% CI ${empirical.ci_low.toFixed(2)}–${empirical.ci_high.toFixed(2)}, n=${empirical.samples})`;
+}
+
+// Line chart of one or more series of { t, v } points sharing a time axis
+export function drawLineChart(canvas, series, { label = '', minMax = 1.0 } = {}) {
+    const ctx = canvas.getContext('2d');
+    const w = canvas.width;
+    const h = canvas.height;
+    ctx.clearRect(0, 0, w, h);
+
+    const points = series.flatMap(s => s.points);
+    if (points.length === 0) return;
+
+    const margin = { left: 50, right: 10, top: 10, bottom: 28 };
+    const innerW = w - margin.left - margin.right;
+    const innerH = h - margin.top - margin.bottom;
+
+    const tMin = Math.min(...points.map(p => p.t));
+    const tMax = Math.max(...points.map(p => p.t));
+    const tSpan = Math.max(1, tMax - tMin);
+    // Give a headroom and a sensible default upper bound
+    const vMax = Math.max(minMax, Math.max(...points.map(p => p.v)) * 1.1);
+
+    // Axes
+    ctx.strokeStyle = 'rgba(255,255,255,0.4)';
+    ctx.lineWidth = 1;
+    ctx.beginPath();
+    ctx.moveTo(margin.left, h - margin.bottom);
+    ctx.lineTo(w - margin.right, h - margin.bottom);
+    ctx.moveTo(margin.left, margin.top);
+    ctx.lineTo(margin.left, h - margin.bottom);
+    ctx.stroke();
+
+    ctx.fillStyle = 'rgba(255,255,255,0.8)';
+    ctx.font = '12px Segoe UI, sans-serif';
+    const y0 = h - margin.bottom;
+    ctx.fillText('0', 10, y0);
+    ctx.fillText(vMax >= 100 ? formatNumber(Math.round(vMax)) : vMax.toFixed(2), 10, margin.top + 4);
+    ctx.fillText(formatUptime(tMin), margin.left, h - 8);
+    const end = formatUptime(tMax);
+    ctx.fillText(end, w - margin.right - ctx.measureText(end).width, h - 8);
+
+    const x = (p) => margin.left + ((p.t - tMin) / tSpan) * innerW;
+    const y = (p) => margin.top + (1 - Math.min(p.v / vMax, 1)) * innerH;
+
+    for (const s of series) {
+        if (s.points.length === 0) continue;
+        ctx.strokeStyle = s.color;
+        ctx.lineWidth = 2;
+        ctx.beginPath();
+        s.points.forEach((p, i) => (i === 0 ? ctx.moveTo(x(p), y(p)) : ctx.lineTo(x(p), y(p))));
+        ctx.stroke();
+        if (series.length === 1) {
+            // Area fill for a nicer look
+            ctx.lineTo(x(s.points[s.points.length - 1]), y0);
+            ctx.lineTo(x(s.points[0]), y0);
+            ctx.closePath();
+            ctx.fillStyle = s.fill || 'rgba(110, 231, 183, 0.15)';
+            ctx.fill();
+        }
+    }
+
+    // Latest value label of the first series
+    const first = series[0].points;
+    if (label && first.length > 0) {
+        const last = first[first.length - 1];
+        ctx.fillStyle = 'rgba(255,255,255,0.95)';
+        const text = `${label}: ${last.v >= 100 ? formatNumber(Math.round(last.v)) : last.v.toFixed(3)}`;
+        ctx.fillText(text, Math.min(x(last) + 8, w - margin.right - ctx.measureText(text).width), Math.max(y(last) - 8, margin.top + 12));
+    }
+}
@@ -0,0 +1,401 @@
+// Overview page: stats, top creatures, live events, live islands and the local preview
+import {
+    creatureSVG, drawLineChart, fetchJson, formatNumber, formatUptime, geneBars, genomeLink,
+    scoped, setupExperimentPicker, shortId, experiment,
+} from './common.js';
+
+function renderCreature(entry, rank) {
+    const { genome, fitness, empirical } = entry;
+    const medal = rank === 1 ? '🥇' : rank === 2 ? '🥈' : rank === 3 ? '🥉' : `#${rank}`;
+
+    return `
+        <div class="creature-card">
+            <div class="creature-rank">${medal}${entry.genome_id ? ` <small>${genomeLink(entry.genome_id)}</small>` : ''}</div>
+            <div class="creature-viz">
+                ${creatureSVG(genome)}
+            </div>
+            <div class="gene-bars">
+                ${geneBars(genome)}
+            </div>
+            <div class="fitness-score">
+                ${empirical
+                    ? `Survival score: ${empirical.mean.toFixed(3)}
+                       <small>(95% CI ${empirical.ci_low.toFixed(2)}–${empirical.ci_high.toFixed(2)}, n=${empirical.samples})</small><br>`
+                    : ''}
+                <small>Trait score: ${fitness.toFixed(3)}</small>
+            </div>
+        </div>
+    `;
+}
+
+async function updateStats() {
+    try {
+        const stats = await fetchJson(scoped('/api/stats'));
+
+        document.getElementById('active-clients').textContent = stats.active_clients;
+        document.getElementById('work-units').textContent = formatNumber(stats.total_work_units);
+        document.getElementById('total-generations').textContent = formatNumber(stats.total_generations);
+        document.getElementById('gene-pool-size').textContent = formatNumber(stats.gene_pool_size);
+        if (typeof stats.unique_genomes === 'number') {
+            document.getElementById('unique-genomes').textContent = formatNumber(stats.unique_genomes);
+        }
+        document.getElementById('uptime').textContent = formatUptime(stats.uptime_seconds);
+
+        const creaturesGrid = document.getElementById('creatures-grid');
+        if (stats.best_genomes && stats.best_genomes.length > 0) {
+            creaturesGrid.innerHTML = stats.best_genomes
+                .slice(0, 6)
+                .map((g, i) => renderCreature(g, i + 1))
+                .join('');
+        }
+    } catch (error) {
+        console.error('Failed to fetch stats:', error);
+    }
+}
+
+// Fitness trend chart, backed by the server-side history since startup
+const fitnessCanvas = document.getElementById('fitness-canvas');
+const MAX_CHART_POINTS = 500;
+
+async function updateHistory() {
+    try {
+        const history = await fetchJson(scoped(`/api/history?max_points=${MAX_CHART_POINTS}`));
+        const points = history.samples.map(s => ({ t: s.uptime_seconds, v: s.mean_fitness }));
+        drawLineChart(fitnessCanvas, [{ points, color: '#6ee7b7' }], { label: 'mean fitness' });
+    } catch (error) {
+        console.error('Failed to fetch history:', error);
+    }
+}
+
+// Live event feed: refresh stats when something changes instead of polling
+const FEED_TYPES = ['work_completed', 'work_rejected', 'new_best', 'extinction', 'client_joined', 'client_left'];
+const MAX_FEED_ITEMS = 30;
+let eventSource = null;
+let statsQueued = false;
+let pollHandle = null;
+
+function queueStatsUpdate() {
+    if (statsQueued) return;
+    statsQueued = true;
+    setTimeout(() => { statsQueued = false; updateStats(); }, 1000);
+}
+
+function describeEvent(type, e) {
+    const id = shortId;
+    switch (type) {
+        case 'work_completed': return `✅ Client ${id(e.client_id)} finished an island: ${e.survivors} survivors from ${e.genomes} genomes`;
+        case 'work_rejected': return `⛔ Result from client ${id(e.client_id)} rejected: ${e.reason}`;
+        case 'new_best': return `🏆 New leader ${id(e.genome_id)}: survival score ${(e.empirical.mean * 100).toFixed(1)}% over ${e.empirical.samples} islands`;
+        case 'extinction': return `💀 Genome ${id(e.genome_id)} went extinct`;
+        case 'client_joined': return `👋 Client ${id(e.client_id)} joined`;
+        case 'client_left': return `🚪 Client ${id(e.client_id)} left`;
+        default: return type;
+    }
+}
+
+function addToFeed(type, e) {
+    const feed = document.getElementById('event-feed');
+    feed.querySelector('.loading')?.remove();
+    const item = document.createElement('li');
+    item.style.padding = '3px 0';
+    item.textContent = `${new Date(e.timestamp_ms).toLocaleTimeString()}  ${describeEvent(type, e)}`;
+    feed.prepend(item);
+    while (feed.children.length > MAX_FEED_ITEMS) feed.lastChild.remove();
+}
+
+function connectEvents() {
+    if (eventSource) eventSource.close();
+    if (!window.EventSource) {
+        pollHandle = pollHandle || setInterval(updateStats, 2000);
+        return;
+    }
+    eventSource = new EventSource(scoped(`/api/events?types=${FEED_TYPES.join(',')}`));
+    eventSource.onopen = () => {
+        // Streaming works, no need to poll
+        if (pollHandle) { clearInterval(pollHandle); pollHandle = null; }
+    };
+    eventSource.onerror = () => {
+        // EventSource reconnects by itself; poll until it does
+        pollHandle = pollHandle || setInterval(updateStats, 2000);
+    };
+    for (const type of FEED_TYPES) {
+        eventSource.addEventListener(type, (msg) => {
+            addToFeed(type, JSON.parse(msg.data));
+            queueStatsUpdate();
+        });
+    }
+}
+
+// Stats follow the event feed, history is sampled every 10 seconds
+setupExperimentPicker(() => {
+    updateStats();
+    updateHistory();
+    connectEvents();
+    updateSpectateList();
+});
+updateStats();
+updateHistory();
+connectEvents();
+setInterval(updateHistory, 10000);
+
+// -------------------
+// Local Live Simulation
+// -------------------
+const canvas = document.getElementById('sim-canvas');
+const ctx = canvas.getContext('2d');
+
+const btnStart = document.getElementById('btn-start');
+const btnPlay = document.getElementById('btn-play');
+const btnStep = document.getElementById('btn-step');
+const btnReset = document.getElementById('btn-reset');
+const speedRange = document.getElementById('speed-range');
+const toggleFood = document.getElementById('toggle-food');
+const stepLabel = document.getElementById('sim-step-label');
+const stepProgress = document.getElementById('sim-step-progress');
+const creaturesLabel = document.getElementById('sim-creatures-label');
+const creaturesProgress = document.getElementById('sim-creatures-progress');
+
+// Islands streamed by clients, relayed by the server
+const spectateCanvas = document.getElementById('spectate-canvas');
+const spectateCtx = spectateCanvas.getContext('2d');
+const spectateList = document.getElementById('spectate-list');
+let spectating = null; // { workId, source }
+
+function lineageColor(index) {
+    // Golden angle keeps neighbouring lineages far apart on the hue wheel
+    return `hsl(${(index * 137.508) % 360},75%,60%)`;
+}
+
+// Grassland, fertile soil, water and mountain, in the order of the server's `Terrain::ALL`
+const TERRAIN_COLORS = ['rgb(126, 200, 80)', 'rgb(34, 110, 48)', 'rgb(40, 90, 200)', 'rgb(150, 140, 130)'];
+
+function renderFrame(frame) {
+    const sx = spectateCanvas.width / frame.world_width;
+    const sy = spectateCanvas.height / frame.world_height;
+    spectateCtx.clearRect(0, 0, spectateCanvas.width, spectateCanvas.height);
+    const blocksX = Math.ceil(frame.world_width / frame.food_cell);
+    // Terrain, faint so creatures and food stand out (absent on flat worlds)
+    spectateCtx.globalAlpha = 0.35;
+    (frame.terrain || []).forEach((terrain, i) => {
+        spectateCtx.fillStyle = TERRAIN_COLORS[terrain] || TERRAIN_COLORS[0];
+        spectateCtx.fillRect(
+            Math.floor((i % blocksX) * frame.food_cell * sx),
+            Math.floor(Math.floor(i / blocksX) * frame.food_cell * sy),
+            Math.ceil(frame.food_cell * sx),
+            Math.ceil(frame.food_cell * sy));
+    });
+    spectateCtx.globalAlpha = 1;
+    const cap = 20 * frame.food_cell * frame.food_cell;
+    for (let i = 0; i < frame.food.length; i++) {
+        if (frame.food[i] <= 0) continue;
+        const alpha = Math.min(0.5, (frame.food[i] / cap) * 0.5);
+        spectateCtx.fillStyle = `rgba(56, 189, 248, ${alpha})`;
+        spectateCtx.fillRect(
+            Math.floor((i % blocksX) * frame.food_cell * sx),
+            Math.floor(Math.floor(i / blocksX) * frame.food_cell * sy),
+            Math.ceil(frame.food_cell * sx),
+            Math.ceil(frame.food_cell * sy));
+    }
+    // Creatures: [x, y, lineage] each
+    const c = frame.creatures;
+    for (let i = 0; i < c.length; i += 3) {
+        spectateCtx.fillStyle = lineageColor(c[i + 2]);
+        spectateCtx.beginPath();
+        spectateCtx.arc((c[i] + 0.5) * sx, (c[i + 1] + 0.5) * sy, 3, 0, Math.PI * 2);
+        spectateCtx.fill();
+    }
+    document.getElementById('spectate-step-label').textContent = `${frame.step} / ${frame.max_steps}`;
+    document.getElementById('spectate-step-progress').style.width = `${(frame.step / frame.max_steps) * 100}%`;
+    document.getElementById('spectate-creatures-label').textContent = formatNumber(frame.population);
+}
+
+function watchIsland(workId) {
+    if (spectating) spectating.source.close();
+    const source = new EventSource(`/api/spectate/${workId}/stream`);
+    source.addEventListener('frame', (msg) => renderFrame(JSON.parse(msg.data)));
+    source.addEventListener('finished', () => {
+        source.close();
+        document.getElementById('spectate-step-label').textContent = 'Finished';
+        spectating = null;
+        updateSpectateList();
+    });
+    spectating = { workId, source };
+    updateSpectateList();
+}
+
+async function updateSpectateList() {
+    try {
+        const resp = await fetch(scoped('/api/spectate'));
+        if (!resp.ok) return;
+        const islands = await resp.json();
+        spectateList.innerHTML = '';
+        if (islands.length === 0) {
+            spectateList.innerHTML = '<li class="loading">No islands are being streamed</li>';
+            return;
+        }
+        for (const island of islands) {
+            const li = document.createElement('li');
+            const watching = spectating && spectating.workId === island.work_id;
+            li.textContent = `${watching ? '▶ ' : ''}${shortId(island.client_id)} · step ${island.step}/${island.max_steps} · ${formatNumber(island.population)} alive`;
+            li.style.cssText = `padding:4px 0; cursor:pointer; ${watching ? 'font-weight:600;' : ''}`;
+            li.addEventListener('click', () => watchIsland(island.work_id));
+            spectateList.appendChild(li);
+        }
+    } catch (e) {
+        console.error('Failed to list live islands', e);
+    }
+}
+updateSpectateList();
+setInterval(updateSpectateList, 5000);
+
+// Helper: UUID v4
+function uuidv4() {
+    // Simple RFC4122-ish generator
+    return 'xxxxxxxx-xxxx-4xxx-yxxx-xxxxxxxxxxxx'.replace(/[xy]/g, function(c) {
+        const r = (Math.random() * 16) | 0;
+        const v = c === 'x' ? r : (r & 0x3) | 0x8;
+        return v.toString(16);
+    });
+}
+
+// The simulation itself is the Rust `sim` crate compiled to WebAssembly
+let wasmModule = null;
+async function loadWasm() {
+    if (!wasmModule) {
+        const module = await import('/wasm/sim_wasm.js');
+        await module.default();
+        wasmModule = module;
+    }
+    return wasmModule;
+}
+
+const Sim = {
+    island: null,
+    playing: false,
+};
+
+function renderSim() {
+    const island = Sim.island;
+    if (!island) return;
+    const width = island.width();
+    const height = island.height();
+    ctx.clearRect(0, 0, canvas.width, canvas.height);
+    // Scale world to canvas
+    const sx = canvas.width / width;
+    const sy = canvas.height / height;
+    // Optional: draw food density heatmap
+    if (toggleFood.checked) {
+        const food = island.food();
+        // Downsample drawing for performance
+        const targetCells = 150; // approx cells across
+        const step = Math.max(1, Math.floor(Math.max(width, height) / targetCells));
+        const drawW = Math.max(1, Math.floor(sx * step));
+        const drawH = Math.max(1, Math.floor(sy * step));
+        // Determine a normalization cap (plants up to 10, food up to ~15)
+        const cap = 20;
+        for (let y = 0; y < height; y += step) {
+            for (let x = 0; x < width; x += step) {
+                const val = food[y * width + x];
+                if (val <= 0) continue;
+                const alpha = Math.min(0.5, (val / cap) * 0.5); // up to 0.5 alpha
+                ctx.fillStyle = `rgba(56, 189, 248, ${alpha})`; // cyan-ish overlay
+                ctx.fillRect(Math.floor(x * sx), Math.floor(y * sy), drawW, drawH);
+            }
+        }
+    }
+    // Draw creatures: [x, y, lineage, strength, size, efficiency] each
+    const creatures = island.creatures();
+    for (let i = 0; i < creatures.length; i += 6) {
+        const hue = creatures[i + 3] * 360;
+        const light = 50 + creatures[i + 5] * 20;
+        const r = 2 + creatures[i + 4] * 3; // small dot
+        ctx.fillStyle = `hsl(${hue},70%,${light}%)`;
+        ctx.beginPath();
+        ctx.arc((creatures[i] + 0.5) * sx, (creatures[i + 1] + 0.5) * sy, r, 0, Math.PI * 2);
+        ctx.fill();
+    }
+    // HUD
+    const count = island.creatureCount();
+    stepLabel.textContent = `${island.step()} / ${island.maxSteps()}`;
+    stepProgress.style.width = `${(island.step() / island.maxSteps()) * 100}%`;
+    creaturesLabel.textContent = `${count}`;
+    creaturesProgress.style.width = `${Math.min(100, (count / 500) * 100)}%`;
+}
+
+function advance(steps) {
+    if (!Sim.island) return;
+    const finished = Sim.island.tick(steps);
+    renderSim();
+    if (finished) setPlaying(false);
+}
+
+let playHandle = null;
+function setPlaying(p) {
+    Sim.playing = p;
+    btnPlay.textContent = p ? 'Pause' : 'Play';
+    if (playHandle) { clearInterval(playHandle); playHandle = null; }
+    if (p) {
+        playHandle = setInterval(() => advance(parseInt(speedRange.value, 10)), 50);
+    }
+}
+
+async function startSimulation() {
+    try {
+        btnStart.disabled = true;
+        btnStart.textContent = 'Loading...';
+        const wasm = await loadWasm();
+        // Request assignment like a client
+        const req = {
+            client_id: uuidv4(),
+            protocol_version: 2,
+            client_version: 'web-preview',
+        };
+        if (experiment()) req.experiment = experiment();
+        const resp = await fetch('/api/work/request', { method: 'POST', headers: { 'Content-Type': 'application/json' }, body: JSON.stringify(req) });
+        if (!resp.ok) throw new Error(`work request failed with status ${resp.status}`);
+
+        Sim.island = new wasm.WasmIsland(await resp.text());
+
+        // Enable controls
+        btnPlay.disabled = false;
+        btnStep.disabled = false;
+        btnReset.disabled = false;
+        btnStart.disabled = false;
+        btnStart.textContent = 'Restart';
+        renderSim();
+    } catch (e) {
+        console.error('Failed to start simulation', e);
+        btnStart.disabled = false;
+        btnStart.textContent = 'Start Simulation';
+        stepLabel.textContent = 'Simulation unavailable';
+    }
+}
+
+function resetSimulation() {
+    setPlaying(false);
+    if (Sim.island) { Sim.island.free(); Sim.island = null; }
+    ctx.clearRect(0, 0, canvas.width, canvas.height);
+    stepLabel.textContent = '0 / 0';
+    stepProgress.style.width = '0%';
+    creaturesLabel.textContent = '-';
+    creaturesProgress.style.width = '0%';
+    btnPlay.disabled = true;
+    btnStep.disabled = true;
+    btnReset.disabled = true;
+    btnStart.disabled = false;
+    btnStart.textContent = 'Start Simulation';
+}
+
+btnStart.addEventListener('click', async () => {
+    if (Sim.island) {
+        resetSimulation();
+        // immediate restart
+        setTimeout(startSimulation, 10);
+    } else {
+        await startSimulation();
+    }
+});
+btnPlay.addEventListener('click', () => setPlaying(!Sim.playing));
+btnStep.addEventListener('click', () => advance(1));
+btnReset.addEventListener('click', resetSimulation);
+speedRange.addEventListener('input', () => { if (Sim.playing) setPlaying(true); });
@@ -0,0 +1,74 @@
+// Genome detail: traits, scores, parents, population over time and recent island results
+import {
+    creatureSVG, describeEmpirical, drawLineChart, escapeHtml, fetchJson, formatNumber, formatUptime,
+    geneBars, genomeLink, pageLink, scoped, setupExperimentPicker, shortId,
+} from './common.js';
+
+const genomeId = location.pathname.split('/').filter(Boolean).pop();
+
+function fact(label, value) {
+    return `<tr><th>${label}</th><td>${value}</td></tr>`;
+}
+
+// Mate preference below 0.5 favours similar partners, above 0.5 strong ones
+function describeMatePreference(preference) {
+    if (preference === undefined || Math.abs(preference - 0.5) < 0.1) {
+        return 'any partner';
+    }
+    return preference < 0.5 ? '🪞 similar partners' : '💪 strong partners';
+}
+
+async function loadGenome() {
+    const facts = document.getElementById('genome-facts');
+    try {
+        const detail = await fetchJson(scoped(`/api/genomes/${encodeURIComponent(genomeId)}`));
+        document.title = `Genome ${shortId(detail.genome_id)} - EvoIslands`;
+        document.getElementById('genome-title').textContent = `🧬 Genome ${shortId(detail.genome_id)}`;
+        document.getElementById('genome-viz').innerHTML = creatureSVG(detail.genome);
+        document.getElementById('genome-genes').innerHTML = geneBars(detail.genome);
+
+        const parents = detail.parent_ids.length > 0
+            ? detail.parent_ids.map(genomeLink).join(', ')
+            : '<span class="muted">none</span>';
+        facts.innerHTML = [
+            fact('ID', `<code>${escapeHtml(detail.genome_id)}</code>`),
+            fact('Population', formatNumber(detail.population) + (detail.archived ? ' <span class="muted">(archived)</span>' : '')),
+            fact('Survival score', describeEmpirical(detail.empirical)),
+            fact('Trait score', detail.fitness.toFixed(3)),
+            fact('Origin', detail.origin.replace('_', ' ')),
+            fact('Diet', detail.genome.diet >= 0.5 ? '🍖 carnivore' : '🌿 plant eater'),
+            fact('Mate choice', describeMatePreference(detail.genome.mate_preference)),
+            fact('Behaviour', detail.genome.brain
+                ? `🧠 neural network (${detail.genome.brain.length} weights)`
+                : 'fixed foraging rule'),
+            fact('Created', `after ${formatUptime(detail.created_seconds)} of uptime`),
+            fact('Parents', parents),
+            fact('Lineage', `${genomeLink(detail.lineage_root)} · ${pageLink(`/lineages?genome=${detail.genome_id}`, 'family tree')}`),
+        ].join('');
+
+        const points = detail.population_history.map(p => ({ t: p.uptime_seconds, v: p.population }));
+        drawLineChart(document.getElementById('population-canvas'),
+            [{ points, color: '#6ee7b7' }], { label: 'population', minMax: 10 });
+
+        const rows = document.getElementById('submission-rows');
+        rows.innerHTML = detail.submissions.length === 0
+            ? '<tr><td colspan="7" class="muted">No island results yet</td></tr>'
+            : detail.submissions.slice().reverse().map(s => `
+                <tr>
+                    <td>${new Date(s.timestamp_ms).toLocaleString()}</td>
+                    <td><code>${shortId(s.client_id)}</code></td>
+                    <td class="num">${s.survived}</td>
+                    <td class="num">${s.total_spawned}</td>
+                    <td class="num">${s.total_food_eaten}</td>
+                    <td class="num">${s.old_population} → ${s.new_population}</td>
+                    <td class="num">${s.trust.toFixed(2)}</td>
+                </tr>
+            `).join('');
+    } catch (error) {
+        console.error('Failed to fetch genome:', error);
+        facts.innerHTML = '<tr><td class="muted">Genome not found in this experiment</td></tr>';
+    }
+}
+
+setupExperimentPicker(loadGenome);
+loadGenome();
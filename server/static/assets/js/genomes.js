@@ -0,0 +1,76 @@
+// Gene pool explorer: every genome in the pool, sorted and filtered, a page at a time
+import {
+    GENES, describeEmpirical, escapeHtml, fetchJson, formatNumber, geneFraction, genomeLink, pageLink,
+    scoped, setupExperimentPicker, shortId,
+} from './common.js';
+
+const PER_PAGE = 50;
+const params = new URLSearchParams(location.search);
+const state = {
+    page: parseInt(params.get('page') || '0', 10),
+    sort: params.get('sort') || 'population',
+    status: params.get('status') || 'all',
+};
+
+const sortSelect = document.getElementById('sort-select');
+const statusSelect = document.getElementById('status-select');
+const prevButton = document.getElementById('prev-page');
+const nextButton = document.getElementById('next-page');
+sortSelect.value = state.sort;
+statusSelect.value = state.status;
+
+// Traits as a compact row of bars
+function traitSparkline(genome) {
+    return GENES.map(gene => `<span title="${gene.name} ${genome[gene.key].toFixed(2)}"
+        style="display:inline-block; width:8px; height:${4 + geneFraction(gene, genome[gene.key]) * 16}px; margin-right:2px;
+               vertical-align:bottom; background:#4facfe; border-radius:2px;"></span>`).join('');
+}
+
+function saveState() {
+    const url = new URL(location.href);
+    url.searchParams.set('page', state.page);
+    url.searchParams.set('sort', state.sort);
+    url.searchParams.set('status', state.status);
+    history.replaceState(null, '', url);
+}
+
+async function loadGenomes() {
+    saveState();
+    const rows = document.getElementById('genome-rows');
+    try {
+        const list = await fetchJson(scoped(
+            `/api/genomes?page=${state.page}&per_page=${PER_PAGE}&sort=${state.sort}&status=${state.status}`));
+        const pages = Math.max(1, Math.ceil(list.total / list.per_page));
+        document.getElementById('genome-total').textContent = `${formatNumber(list.total)} genomes`;
+        document.getElementById('page-label').textContent = `Page ${list.page + 1} of ${pages}`;
+        prevButton.disabled = list.page === 0;
+        nextButton.disabled = list.page + 1 >= pages;
+
+        if (list.genomes.length === 0) {
+            rows.innerHTML = '<tr><td colspan="7" class="muted">No genomes match</td></tr>';
+            return;
+        }
+        rows.innerHTML = list.genomes.map(g => `
+            <tr>
+                <td>${genomeLink(g.genome_id)}</td>
+                <td class="num">${formatNumber(g.population)}</td>
+                <td class="num">${g.empirical ? g.empirical.mean.toFixed(3) : '–'}</td>
+                <td class="num">${g.fitness.toFixed(3)}</td>
+                <td title="${escapeHtml(describeEmpirical(g.empirical))}">${traitSparkline(g.genome)}</td>
+                <td>${g.origin.replace('_', ' ')}</td>
+                <td>${pageLink(`/lineages?root=${g.lineage_root}`, shortId(g.lineage_root))}</td>
+            </tr>
+        `).join('');
+    } catch (error) {
+        console.error('Failed to fetch genomes:', error);
+        rows.innerHTML = '<tr><td colspan="7" class="muted">Failed to load genomes</td></tr>';
+    }
+}
+
+sortSelect.addEventListener('change', () => { state.sort = sortSelect.value; state.page = 0; loadGenomes(); });
+statusSelect.addEventListener('change', () => { state.status = statusSelect.value; state.page = 0; loadGenomes(); });
+prevButton.addEventListener('click', () => { state.page = Math.max(0, state.page - 1); loadGenomes(); });
+nextButton.addEventListener('click', () => { state.page += 1; loadGenomes(); });
+
+setupExperimentPicker(() => { state.page = 0; loadGenomes(); });
+loadGenomes();
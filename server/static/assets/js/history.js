@@ -0,0 +1,68 @@
+// Experiment history: the experiments on this server and how the selected one evolved over time
+import {
+    GENES, drawLineChart, escapeHtml, experiment, fetchJson, formatNumber, setupExperimentPicker,
+} from './common.js';
+
+const MAX_CHART_POINTS = 500;
+const TRAIT_COLORS = ['#f87171', '#fbbf24', '#6ee7b7', '#60a5fa', '#c084fc', '#f472b6', '#a3e635', '#22d3ee'];
+
+document.getElementById('trait-legend').innerHTML = GENES
+    .map((gene, i) => `<span style="color:${TRAIT_COLORS[i % TRAIT_COLORS.length]};">■ ${gene.name}</span>`)
+    .join('');
+
+async function loadExperiments() {
+    try {
+        const experiments = await fetchJson('/api/experiments');
+        document.getElementById('experiment-rows').innerHTML = experiments.map(e => {
+            const selected = experiment() ? e.name === experiment() : e.default;
+            return `
+                <tr style="${selected ? 'font-weight:600;' : ''}">
+                    <td><a href="/history?experiment=${encodeURIComponent(e.name)}">${escapeHtml(e.name)}</a>${e.default ? ' <span class="muted">(default)</span>' : ''}</td>
+                    <td class="num">${e.weight}</td>
+                    <td>${e.paused ? '⏸ paused' : '▶ running'}</td>
+                    <td class="num">${e.island.world_width}×${e.island.world_height}</td>
+                    <td>${e.island.mutation.type} / ${e.island.crossover.type.replace('_', ' ')}</td>
+                    <td class="num">${formatNumber(e.gene_pool_size)}</td>
+                    <td class="num">${e.active_clients}</td>
+                    <td class="num">${formatNumber(e.total_work_units)}</td>
+                </tr>
+            `;
+        }).join('');
+    } catch (error) {
+        console.error('Failed to fetch experiments:', error);
+    }
+}
+
+async function loadHistory() {
+    try {
+        const url = `/api/history?max_points=${MAX_CHART_POINTS}`
+            + (experiment() ? `&experiment=${encodeURIComponent(experiment())}` : '');
+        const { samples } = await fetchJson(url);
+        const series = (key) => samples.map(s => ({ t: s.uptime_seconds, v: key(s) }));
+
+        drawLineChart(document.getElementById('fitness-canvas'),
+            [{ points: series(s => s.mean_fitness), color: '#6ee7b7' }], { label: 'mean fitness' });
+        drawLineChart(document.getElementById('population-canvas'),
+            [{ points: series(s => s.total_population), color: '#60a5fa', fill: 'rgba(96, 165, 250, 0.15)' }],
+            { label: 'population', minMax: 10 });
+        drawLineChart(document.getElementById('traits-canvas'),
+            GENES.map((gene, i) => ({
+                points: series(s => s.mean_traits[gene.key]),
+                color: TRAIT_COLORS[i % TRAIT_COLORS.length],
+            })));
+        drawLineChart(document.getElementById('diversity-canvas'),
+            [{ points: series(s => s.diversity), color: '#fbbf24', fill: 'rgba(251, 191, 36, 0.15)' }],
+            { label: 'diversity', minMax: 0.1 });
+        drawLineChart(document.getElementById('genomes-canvas'), [
+            { points: series(s => s.living), color: '#6ee7b7' },
+            { points: series(s => s.extinct), color: '#f87171' },
+        ], { label: 'living', minMax: 10 });
+    } catch (error) {
+        console.error('Failed to fetch history:', error);
+    }
+}
+
+setupExperimentPicker(() => { loadExperiments(); loadHistory(); });
+loadExperiments();
+loadHistory();
+setInterval(loadHistory, 10000);
@@ -0,0 +1,102 @@
+// Lineage tree: lineages ranked by population share, and the family tree of one lineage or genome
+import {
+    escapeHtml, fetchJson, formatNumber, formatUptime, genomeLink, scoped, setupExperimentPicker,
+    shortId,
+} from './common.js';
+
+const params = new URLSearchParams(location.search);
+// Either a lineage root, whose descendants are shown, or a genome, shown with its ancestors too
+let selected = params.get('genome')
+    ? { genome: params.get('genome') }
+    : params.get('root') ? { root: params.get('root') } : null;
+
+const includeExtinct = document.getElementById('include-extinct');
+const depthSelect = document.getElementById('depth-select');
+
+function renderNode(node) {
+    const extinct = node.population === 0 ? ' muted' : '';
+    const children = node.related.length > 0
+        ? `<ul>${node.related.map(renderNode).join('')}</ul>`
+        : '';
+    return `
+        <li>
+            <span class="${extinct}">${genomeLink(node.genome_id)}
+                · pop ${formatNumber(node.population)}
+                · ${node.origin.replace('_', ' ')}
+                · ${formatUptime(node.created_seconds)}</span>
+            ${children}
+        </li>
+    `;
+}
+
+async function renderTree(elementId, title, url) {
+    const element = document.getElementById(elementId);
+    try {
+        const tree = await fetchJson(scoped(url));
+        element.innerHTML = `<h3 style="margin:12px 0 4px 0;">${title}</h3><ul class="tree">${renderNode(tree)}</ul>`;
+    } catch (error) {
+        console.error('Failed to fetch tree:', error);
+        element.innerHTML = `<p class="muted">${title}: not found in this experiment</p>`;
+    }
+}
+
+async function loadTree() {
+    const ancestry = document.getElementById('ancestry');
+    const descendants = document.getElementById('descendants');
+    ancestry.innerHTML = '';
+    descendants.innerHTML = '';
+    if (!selected) return;
+
+    const depth = depthSelect.value;
+    const genomeId = selected.genome || selected.root;
+    document.getElementById('tree-title').innerHTML = selected.genome
+        ? `🌳 Family of ${genomeLink(genomeId)}`
+        : `🌳 Lineage ${genomeLink(genomeId)}`;
+    if (selected.genome) {
+        await renderTree('ancestry', 'Ancestors', `/api/genomes/${encodeURIComponent(genomeId)}/ancestry?depth=${depth}`);
+    }
+    await renderTree('descendants', 'Descendants', `/api/genomes/${encodeURIComponent(genomeId)}/descendants?depth=${depth}`);
+}
+
+function select(root) {
+    selected = { root };
+    const url = new URL(location.href);
+    url.searchParams.delete('genome');
+    url.searchParams.set('root', root);
+    history.replaceState(null, '', url);
+    loadLineages();
+    loadTree();
+}
+
+async function loadLineages() {
+    const rows = document.getElementById('lineage-rows');
+    try {
+        const lineages = await fetchJson(scoped(`/api/lineages?limit=50&include_extinct=${includeExtinct.checked}`));
+        if (lineages.length === 0) {
+            rows.innerHTML = '<tr><td colspan="5" class="muted">No lineages</td></tr>';
+            return;
+        }
+        rows.innerHTML = lineages.map(l => `
+            <tr data-root="${escapeHtml(l.lineage_root)}" style="cursor:pointer; ${selected && selected.root === l.lineage_root ? 'font-weight:600;' : ''}">
+                <td><code>${shortId(l.lineage_root)}</code></td>
+                <td class="num">${formatNumber(l.genomes)}</td>
+                <td class="num">${formatNumber(l.living_genomes)}</td>
+                <td class="num">${formatNumber(l.total_population)}</td>
+                <td class="num">${(l.population_share * 100).toFixed(1)}%</td>
+            </tr>
+        `).join('');
+        for (const row of rows.querySelectorAll('tr[data-root]')) {
+            row.addEventListener('click', () => select(row.dataset.root));
+        }
+        // Show the biggest lineage until one is picked
+        if (!selected) select(lineages[0].lineage_root);
+    } catch (error) {
+        console.error('Failed to fetch lineages:', error);
+    }
+}
+
+includeExtinct.addEventListener('change', loadLineages);
+depthSelect.addEventListener('change', loadTree);
+setupExperimentPicker(() => { selected = null; loadLineages(); loadTree(); });
+loadLineages();
+loadTree();
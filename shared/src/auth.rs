@@ -0,0 +1,102 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header naming the API key a request is signed with
+pub const KEY_ID_HEADER: &str = "x-evo-key-id";
+
+/// Header with the Unix time (seconds) at which the request was signed
+pub const TIMESTAMP_HEADER: &str = "x-evo-timestamp";
+
+/// Header with the hex-encoded HMAC-SHA256 request signature
+pub const SIGNATURE_HEADER: &str = "x-evo-signature";
+
+/// Signed requests older or newer than this are rejected to limit replays
+pub const MAX_CLOCK_SKEW_SECS: u64 = 300;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn mac(secret: &str, timestamp: u64, method: &str, path: &str, body: &[u8]) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}\n{}\n{}\n", timestamp, method.to_uppercase(), path).as_bytes());
+    mac.update(body);
+    mac
+}
+
+/// Sign a request: HMAC-SHA256 over the timestamp, method, path and body, hex encoded
+pub fn sign_request(secret: &str, timestamp: u64, method: &str, path: &str, body: &[u8]) -> String {
+    hex::encode(
+        mac(secret, timestamp, method, path, body)
+            .finalize()
+            .into_bytes(),
+    )
+}
+
+/// Check a hex-encoded request signature in constant time
+pub fn verify_request(
+    secret: &str,
+    timestamp: u64,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    signature: &str,
+) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    mac(secret, timestamp, method, path, body)
+        .verify_slice(&signature)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let body = br#"{"client_id":"abc"}"#;
+        let signature = sign_request("secret", 1700000000, "post", "/api/work/submit", body);
+
+        assert!(verify_request(
+            "secret",
+            1700000000,
+            "POST",
+            "/api/work/submit",
+            body,
+            &signature
+        ));
+        assert!(!verify_request(
+            "other",
+            1700000000,
+            "POST",
+            "/api/work/submit",
+            body,
+            &signature
+        ));
+        assert!(!verify_request(
+            "secret",
+            1700000001,
+            "POST",
+            "/api/work/submit",
+            body,
+            &signature
+        ));
+        assert!(!verify_request(
+            "secret",
+            1700000000,
+            "POST",
+            "/api/work/submit",
+            b"{}",
+            &signature
+        ));
+        assert!(!verify_request(
+            "secret",
+            1700000000,
+            "POST",
+            "/api/work/submit",
+            body,
+            "not hex"
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+use crate::operators::standard_normal;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Sensor readings a brain takes each step, all roughly within -1 to 1:
+/// - 0, 1: direction to the nearest visible food it can digest (x, y), scaled by vision
+/// - 2: closeness of that food (1 adjacent, 0 at the edge of vision or none)
+/// - 3, 4: direction to the nearest creature nearby (x, y)
+/// - 5: closeness of that creature
+/// - 6: how much stronger that creature is
+/// - 7: crowding, the number of creatures nearby relative to 8
+/// - 8: energy, relative to the starting energy
+/// - 9: health, relative to full health
+/// - 10: a constant 1 (bias)
+pub const BRAIN_INPUTS: usize = 11;
+
+/// Recurrent hidden neurons, whose previous values feed back in as memory
+pub const BRAIN_HIDDEN: usize = 6;
+
+/// Actions a brain chooses each step, each within -1 to 1:
+/// - 0, 1: direction to move (x, y); near 0 means stay put
+/// - 2: reaction to the nearest creature: chase it (positive) or flee (negative)
+pub const BRAIN_OUTPUTS: usize = 3;
+
+/// Weights into each hidden neuron: the inputs, the previous hidden values and a bias
+const HIDDEN_FAN_IN: usize = BRAIN_INPUTS + BRAIN_HIDDEN + 1;
+
+/// Weights into each output neuron: the hidden values and a bias
+const OUTPUT_FAN_IN: usize = BRAIN_HIDDEN + 1;
+
+/// Number of weights in a brain
+pub const BRAIN_WEIGHTS: usize = BRAIN_HIDDEN * HIDDEN_FAN_IN + BRAIN_OUTPUTS * OUTPUT_FAN_IN;
+
+/// Standard deviation of the noise a mutation adds to a weight
+const WEIGHT_MUTATION_STEP: f64 = 0.2;
+
+/// Largest magnitude of a weight
+const MAX_WEIGHT: f64 = 4.0;
+
+/// A small recurrent neural network deciding how a creature acts.
+/// Serialized as its list of weights; the layout is fixed by the constants
+/// above, so changing them needs a new `GENOME_SCHEMA_VERSION`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<f64>", into = "Vec<f64>")]
+pub struct Brain {
+    /// Weights of each hidden neuron, then of each output neuron
+    weights: Vec<f64>,
+}
+
+impl TryFrom<Vec<f64>> for Brain {
+    type Error = String;
+
+    fn try_from(weights: Vec<f64>) -> Result<Self, String> {
+        if weights.len() != BRAIN_WEIGHTS {
+            return Err(format!(
+                "brain has {} weights, expected {}",
+                weights.len(),
+                BRAIN_WEIGHTS
+            ));
+        }
+        Ok(Self { weights })
+    }
+}
+
+impl From<Brain> for Vec<f64> {
+    fn from(brain: Brain) -> Self {
+        brain.weights
+    }
+}
+
+fn random_weight(rng: &mut dyn RngCore) -> f64 {
+    rng.gen_range(-1.0..1.0)
+}
+
+impl Brain {
+    /// Brain with weights drawn uniformly from -1 to 1
+    pub fn random(rng: &mut dyn RngCore) -> Self {
+        Self {
+            weights: (0..BRAIN_WEIGHTS).map(|_| random_weight(rng)).collect(),
+        }
+    }
+
+    /// Every weight is finite and within the weight limit
+    pub fn is_valid(&self) -> bool {
+        self.weights.len() == BRAIN_WEIGHTS && self.weights.iter().all(|w| w.abs() <= MAX_WEIGHT)
+    }
+
+    /// Run one step: update `memory` (the hidden neurons) from the inputs and
+    /// its previous values, and return the outputs
+    pub fn think(
+        &self,
+        inputs: &[f64; BRAIN_INPUTS],
+        memory: &mut [f64; BRAIN_HIDDEN],
+    ) -> [f64; BRAIN_OUTPUTS] {
+        let (hidden_weights, output_weights) = self.weights.split_at(BRAIN_HIDDEN * HIDDEN_FAN_IN);
+
+        let previous = *memory;
+        for (neuron, weights) in memory.iter_mut().zip(hidden_weights.chunks(HIDDEN_FAN_IN)) {
+            let sum: f64 = inputs
+                .iter()
+                .chain(&previous)
+                .chain(&[1.0])
+                .zip(weights)
+                .map(|(value, weight)| value * weight)
+                .sum();
+            *neuron = sum.tanh();
+        }
+
+        std::array::from_fn(|i| {
+            let weights = &output_weights[i * OUTPUT_FAN_IN..(i + 1) * OUTPUT_FAN_IN];
+            let sum: f64 = memory
+                .iter()
+                .chain(&[1.0])
+                .zip(weights)
+                .map(|(value, weight)| value * weight)
+                .sum();
+            sum.tanh()
+        })
+    }
+
+    /// Add Gaussian noise to each weight with probability `rate`
+    pub fn mutate(&mut self, rate: f64, rng: &mut dyn RngCore) {
+        for weight in &mut self.weights {
+            if rng.gen::<f64>() < rate {
+                let noise = standard_normal(rng);
+                *weight = (*weight + WEIGHT_MUTATION_STEP * noise).clamp(-MAX_WEIGHT, MAX_WEIGHT);
+            }
+        }
+    }
+
+    /// Child brain taking each neuron, with all its incoming weights, from
+    /// either parent, so neurons keep working as they did in the parent
+    pub fn crossover(&self, other: &Brain, rng: &mut dyn RngCore) -> Brain {
+        let (my_hidden, my_outputs) = self.weights.split_at(BRAIN_HIDDEN * HIDDEN_FAN_IN);
+        let (their_hidden, their_outputs) = other.weights.split_at(BRAIN_HIDDEN * HIDDEN_FAN_IN);
+        let mut weights = Vec::with_capacity(BRAIN_WEIGHTS);
+        for (a, b) in my_hidden
+            .chunks(HIDDEN_FAN_IN)
+            .zip(their_hidden.chunks(HIDDEN_FAN_IN))
+            .chain(
+                my_outputs
+                    .chunks(OUTPUT_FAN_IN)
+                    .zip(their_outputs.chunks(OUTPUT_FAN_IN)),
+            )
+        {
+            weights.extend_from_slice(if rng.gen() { a } else { b });
+        }
+        Brain { weights }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_think_is_bounded_and_recurrent() {
+        let mut rng = rand::thread_rng();
+        let brain = Brain::random(&mut rng);
+        let inputs = [0.5; BRAIN_INPUTS];
+
+        let mut memory = [0.0; BRAIN_HIDDEN];
+        let first = brain.think(&inputs, &mut memory);
+        assert!(first.iter().all(|o| (-1.0..=1.0).contains(o)));
+        assert!(memory.iter().any(|m| *m != 0.0));
+
+        // Same inputs and memory give the same outputs
+        let mut again = [0.0; BRAIN_HIDDEN];
+        assert_eq!(brain.think(&inputs, &mut again), first);
+        assert_eq!(again, memory);
+    }
+
+    #[test]
+    fn test_mutation_and_crossover() {
+        let mut rng = rand::thread_rng();
+        let a = Brain::random(&mut rng);
+        let b = Brain::random(&mut rng);
+
+        let child = a.crossover(&b, &mut rng);
+        assert!(child.is_valid());
+        // Each neuron's weights come whole from one parent
+        for (i, weights) in child.weights[..BRAIN_HIDDEN * HIDDEN_FAN_IN]
+            .chunks(HIDDEN_FAN_IN)
+            .enumerate()
+        {
+            let range = i * HIDDEN_FAN_IN..(i + 1) * HIDDEN_FAN_IN;
+            assert!(weights == &a.weights[range.clone()] || weights == &b.weights[range]);
+        }
+
+        let mut mutated = a.clone();
+        mutated.mutate(1.0, &mut rng);
+        assert!(mutated.is_valid());
+        assert_ne!(mutated, a);
+        let mut unchanged = a.clone();
+        unchanged.mutate(0.0, &mut rng);
+        assert_eq!(unchanged, a);
+    }
+
+    #[test]
+    fn test_serialization() {
+        let brain = Brain::random(&mut rand::thread_rng());
+        let json = serde_json::to_string(&brain).unwrap();
+        assert!(json.starts_with('['));
+        let parsed: Brain = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.weights.len(), BRAIN_WEIGHTS);
+
+        assert!(serde_json::from_str::<Brain>("[0.1, 0.2]").is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+/// How creatures contest food and hunters' attacks, as configured and sent
+/// with work assignments
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CombatConfig {
+    /// Chance that a creature of speed 1 flees a stronger rival or a hunter
+    /// before a fight; slower creatures flee less often
+    pub flee_chance: f64,
+    /// Chance that creatures left contesting food fight over it rather than
+    /// sharing it in proportion to their combat power
+    pub fight_chance: f64,
+    /// Health each loser of a fight loses per unit of the winner's combat power
+    pub damage: f64,
+    /// Fraction of `damage` the winner takes from the strongest loser
+    pub retaliation: f64,
+    /// Energy every creature spends on a fight or a hunt
+    pub energy_cost: f64,
+}
+
+impl Default for CombatConfig {
+    fn default() -> Self {
+        Self {
+            flee_chance: 0.3,
+            fight_chance: 0.7,
+            damage: 0.25,
+            retaliation: 0.5,
+            energy_cost: 2.0,
+        }
+    }
+}
+
+impl CombatConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("flee_chance", self.flee_chance),
+            ("fight_chance", self.fight_chance),
+            ("retaliation", self.retaliation),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("Combat {} must be between 0 and 1", name));
+            }
+        }
+        for (name, value) in [("damage", self.damage), ("energy_cost", self.energy_cost)] {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(format!("Combat {} must be a non-negative number", name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Chance that a creature of the given speed flees
+    pub fn flee_probability(&self, speed: f64) -> f64 {
+        let chance = self.flee_chance * speed;
+        if chance.is_nan() {
+            0.0
+        } else {
+            chance.clamp(0.0, 1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combat_config() {
+        let config: CombatConfig = serde_json::from_str(r#"{"flee_chance": 0.8}"#).unwrap();
+        assert_eq!(config.flee_chance, 0.8);
+        assert_eq!(config.damage, 0.25);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.flee_probability(0.5), 0.4);
+        assert_eq!(config.flee_probability(f64::NAN), 0.0);
+
+        for bad in [
+            CombatConfig {
+                fight_chance: 1.5,
+                ..config
+            },
+            CombatConfig {
+                damage: -1.0,
+                ..config
+            },
+            CombatConfig {
+                energy_cost: f64::NAN,
+                ..config
+            },
+        ] {
+            assert!(bad.validate().is_err());
+        }
+    }
+}
@@ -1,164 +1,266 @@
+use crate::brain::Brain;
+use crate::operators::{CrossoverOperator, MutationOperator, UniformCrossover, UniformMutation};
 use rand::Rng;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, IgnoredAny, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
 
-/// Total trait budget - the sum of all traits should equal this value
+/// Total trait budget - the weighted sum of all budgeted traits should equal this value
 /// With 5 traits, a budget of 2.5 means an average of 0.5 per trait
 /// This forces trade-offs: high values in some traits means low values in others
 const TRAIT_BUDGET: f64 = 2.5;
 
-/// A genome represents the genetic makeup of a creature.
-/// Each gene has a value between 0.0 and 1.0.
-///
-/// Design Philosophy:
-/// - Higher strength/speed/size provides advantages but increases energy costs
-/// - Efficiency reduces energy costs
-/// - Reproduction affects breeding rate but costs energy
-/// - **Trait Budget**: All traits sum to a fixed budget, forcing strategic trade-offs
-/// - This creates diverse strategies and prevents all traits from maxing out
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct Genome {
-    /// Physical strength - increases combat power but costs energy
-    pub strength: f64,
-
-    /// Movement speed - helps escape/chase but costs energy
-    pub speed: f64,
-
-    /// Body size - provides more health but increases energy needs
-    pub size: f64,
-
-    /// Energy efficiency - reduces overall energy consumption
-    pub efficiency: f64,
-
-    /// Reproduction rate - affects breeding frequency
-    pub reproduction: f64,
+/// Version of the gene schema, written with every serialized genome.
+/// Adding a gene doesn't need a new version, since genomes serialized before it
+/// existed read back with the gene's default. Bump it when a gene is removed or
+/// changes meaning, and convert older genomes in `Genome::deserialize`.
+pub const GENOME_SCHEMA_VERSION: u32 = 1;
+
+/// Key holding the schema version in a serialized genome
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Key holding the self-adaptive mutation step sizes in a serialized genome
+const MUTATION_STEPS_KEY: &str = "mutation_steps";
+
+/// Key holding the brain's weights in a serialized genome
+const BRAIN_KEY: &str = "brain";
+
+/// Everything the generic genome operations need to know about one gene
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GeneSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub min: f64,
+    pub max: f64,
+
+    /// Value of genomes serialized before the gene existed
+    pub default: f64,
+
+    /// How much the gene counts toward `TRAIT_BUDGET`; 0 keeps it out of the budget.
+    /// Budgeted genes must have a minimum of 0.
+    pub budget_weight: f64,
+
+    /// Largest change a single mutation makes, as a fraction of the gene's range
+    pub mutation_scale: f64,
+
+    /// Energy per tick per unit of the gene, before the efficiency multiplier
+    pub energy_cost: f64,
+}
+
+impl GeneSpec {
+    /// Largest change a single mutation makes, in the gene's units
+    pub fn mutation_step(&self) -> f64 {
+        self.mutation_scale * (self.max - self.min)
+    }
 }
 
+/// Defines `Genome` with one `f64` field per gene, the `GENES` schema in the
+/// same order, and conversions between the two
+macro_rules! genome_schema {
+    (
+        $(#[$meta:meta])*
+        pub struct Genome {
+            $($name:ident {
+                description: $description:literal,
+                $($field:ident: $value:expr),* $(,)?
+            }),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Genome {
+            $(
+                #[doc = $description]
+                pub $name: f64,
+            )*
+
+            /// Per-gene step sizes evolved by self-adaptive Gaussian mutation,
+            /// in schema order. None until such a mutation first applies.
+            pub mutation_steps: Option<[f64; GENE_COUNT]>,
+
+            /// Neural network choosing the creature's actions; creatures
+            /// without one follow the fixed foraging rule
+            pub brain: Option<Brain>,
+        }
+
+        /// The genes of a `Genome`, in field order
+        pub const GENES: &[GeneSpec] = &[$(GeneSpec {
+            name: stringify!($name),
+            description: $description,
+            $($field: $value),*
+        }),*];
+
+        impl Genome {
+            /// Gene values in schema order
+            pub fn values(&self) -> [f64; GENE_COUNT] {
+                [$(self.$name),*]
+            }
+
+            /// Genome with the given gene values in schema order, as they are.
+            /// Use `normalized` to hold them to the trait budget.
+            pub fn from_values(values: [f64; GENE_COUNT]) -> Self {
+                let [$($name),*] = values;
+                Self { $($name,)* mutation_steps: None, brain: None }
+            }
+
+            /// Replace the gene values, keeping the mutation step sizes
+            pub fn set_values(&mut self, values: [f64; GENE_COUNT]) {
+                let [$($name),*] = values;
+                $(self.$name = $name;)*
+            }
+
+            /// Create a genome with specific values (normalized to fit TRAIT_BUDGET)
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($name: f64),*) -> Self {
+                Self::from_values([$($name),*]).normalized()
+            }
+        }
+    };
+}
+
+genome_schema! {
+    /// A genome represents the genetic makeup of a creature.
+    /// Its genes are listed in `GENES`, each with a range (0.0 to 1.0 so far).
+    ///
+    /// Design Philosophy:
+    /// - Higher strength/speed/size provides advantages but increases energy costs
+    /// - Efficiency reduces energy costs
+    /// - Reproduction affects breeding rate but costs energy
+    /// - Diet places the creature between plant eater and hunter, outside the budget
+    /// - Mate preference steers the choice of partners, also outside the budget
+    /// - **Trait Budget**: Budgeted traits sum to a fixed budget, forcing strategic trade-offs
+    /// - This creates diverse strategies and prevents all traits from maxing out
+    pub struct Genome {
+        strength {
+            description: "Physical strength - increases combat power but costs energy",
+            min: 0.0, max: 1.0, default: 0.5,
+            budget_weight: 1.0, mutation_scale: 0.1, energy_cost: 0.3,
+        },
+        speed {
+            description: "Movement speed - helps escape/chase but costs energy",
+            min: 0.0, max: 1.0, default: 0.5,
+            budget_weight: 1.0, mutation_scale: 0.1, energy_cost: 0.2,
+        },
+        size {
+            description: "Body size - provides more health but increases energy needs",
+            min: 0.0, max: 1.0, default: 0.5,
+            budget_weight: 1.0, mutation_scale: 0.1, energy_cost: 0.25,
+        },
+        efficiency {
+            description: "Energy efficiency - reduces overall energy consumption",
+            min: 0.0, max: 1.0, default: 0.5,
+            budget_weight: 1.0, mutation_scale: 0.1, energy_cost: 0.0,
+        },
+        reproduction {
+            description: "Reproduction rate - affects breeding frequency",
+            min: 0.0, max: 1.0, default: 0.5,
+            budget_weight: 1.0, mutation_scale: 0.1, energy_cost: 0.1,
+        },
+        diet {
+            description: "Diet - below 0.5 eats plants, above hunts and scavenges other creatures",
+            min: 0.0, max: 1.0, default: 0.0,
+            budget_weight: 0.0, mutation_scale: 0.1, energy_cost: 0.0,
+        },
+        mate_preference {
+            description: "Mate preference - below 0.5 favours similar partners, above strong ones",
+            min: 0.0, max: 1.0, default: 0.5,
+            budget_weight: 0.0, mutation_scale: 0.1, energy_cost: 0.0,
+        },
+    }
+}
+
+/// Number of genes in a `Genome`
+pub const GENE_COUNT: usize = GENES.len();
+
 impl Genome {
-    /// Normalize traits to fit within the TRAIT_BUDGET while keeping traits in [0, 1]
+    /// Normalize traits to fit within the TRAIT_BUDGET while keeping traits in range
     /// This enforces trade-offs: high values in some traits means low values in others
-    fn normalize(&mut self) {
-        // Clamp all values to [0, 1] first
-        self.strength = self.strength.clamp(0.0, 1.0);
-        self.speed = self.speed.clamp(0.0, 1.0);
-        self.size = self.size.clamp(0.0, 1.0);
-        self.efficiency = self.efficiency.clamp(0.0, 1.0);
-        self.reproduction = self.reproduction.clamp(0.0, 1.0);
-
-        let sum = self.strength + self.speed + self.size + self.efficiency + self.reproduction;
-
-        // Scale proportionally to meet TRAIT_BUDGET, then clamp again
-        if sum > 0.0 {
-            let scale = TRAIT_BUDGET / sum;
-            self.strength = (self.strength * scale).min(1.0);
-            self.speed = (self.speed * scale).min(1.0);
-            self.size = (self.size * scale).min(1.0);
-            self.efficiency = (self.efficiency * scale).min(1.0);
-            self.reproduction = (self.reproduction * scale).min(1.0);
-        } else {
-            // If all traits are 0, distribute evenly
-            let even_split = TRAIT_BUDGET / 5.0;
-            self.strength = even_split;
-            self.speed = even_split;
-            self.size = even_split;
-            self.efficiency = even_split;
-            self.reproduction = even_split;
+    pub(crate) fn normalize(&mut self) {
+        // Clamp all values to their range first
+        let mut values = self.values();
+        for (value, gene) in values.iter_mut().zip(GENES) {
+            *value = value.clamp(gene.min, gene.max);
         }
+
+        let sum: f64 = values
+            .iter()
+            .zip(GENES)
+            .map(|(value, gene)| value * gene.budget_weight)
+            .sum();
+        let total_weight: f64 = GENES.iter().map(|gene| gene.budget_weight).sum();
+
+        // Scale budgeted traits proportionally to meet TRAIT_BUDGET, then clamp again.
+        // If they are all 0, distribute the budget evenly.
+        for (value, gene) in values.iter_mut().zip(GENES) {
+            if gene.budget_weight > 0.0 {
+                *value = if sum > 0.0 {
+                    *value * (TRAIT_BUDGET / sum)
+                } else {
+                    TRAIT_BUDGET / total_weight
+                }
+                .min(gene.max);
+            }
+        }
+
+        self.set_values(values);
+    }
+
+    /// The genome with its traits held to the TRAIT_BUDGET
+    pub fn normalized(mut self) -> Self {
+        self.normalize();
+        self
     }
 
     /// Create a new random genome with balanced traits
     /// Traits will sum to TRAIT_BUDGET, forcing strategic trade-offs
     pub fn random() -> Self {
         let mut rng = rand::thread_rng();
-        let mut genome = Self {
-            strength: rng.gen(),
-            speed: rng.gen(),
-            size: rng.gen(),
-            efficiency: rng.gen(),
-            reproduction: rng.gen(),
-        };
-        genome.normalize();
-        genome
-    }
-
-    /// Create a genome with specific values (normalized to fit TRAIT_BUDGET)
-    pub fn new(strength: f64, speed: f64, size: f64, efficiency: f64, reproduction: f64) -> Self {
-        let mut genome = Self {
-            strength: strength.clamp(0.0, 1.0),
-            speed: speed.clamp(0.0, 1.0),
-            size: size.clamp(0.0, 1.0),
-            efficiency: efficiency.clamp(0.0, 1.0),
-            reproduction: reproduction.clamp(0.0, 1.0),
-        };
-        genome.normalize();
-        genome
+        let values = std::array::from_fn(|i| {
+            let gene = &GENES[i];
+            gene.min + rng.gen::<f64>() * (gene.max - gene.min)
+        });
+        Self::from_values(values).normalized()
     }
 
     /// Mutate this genome by adding random noise while maintaining TRAIT_BUDGET
     /// Mutations shift trait values, creating trade-offs between different traits
     pub fn mutate(&mut self, mutation_rate: f64) {
-        let mut rng = rand::thread_rng();
-        let mut mutated = false;
-
-        if rng.gen::<f64>() < mutation_rate {
-            self.strength = (self.strength + rng.gen_range(-0.1..0.1)).clamp(0.0, 1.0);
-            mutated = true;
-        }
-        if rng.gen::<f64>() < mutation_rate {
-            self.speed = (self.speed + rng.gen_range(-0.1..0.1)).clamp(0.0, 1.0);
-            mutated = true;
-        }
-        if rng.gen::<f64>() < mutation_rate {
-            self.size = (self.size + rng.gen_range(-0.1..0.1)).clamp(0.0, 1.0);
-            mutated = true;
-        }
-        if rng.gen::<f64>() < mutation_rate {
-            self.efficiency = (self.efficiency + rng.gen_range(-0.1..0.1)).clamp(0.0, 1.0);
-            mutated = true;
-        }
-        if rng.gen::<f64>() < mutation_rate {
-            self.reproduction = (self.reproduction + rng.gen_range(-0.1..0.1)).clamp(0.0, 1.0);
-            mutated = true;
-        }
-
-        // Re-normalize to maintain trait budget after mutations
-        if mutated {
-            self.normalize();
-        }
+        UniformMutation.mutate(self, mutation_rate, &mut rand::thread_rng());
     }
 
     /// Cross two genomes to create offspring with normalized traits
     pub fn crossover(&self, other: &Genome) -> Genome {
-        let mut rng = rand::thread_rng();
-        let mut child = Genome {
-            strength: if rng.gen() {
-                self.strength
-            } else {
-                other.strength
-            },
-            speed: if rng.gen() { self.speed } else { other.speed },
-            size: if rng.gen() { self.size } else { other.size },
-            efficiency: if rng.gen() {
-                self.efficiency
-            } else {
-                other.efficiency
-            },
-            reproduction: if rng.gen() {
-                self.reproduction
-            } else {
-                other.reproduction
-            },
-        };
-        child.normalize();
-        child
+        UniformCrossover.crossover(self, other, &mut rand::thread_rng())
+    }
+
+    /// Every trait is a finite number within its gene's range, any mutation
+    /// step sizes are finite and positive, and any brain has valid weights
+    pub fn is_valid(&self) -> bool {
+        let steps_valid = self
+            .mutation_steps
+            .is_none_or(|steps| steps.iter().all(|step| step.is_finite() && *step > 0.0));
+        steps_valid
+            && self.brain.as_ref().is_none_or(Brain::is_valid)
+            && self
+                .values()
+                .iter()
+                .zip(GENES)
+                .all(|(value, gene)| (gene.min..=gene.max).contains(value))
     }
 
     /// Calculate the energy cost per tick for this genome
-    /// Higher strength, speed, and size increase cost
+    /// Each trait adds its gene's energy cost (higher strength, speed, and size cost most)
     /// Higher efficiency decreases cost
     pub fn energy_cost(&self) -> f64 {
         let base_cost = 0.2; // Reduced from 1.0 to 0.2
-        let trait_cost =
-            self.strength * 0.3 + self.speed * 0.2 + self.size * 0.25 + self.reproduction * 0.1; // Reduced multipliers by ~85%
+        let trait_cost: f64 = self
+            .values()
+            .iter()
+            .zip(GENES)
+            .map(|(value, gene)| value * gene.energy_cost)
+            .sum();
         let efficiency_multiplier = 1.5 - (self.efficiency * 0.5); // Range 1.0 to 1.5 (was 1.0 to 2.0)
 
         base_cost + trait_cost * efficiency_multiplier
@@ -180,17 +282,114 @@ impl Genome {
         // Balanced fitness that doesn't favor any single strategy
         (combat * survival * breeding).powf(1.0 / 3.0)
     }
+
+    /// How alike two genomes' genes are: 1 for identical genes, 0 for genes
+    /// at opposite ends of their ranges
+    pub fn similarity(&self, other: &Genome) -> f64 {
+        let difference: f64 = self
+            .values()
+            .iter()
+            .zip(other.values())
+            .zip(GENES)
+            .map(|((a, b), gene)| (a - b).abs() / (gene.max - gene.min))
+            .sum();
+        (1.0 - difference / GENE_COUNT as f64).clamp(0.0, 1.0)
+    }
 }
 
 impl Default for Genome {
     fn default() -> Self {
-        Self {
-            strength: 0.5,
-            speed: 0.5,
-            size: 0.5,
-            efficiency: 0.5,
-            reproduction: 0.5,
+        Self::from_values(std::array::from_fn(|i| GENES[i].default))
+    }
+}
+
+/// Gene values serialized as an object keyed by gene name
+struct GeneValues<'a>(&'a [f64; GENE_COUNT]);
+
+impl Serialize for GeneValues<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(GENE_COUNT))?;
+        for (gene, value) in GENES.iter().zip(self.0) {
+            map.serialize_entry(gene.name, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Serialized as an object of gene values by name, plus the schema version,
+/// any mutation step sizes (also keyed by gene name) and any brain
+impl Serialize for Genome {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry(SCHEMA_VERSION_KEY, &GENOME_SCHEMA_VERSION)?;
+        for (gene, value) in GENES.iter().zip(self.values()) {
+            map.serialize_entry(gene.name, &value)?;
+        }
+        if let Some(steps) = &self.mutation_steps {
+            map.serialize_entry(MUTATION_STEPS_KEY, &GeneValues(steps))?;
+        }
+        if let Some(brain) = &self.brain {
+            map.serialize_entry(BRAIN_KEY, brain)?;
         }
+        map.end()
+    }
+}
+
+/// Genes missing from the input take their default and unknown keys are
+/// ignored, so genomes stay readable as genes are added. Genomes without a
+/// schema version predate it and are read as version 1.
+impl<'de> Deserialize<'de> for Genome {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct GenomeVisitor;
+
+        impl<'de> Visitor<'de> for GenomeVisitor {
+            type Value = Genome;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of gene values")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Genome, A::Error> {
+                let mut values = Genome::default().values();
+                let mut version = 1;
+                let mut mutation_steps = None;
+                let mut brain = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == SCHEMA_VERSION_KEY {
+                        version = map.next_value()?;
+                    } else if key == MUTATION_STEPS_KEY {
+                        // Genes without a step start from their mutation scale
+                        let steps: HashMap<String, f64> = map.next_value()?;
+                        mutation_steps = Some(std::array::from_fn(|i| {
+                            let gene = &GENES[i];
+                            steps
+                                .get(gene.name)
+                                .copied()
+                                .unwrap_or(gene.mutation_step())
+                        }));
+                    } else if key == BRAIN_KEY {
+                        brain = map.next_value()?;
+                    } else if let Some(i) = GENES.iter().position(|gene| gene.name == key) {
+                        values[i] = map.next_value()?;
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                if version > GENOME_SCHEMA_VERSION {
+                    return Err(de::Error::custom(format!(
+                        "genome schema version {} is newer than the supported {}",
+                        version, GENOME_SCHEMA_VERSION
+                    )));
+                }
+                Ok(Genome {
+                    mutation_steps,
+                    brain,
+                    ..Genome::from_values(values)
+                })
+            }
+        }
+
+        deserializer.deserialize_map(GenomeVisitor)
     }
 }
 
@@ -200,7 +399,7 @@ mod tests {
 
     #[test]
     fn test_genome_creation() {
-        let genome = Genome::new(0.8, 0.6, 0.7, 0.5, 0.4);
+        let genome = Genome::new(0.8, 0.6, 0.7, 0.5, 0.4, 0.0, 0.5);
         // After normalization, traits should sum close to TRAIT_BUDGET (or less if clamped)
         let sum =
             genome.strength + genome.speed + genome.size + genome.efficiency + genome.reproduction;
@@ -219,7 +418,7 @@ mod tests {
 
     #[test]
     fn test_genome_clamping() {
-        let genome = Genome::new(1.5, -0.5, 0.5, 0.5, 0.5);
+        let genome = Genome::new(1.5, -0.5, 0.5, 0.5, 0.5, 0.0, 0.5);
         // Values should be clamped and normalized
         assert!(genome.strength >= 0.0 && genome.strength <= 1.0);
         assert!(genome.speed >= 0.0 && genome.speed <= 1.0);
@@ -237,16 +436,16 @@ mod tests {
 
     #[test]
     fn test_energy_cost() {
-        let high_cost = Genome::new(1.0, 1.0, 1.0, 0.0, 1.0);
-        let low_cost = Genome::new(0.0, 0.0, 0.0, 1.0, 0.0);
+        let high_cost = Genome::new(1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.5);
+        let low_cost = Genome::new(0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.5);
 
         assert!(high_cost.energy_cost() > low_cost.energy_cost());
     }
 
     #[test]
     fn test_crossover() {
-        let parent1 = Genome::new(1.0, 0.0, 1.0, 0.0, 1.0);
-        let parent2 = Genome::new(0.0, 1.0, 0.0, 1.0, 0.0);
+        let parent1 = Genome::new(1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.5);
+        let parent2 = Genome::new(0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.5);
 
         let child = parent1.crossover(&parent2);
 
@@ -267,7 +466,7 @@ mod tests {
 
     #[test]
     fn test_mutation_maintains_bounds() {
-        let mut genome = Genome::new(0.5, 0.5, 0.5, 0.5, 0.5);
+        let mut genome = Genome::new(0.5, 0.5, 0.5, 0.5, 0.5, 0.0, 0.5);
 
         for _ in 0..100 {
             genome.mutate(1.0); // Always mutate
@@ -294,4 +493,65 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_similarity() {
+        let a = Genome::new(1.0, 0.0, 1.0, 0.0, 0.5, 0.0, 0.5);
+        let b = Genome::new(0.0, 1.0, 0.0, 1.0, 0.5, 0.0, 0.5);
+        assert_eq!(a.similarity(&a), 1.0);
+        assert!(a.similarity(&b) < 0.5);
+        assert_eq!(a.similarity(&b), b.similarity(&a));
+    }
+
+    #[test]
+    fn test_schema_is_consistent() {
+        assert_eq!(Genome::default().values().len(), GENES.len());
+        for gene in GENES {
+            assert!(gene.min < gene.max, "{} has an empty range", gene.name);
+            assert!((gene.min..=gene.max).contains(&gene.default));
+            assert!(gene.budget_weight == 0.0 || gene.min == 0.0);
+        }
+        let genome = Genome::random();
+        assert_eq!(Genome::from_values(genome.values()), genome);
+        assert!(genome.is_valid());
+    }
+
+    #[test]
+    fn test_versioned_serialization() {
+        let genome = Genome::random();
+        let json = serde_json::to_value(&genome).unwrap();
+        assert_eq!(json[SCHEMA_VERSION_KEY], GENOME_SCHEMA_VERSION);
+        assert_eq!(json["strength"], genome.strength);
+        assert_eq!(serde_json::from_value::<Genome>(json).unwrap(), genome);
+        assert!(!serde_json::to_string(&genome).unwrap().contains(BRAIN_KEY));
+
+        let mut thinking = Genome::random();
+        thinking.brain = Some(Brain::random(&mut rand::thread_rng()));
+        let json = serde_json::to_value(&thinking).unwrap();
+        assert_eq!(
+            json[BRAIN_KEY].as_array().unwrap().len(),
+            crate::brain::BRAIN_WEIGHTS
+        );
+        assert!(serde_json::from_value::<Genome>(json)
+            .unwrap()
+            .brain
+            .is_some());
+
+        // Older genomes lack genes added since; newer ones may carry unknown genes
+        let old: Genome =
+            serde_json::from_str(r#"{"strength": 0.9, "speed": 0.1, "vision": 0.7}"#).unwrap();
+        assert_eq!(old.strength, 0.9);
+        assert_eq!(old.size, GENES[2].default);
+        // Genomes from before the diet gene are plant eaters
+        assert_eq!(old.diet, 0.0);
+        // ...and mate at random
+        assert_eq!(old.mate_preference, 0.5);
+
+        let newer = format!(
+            r#"{{"{}": {}}}"#,
+            SCHEMA_VERSION_KEY,
+            GENOME_SCHEMA_VERSION + 1
+        );
+        assert!(serde_json::from_str::<Genome>(&newer).is_err());
+    }
 }
@@ -1,8 +1,23 @@
+pub mod auth;
+pub mod brain;
+pub mod combat;
 pub mod genes;
+pub mod migration;
+pub mod operators;
 pub mod protocol;
+pub mod schedule;
+pub mod terrain;
+pub mod topology;
 
+pub use brain::Brain;
+pub use combat::CombatConfig;
 pub use genes::*;
+pub use migration::{MigrationConfig, Topology};
+pub use operators::{CrossoverOperator, MutationOperator};
 pub use protocol::*;
+pub use schedule::{EnvironmentSchedule, ScheduleEvent};
+pub use terrain::{Terrain, TerrainConfig};
+pub use topology::WorldTopology;
 
 /// The protocol version - clients must match this exactly
 /// Version 2: Spatial simulation with competitive evolution
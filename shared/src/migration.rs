@@ -0,0 +1,125 @@
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// Most islands a client runs side by side for one work assignment
+pub const MAX_ISLANDS: usize = 16;
+
+/// Which islands each island sends its migrants to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Topology {
+    /// To the next island, the last sending to the first
+    #[default]
+    Ring,
+
+    /// Each migrant to any other island
+    FullyConnected,
+
+    /// All of an island's migrants to one other island, drawn anew at
+    /// every migration
+    Random,
+}
+
+impl Topology {
+    /// Islands that migrants from `source` may go to, out of `islands`
+    pub fn destinations(self, source: usize, islands: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        let others: Vec<usize> = (0..islands).filter(|&i| i != source).collect();
+        match self {
+            Topology::Ring if islands > 1 => vec![(source + 1) % islands],
+            Topology::Ring => Vec::new(),
+            Topology::FullyConnected => others,
+            Topology::Random => others.choose(rng).copied().into_iter().collect(),
+        }
+    }
+}
+
+/// Islands run together for one work assignment and the migration between
+/// them, as configured and sent with work assignments
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MigrationConfig {
+    /// Islands in the archipelago, each starting from all seed genomes
+    pub islands: usize,
+    pub topology: Topology,
+    /// Fraction of each island's creatures that leave at every migration
+    pub rate: f64,
+    /// Steps between migrations
+    pub interval: u32,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        Self {
+            islands: 1,
+            topology: Topology::default(),
+            rate: 0.05,
+            interval: 100,
+        }
+    }
+}
+
+impl MigrationConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_ISLANDS).contains(&self.islands) {
+            return Err(format!("Migration islands must be 1 to {}", MAX_ISLANDS));
+        }
+        if !(0.0..=1.0).contains(&self.rate) {
+            return Err("Migration rate must be between 0 and 1".to_string());
+        }
+        if self.interval == 0 {
+            return Err("Migration interval must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether there is more than one island
+    pub fn is_archipelago(&self) -> bool {
+        self.islands > 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topologies() {
+        let mut rng = rand::thread_rng();
+        assert_eq!(Topology::Ring.destinations(3, 4, &mut rng), vec![0]);
+        assert_eq!(
+            Topology::Ring.destinations(0, 1, &mut rng),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            Topology::FullyConnected.destinations(1, 4, &mut rng),
+            vec![0, 2, 3]
+        );
+        let random = Topology::Random.destinations(2, 4, &mut rng);
+        assert_eq!(random.len(), 1);
+        assert_ne!(random[0], 2);
+
+        let config: MigrationConfig =
+            serde_json::from_str(r#"{"islands": 4, "topology": {"type": "fully_connected"}}"#)
+                .unwrap();
+        assert_eq!(config.topology, Topology::FullyConnected);
+        assert_eq!(config.interval, 100);
+        assert!(config.validate().is_ok());
+        for bad in [
+            MigrationConfig {
+                islands: 0,
+                ..config
+            },
+            MigrationConfig {
+                rate: 1.5,
+                ..config
+            },
+            MigrationConfig {
+                interval: 0,
+                ..config
+            },
+        ] {
+            assert!(bad.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,437 @@
+use crate::brain::Brain;
+use crate::genes::{Genome, GENES, GENE_COUNT};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Smallest self-adaptive step size, as a fraction of the gene's range.
+/// Keeps the step sizes from collapsing to zero and freezing the genome.
+const MIN_STEP_FRACTION: f64 = 0.001;
+
+/// Changes some genes of a genome, leaving it within the trait budget
+pub trait MutationOperator {
+    /// Mutate each gene with probability `rate`
+    fn mutate(&self, genome: &mut Genome, rate: f64, rng: &mut dyn RngCore);
+}
+
+/// Combines two parent genomes into a child within the trait budget
+pub trait CrossoverOperator {
+    fn crossover(&self, a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Genome;
+}
+
+/// Mutate the genome's brain, if it has one, at the same rate as its genes
+fn mutate_brain(genome: &mut Genome, rate: f64, rng: &mut dyn RngCore) {
+    if let Some(brain) = &mut genome.brain {
+        brain.mutate(rate, rng);
+    }
+}
+
+/// Sample from the standard normal distribution (Box-Muller)
+pub(crate) fn standard_normal(rng: &mut dyn RngCore) -> f64 {
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+/// Uniform noise of up to each gene's mutation step, in either direction
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniformMutation;
+
+impl MutationOperator for UniformMutation {
+    fn mutate(&self, genome: &mut Genome, rate: f64, rng: &mut dyn RngCore) {
+        let mut values = genome.values();
+        let mut mutated = false;
+
+        for (value, gene) in values.iter_mut().zip(GENES) {
+            if rng.gen::<f64>() < rate {
+                let step = gene.mutation_step();
+                *value = (*value + rng.gen_range(-step..step)).clamp(gene.min, gene.max);
+                mutated = true;
+            }
+        }
+
+        // Re-normalize to maintain trait budget after mutations
+        if mutated {
+            genome.set_values(values);
+            genome.normalize();
+        }
+        mutate_brain(genome, rate, rng);
+    }
+}
+
+/// Gaussian noise with step sizes that evolve with the genome: each mutated
+/// gene's step size is scaled log-normally before it is used, so lineages
+/// whose step sizes suit the landscape carry them on to their offspring.
+/// Genomes without step sizes start from their genes' mutation steps.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GaussianMutation;
+
+impl MutationOperator for GaussianMutation {
+    fn mutate(&self, genome: &mut Genome, rate: f64, rng: &mut dyn RngCore) {
+        let n = GENE_COUNT as f64;
+        let global_rate = 1.0 / (2.0 * n).sqrt();
+        let gene_rate = 1.0 / (2.0 * n.sqrt()).sqrt();
+        let global = global_rate * standard_normal(rng);
+
+        let mut steps = genome
+            .mutation_steps
+            .unwrap_or_else(|| std::array::from_fn(|i| GENES[i].mutation_step()));
+        let mut values = genome.values();
+        let mut mutated = false;
+
+        for ((value, step), gene) in values.iter_mut().zip(&mut steps).zip(GENES) {
+            if rng.gen::<f64>() < rate {
+                let range = gene.max - gene.min;
+                *step = (*step * (global + gene_rate * standard_normal(rng)).exp())
+                    .clamp(MIN_STEP_FRACTION * range, range);
+                *value = (*value + *step * standard_normal(rng)).clamp(gene.min, gene.max);
+                mutated = true;
+            }
+        }
+
+        if mutated {
+            genome.set_values(values);
+            genome.mutation_steps = Some(steps);
+            genome.normalize();
+        }
+        mutate_brain(genome, rate, rng);
+    }
+}
+
+/// Deb's polynomial mutation: small changes are far more likely than large
+/// ones, the more so the larger the distribution index `eta`
+#[derive(Debug, Clone, Copy)]
+pub struct PolynomialMutation {
+    pub eta: f64,
+}
+
+impl Default for PolynomialMutation {
+    fn default() -> Self {
+        Self { eta: 20.0 }
+    }
+}
+
+impl MutationOperator for PolynomialMutation {
+    fn mutate(&self, genome: &mut Genome, rate: f64, rng: &mut dyn RngCore) {
+        let exponent = 1.0 / (self.eta + 1.0);
+        let mut values = genome.values();
+        let mut mutated = false;
+
+        for (value, gene) in values.iter_mut().zip(GENES) {
+            if rng.gen::<f64>() < rate {
+                let u = rng.gen::<f64>();
+                let delta = if u < 0.5 {
+                    (2.0 * u).powf(exponent) - 1.0
+                } else {
+                    1.0 - (2.0 * (1.0 - u)).powf(exponent)
+                };
+                *value = (*value + delta * (gene.max - gene.min)).clamp(gene.min, gene.max);
+                mutated = true;
+            }
+        }
+
+        if mutated {
+            genome.set_values(values);
+            genome.normalize();
+        }
+        mutate_brain(genome, rate, rng);
+    }
+}
+
+/// Step sizes of a child: the parents' mean, or those of the only parent
+/// that has them
+fn inherit_steps(a: &Genome, b: &Genome) -> Option<[f64; GENE_COUNT]> {
+    match (a.mutation_steps, b.mutation_steps) {
+        (Some(x), Some(y)) => Some(std::array::from_fn(|i| (x[i] + y[i]) / 2.0)),
+        (steps, None) | (None, steps) => steps,
+    }
+}
+
+/// Brain of a child: a crossover of the parents' brains, or the brain of
+/// the only parent that has one
+fn inherit_brain(a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Option<Brain> {
+    match (&a.brain, &b.brain) {
+        (Some(x), Some(y)) => Some(x.crossover(y, rng)),
+        (Some(brain), None) | (None, Some(brain)) => Some(brain.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Child with the given gene values and its parents' step sizes and brains
+fn child(values: [f64; GENE_COUNT], a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Genome {
+    Genome {
+        mutation_steps: inherit_steps(a, b),
+        brain: inherit_brain(a, b, rng),
+        ..Genome::from_values(values)
+    }
+    .normalized()
+}
+
+/// Each gene comes from either parent with equal probability
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniformCrossover;
+
+impl CrossoverOperator for UniformCrossover {
+    fn crossover(&self, a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Genome {
+        let (mine, theirs) = (a.values(), b.values());
+        let values = std::array::from_fn(|i| if rng.gen() { mine[i] } else { theirs[i] });
+        child(values, a, b, rng)
+    }
+}
+
+/// BLX-α: each gene is drawn uniformly from the interval spanned by the
+/// parents' values, widened on both sides by `alpha` times its length
+#[derive(Debug, Clone, Copy)]
+pub struct BlendCrossover {
+    pub alpha: f64,
+}
+
+impl Default for BlendCrossover {
+    fn default() -> Self {
+        Self { alpha: 0.5 }
+    }
+}
+
+impl CrossoverOperator for BlendCrossover {
+    fn crossover(&self, a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Genome {
+        let (mine, theirs) = (a.values(), b.values());
+        let values = std::array::from_fn(|i| {
+            let gene = &GENES[i];
+            let (low, high) = (mine[i].min(theirs[i]), mine[i].max(theirs[i]));
+            let spread = self.alpha * (high - low);
+            let value = low - spread + rng.gen::<f64>() * (high - low + 2.0 * spread);
+            value.clamp(gene.min, gene.max)
+        });
+        child(values, a, b, rng)
+    }
+}
+
+/// A random weighted mean of the parents, with one weight for every gene
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArithmeticCrossover;
+
+impl CrossoverOperator for ArithmeticCrossover {
+    fn crossover(&self, a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Genome {
+        let weight = rng.gen::<f64>();
+        let (mine, theirs) = (a.values(), b.values());
+        let values = std::array::from_fn(|i| weight * mine[i] + (1.0 - weight) * theirs[i]);
+        child(values, a, b, rng)
+    }
+}
+
+/// Genes before a random cut point in schema order come from the first
+/// parent, the rest from the second
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SinglePointCrossover;
+
+impl CrossoverOperator for SinglePointCrossover {
+    fn crossover(&self, a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Genome {
+        let cut = if GENE_COUNT > 1 {
+            rng.gen_range(1..GENE_COUNT)
+        } else {
+            0
+        };
+        let (mine, theirs) = (a.values(), b.values());
+        let values = std::array::from_fn(|i| if i < cut { mine[i] } else { theirs[i] });
+        child(values, a, b, rng)
+    }
+}
+
+/// Mutation operator of an island, as configured and sent with work assignments
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Mutation {
+    /// Uniform noise of up to each gene's mutation scale
+    #[default]
+    Uniform,
+
+    /// Gaussian noise with self-adaptive per-genome step sizes
+    Gaussian,
+
+    /// Polynomial mutation with distribution index `eta`
+    Polynomial {
+        #[serde(default = "default_eta")]
+        eta: f64,
+    },
+}
+
+fn default_eta() -> f64 {
+    PolynomialMutation::default().eta
+}
+
+impl Mutation {
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Mutation::Polynomial { eta } if !(eta.is_finite() && eta >= 0.0) => {
+                Err("Polynomial mutation eta must be a non-negative number".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl MutationOperator for Mutation {
+    fn mutate(&self, genome: &mut Genome, rate: f64, rng: &mut dyn RngCore) {
+        match *self {
+            Mutation::Uniform => UniformMutation.mutate(genome, rate, rng),
+            Mutation::Gaussian => GaussianMutation.mutate(genome, rate, rng),
+            Mutation::Polynomial { eta } => PolynomialMutation { eta }.mutate(genome, rate, rng),
+        }
+    }
+}
+
+/// Crossover operator of an island, as configured and sent with work assignments
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Crossover {
+    /// Each gene from either parent
+    #[default]
+    Uniform,
+
+    /// BLX-α, sampling around the parents' values
+    Blend {
+        #[serde(default = "default_alpha")]
+        alpha: f64,
+    },
+
+    /// Random weighted mean of the parents
+    Arithmetic,
+
+    /// Leading genes from one parent, the rest from the other
+    SinglePoint,
+}
+
+fn default_alpha() -> f64 {
+    BlendCrossover::default().alpha
+}
+
+impl Crossover {
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Crossover::Blend { alpha } if !(0.0..=1.0).contains(&alpha) => {
+                Err("Blend crossover alpha must be between 0 and 1".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl CrossoverOperator for Crossover {
+    fn crossover(&self, a: &Genome, b: &Genome, rng: &mut dyn RngCore) -> Genome {
+        match *self {
+            Crossover::Uniform => UniformCrossover.crossover(a, b, rng),
+            Crossover::Blend { alpha } => BlendCrossover { alpha }.crossover(a, b, rng),
+            Crossover::Arithmetic => ArithmeticCrossover.crossover(a, b, rng),
+            Crossover::SinglePoint => SinglePointCrossover.crossover(a, b, rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MUTATIONS: [Mutation; 3] = [
+        Mutation::Uniform,
+        Mutation::Gaussian,
+        Mutation::Polynomial { eta: 20.0 },
+    ];
+
+    const CROSSOVERS: [Crossover; 4] = [
+        Crossover::Uniform,
+        Crossover::Blend { alpha: 0.5 },
+        Crossover::Arithmetic,
+        Crossover::SinglePoint,
+    ];
+
+    #[test]
+    fn test_operators_keep_genomes_valid() {
+        let mut rng = rand::thread_rng();
+        for mutation in MUTATIONS {
+            for crossover in CROSSOVERS {
+                let mut a = Genome::new(1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.5);
+                let mut b = Genome::new(0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.5);
+                a.brain = Some(Brain::random(&mut rng));
+                for _ in 0..50 {
+                    let mut child = crossover.crossover(&a, &b, &mut rng);
+                    mutation.mutate(&mut child, 1.0, &mut rng);
+                    assert!(
+                        child.is_valid(),
+                        "{:?} {:?}: {:?}",
+                        mutation,
+                        crossover,
+                        child
+                    );
+                    let sum: f64 = child
+                        .values()
+                        .iter()
+                        .zip(GENES)
+                        .map(|(value, gene)| value * gene.budget_weight)
+                        .sum();
+                    assert!(sum <= 2.5 + 1e-9);
+                    assert!(child.brain.is_some());
+                    (a, b) = (b, child);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gaussian_steps_adapt_and_are_inherited() {
+        let mut rng = rand::thread_rng();
+        let mut genome = Genome::default();
+        GaussianMutation.mutate(&mut genome, 0.0, &mut rng);
+        assert_eq!(genome.mutation_steps, None);
+
+        GaussianMutation.mutate(&mut genome, 1.0, &mut rng);
+        let steps = genome.mutation_steps.unwrap();
+        assert!(steps
+            .iter()
+            .zip(GENES)
+            .all(|(step, gene)| *step != gene.mutation_step()));
+
+        let child = SinglePointCrossover.crossover(&genome, &Genome::default(), &mut rng);
+        assert_eq!(child.mutation_steps, Some(steps));
+        let round_trip: Genome =
+            serde_json::from_str(&serde_json::to_string(&child).unwrap()).unwrap();
+        let round_trip_steps = round_trip.mutation_steps.unwrap();
+        assert!(round_trip_steps
+            .iter()
+            .zip(steps)
+            .all(|(x, y)| (x - y).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_single_point_and_arithmetic_crossover() {
+        let mut rng = rand::thread_rng();
+        let a = Genome::new(0.9, 0.1, 0.9, 0.1, 0.4, 0.0, 0.5);
+        let b = Genome::new(0.1, 0.9, 0.1, 0.9, 0.4, 0.0, 0.5);
+        for _ in 0..20 {
+            // Rescaling to the budget keeps the ratios of the inherited genes
+            let child = SinglePointCrossover.crossover(&a, &b, &mut rng);
+            let ratio = child.strength / child.reproduction;
+            assert!((ratio - a.strength / b.reproduction).abs() < 1e-9);
+
+            // Both parents sum to the budget, so the mean needs no rescaling
+            let child = ArithmeticCrossover.crossover(&a, &b, &mut rng);
+            let weight = (child.strength - b.strength) / (a.strength - b.strength);
+            assert!((child.speed - (weight * a.speed + (1.0 - weight) * b.speed)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_operator_config() {
+        let mutation: Mutation = serde_json::from_str(r#"{"type": "polynomial"}"#).unwrap();
+        assert_eq!(mutation, Mutation::Polynomial { eta: 20.0 });
+        let crossover: Crossover =
+            serde_json::from_str(r#"{"type": "blend", "alpha": 0.3}"#).unwrap();
+        assert_eq!(crossover, Crossover::Blend { alpha: 0.3 });
+        assert_eq!(
+            serde_json::to_string(&Crossover::SinglePoint).unwrap(),
+            r#"{"type":"single_point"}"#
+        );
+
+        assert!(Mutation::Polynomial { eta: -1.0 }.validate().is_err());
+        assert!(Crossover::Blend { alpha: 1.5 }.validate().is_err());
+        assert!(Crossover::Arithmetic.validate().is_ok());
+    }
+}
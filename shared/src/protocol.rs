@@ -1,3 +1,9 @@
+use crate::combat::CombatConfig;
+use crate::migration::MigrationConfig;
+use crate::operators::{Crossover, Mutation};
+use crate::schedule::EnvironmentSchedule;
+use crate::terrain::TerrainConfig;
+use crate::topology::WorldTopology;
 use crate::Genome;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -13,6 +19,10 @@ pub struct WorkRequest {
 
     /// Client version string
     pub client_version: String,
+
+    /// Experiment the client wants to work on; the server picks one if None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<String>,
 }
 
 /// A genome paired with its lineage ID for tracking
@@ -44,6 +54,50 @@ pub struct WorkAssignment {
     /// Mutation rate (0.0 to 1.0)
     pub mutation_rate: f64,
 
+    /// Mutation operator applied to offspring
+    #[serde(default)]
+    pub mutation: Mutation,
+
+    /// Crossover operator combining the parents of offspring
+    #[serde(default)]
+    pub crossover: Crossover,
+
+    /// Give seed genomes without a brain a random one
+    #[serde(default)]
+    pub brains: bool,
+
+    /// Terrain of the island's world
+    #[serde(default)]
+    pub terrain: TerrainConfig,
+
+    /// What lies beyond the edges of the island's world
+    #[serde(default)]
+    pub world_topology: WorldTopology,
+
+    /// How creatures contest food and flee hunters
+    #[serde(default)]
+    pub combat: CombatConfig,
+
+    /// Changes to the environment over time
+    #[serde(default, skip_serializing_if = "EnvironmentSchedule::is_empty")]
+    pub schedule: EnvironmentSchedule,
+
+    /// Environment clock at the island's first step
+    #[serde(default)]
+    pub clock_start: u64,
+
+    /// Islands to run side by side and the migration between them
+    #[serde(default)]
+    pub migration: MigrationConfig,
+
+    /// Experiment this work belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<String>,
+
+    /// World parameters for the island; clients use their defaults if None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<IslandEnvironment>,
+
     // Legacy fields for backwards compatibility (deprecated)
     #[serde(default)]
     pub seed_genomes: Vec<Genome>,
@@ -55,6 +109,24 @@ pub struct WorkAssignment {
     pub population_size: usize,
 }
 
+/// World parameters for a spatial simulation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IslandEnvironment {
+    /// Fraction of tiles that start with a plant
+    pub plant_density: f64,
+
+    /// Fraction of tiles that start with food
+    pub food_density: f64,
+
+    /// Energy a creature needs before it can reproduce
+    pub reproduction_threshold: f64,
+
+    /// Farthest apart, in tiles, two creatures can be to mate; None lets
+    /// them mate anywhere on the island
+    #[serde(default)]
+    pub mating_radius: Option<f64>,
+}
+
 /// Survival statistics for a genome lineage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SurvivalResult {
@@ -80,6 +152,10 @@ pub struct WorkResult {
     /// Number of simulation steps completed
     pub steps_completed: u32,
 
+    /// Experiment named in the work assignment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<String>,
+
     // Legacy fields for backwards compatibility (deprecated)
     #[serde(default)]
     pub best_genomes: Vec<GenomeWithFitness>,
@@ -91,11 +167,75 @@ pub struct WorkResult {
     pub stats: Option<SimulationStats>,
 }
 
+/// Client -> Server: Low-rate snapshot of a running island, for spectators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorFrame {
+    /// Work assignment the island is running
+    pub work_id: Uuid,
+
+    pub client_id: Uuid,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<String>,
+
+    pub step: u32,
+    pub max_steps: u32,
+    pub world_width: usize,
+    pub world_height: usize,
+
+    /// Seed genome IDs; creatures refer to their lineage by index into this
+    pub lineages: Vec<Uuid>,
+
+    /// Living creatures as `[x, y, lineage]` triples
+    pub creatures: Vec<u32>,
+
+    /// Creatures alive, which may exceed those listed in `creatures`
+    pub population: usize,
+
+    /// Side of the square blocks of tiles food is summed over
+    pub food_cell: usize,
+
+    /// Food per block, row by row, capped at 255
+    pub food: Vec<u8>,
+
+    /// Terrain in the middle of each block, row by row, as indices into
+    /// `Terrain::ALL`; empty when the world is grassland everywhere
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub terrain: Vec<u8>,
+}
+
 /// A genome paired with its fitness score
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenomeWithFitness {
     pub genome: Genome,
+
+    /// Analytic score computed from the genome's traits
     pub fitness: f64,
+
+    /// Gene pool ID of this genome, or for genomes evolved on a client,
+    /// the ID of the seed genome whose lineage it descends from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genome_id: Option<Uuid>,
+
+    /// Fitness measured by the server from client survival results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empirical: Option<EmpiricalFitness>,
+}
+
+/// Fitness estimated from the outcomes of many island simulations
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EmpiricalFitness {
+    /// Number of island outcomes the estimate is based on
+    pub samples: u32,
+
+    /// Mean outcome score (0.0 to 1.0)
+    pub mean: f64,
+
+    /// Lower bound of the 95% confidence interval for the mean
+    pub ci_low: f64,
+
+    /// Upper bound of the 95% confidence interval for the mean
+    pub ci_high: f64,
 }
 
 /// Statistics about a simulation run
@@ -165,6 +305,7 @@ impl WorkRequest {
             client_id,
             protocol_version,
             client_version: env!("CARGO_PKG_VERSION").to_string(),
+            experiment: None,
         }
     }
 }
@@ -185,6 +326,17 @@ impl WorkAssignment {
             grid_height,
             max_steps,
             mutation_rate,
+            mutation: Mutation::default(),
+            crossover: Crossover::default(),
+            brains: false,
+            terrain: TerrainConfig::Flat,
+            world_topology: WorldTopology::Bounded,
+            combat: CombatConfig::default(),
+            schedule: EnvironmentSchedule::default(),
+            clock_start: 0,
+            migration: MigrationConfig::default(),
+            experiment: None,
+            environment: None,
             // Legacy fields
             seed_genomes: vec![],
             generations: 0,
@@ -206,6 +358,17 @@ impl WorkAssignment {
             grid_height: 0,
             max_steps: 0,
             mutation_rate,
+            mutation: Mutation::default(),
+            crossover: Crossover::default(),
+            brains: false,
+            terrain: TerrainConfig::Flat,
+            world_topology: WorldTopology::Bounded,
+            combat: CombatConfig::default(),
+            schedule: EnvironmentSchedule::default(),
+            clock_start: 0,
+            migration: MigrationConfig::default(),
+            experiment: None,
+            environment: None,
             seed_genomes,
             generations,
             population_size,
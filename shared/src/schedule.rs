@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::f64::consts::TAU;
+
+/// Change to an island's environment over time. Times are in steps of the
+/// environment clock, which carries on across an experiment's islands.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduleEvent {
+    /// Plant regrowth rising and falling in a sine wave, from
+    /// `1 - amplitude` to `1 + amplitude` times its usual rate
+    Seasons { period: u32, amplitude: f64 },
+
+    /// Plant regrowth slowed to `growth` times its usual rate for `duration`
+    /// steps from `start`, and again every `every` steps if set
+    Drought {
+        start: u64,
+        duration: u32,
+        #[serde(default)]
+        every: Option<u32>,
+        #[serde(default)]
+        growth: f64,
+    },
+
+    /// Food scattered over `density` of the tiles at `start`, and again
+    /// every `every` steps if set
+    Bloom {
+        start: u64,
+        #[serde(default)]
+        every: Option<u32>,
+        density: f64,
+    },
+
+    /// Plant regrowth changing steadily by `rate` times its usual rate every
+    /// 1000 steps, never below zero
+    Drift { rate: f64 },
+}
+
+impl ScheduleEvent {
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            ScheduleEvent::Seasons { period, amplitude } => {
+                if period == 0 {
+                    return Err("Season period must be positive".to_string());
+                }
+                if !(0.0..=1.0).contains(&amplitude) {
+                    return Err("Season amplitude must be between 0 and 1".to_string());
+                }
+            }
+            ScheduleEvent::Drought {
+                duration,
+                every,
+                growth,
+                ..
+            } => {
+                if every.is_some_and(|every| every == 0 || every < duration) {
+                    return Err("Drought interval must be at least its duration".to_string());
+                }
+                if !(growth.is_finite() && growth >= 0.0) {
+                    return Err("Drought growth must be a non-negative number".to_string());
+                }
+            }
+            ScheduleEvent::Bloom { every, density, .. } => {
+                if every == Some(0) {
+                    return Err("Bloom interval must be positive".to_string());
+                }
+                if !(0.0..=1.0).contains(&density) {
+                    return Err("Bloom density must be between 0 and 1".to_string());
+                }
+            }
+            ScheduleEvent::Drift { rate } => {
+                if !rate.is_finite() {
+                    return Err("Drift rate must be a number".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Factor on plant regrowth at a time
+    fn growth(&self, time: u64) -> f64 {
+        match *self {
+            ScheduleEvent::Seasons { period, amplitude } => {
+                let phase = (time % period as u64) as f64 / period as f64;
+                1.0 + amplitude * (TAU * phase).sin()
+            }
+            ScheduleEvent::Drought {
+                start,
+                duration,
+                every,
+                growth,
+            } if is_active(time, start, duration, every) => growth,
+            ScheduleEvent::Drift { rate } => (1.0 + rate * time as f64 / 1000.0).max(0.0),
+            _ => 1.0,
+        }
+    }
+
+    /// Fraction of tiles to scatter food over at a time
+    fn bloom(&self, time: u64) -> f64 {
+        match *self {
+            ScheduleEvent::Bloom {
+                start,
+                every,
+                density,
+            } if is_active(time, start, 1, every) => density,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Whether an event lasting `duration` steps from `start`, repeating every
+/// `every` steps if set, is under way at a time
+fn is_active(time: u64, start: u64, duration: u32, every: Option<u32>) -> bool {
+    let Some(elapsed) = time.checked_sub(start) else {
+        return false;
+    };
+    let elapsed = match every {
+        Some(every) => elapsed % every as u64,
+        None => elapsed,
+    };
+    elapsed < duration as u64
+}
+
+/// Changes to an island's environment over time, as configured and sent with
+/// work assignments. Without events the environment never changes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnvironmentSchedule {
+    pub events: Vec<ScheduleEvent>,
+}
+
+impl EnvironmentSchedule {
+    pub fn validate(&self) -> Result<(), String> {
+        self.events.iter().try_for_each(ScheduleEvent::validate)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Factor on plant regrowth at a time: the product of every event's
+    pub fn growth(&self, time: u64) -> f64 {
+        self.events.iter().map(|event| event.growth(time)).product()
+    }
+
+    /// Fraction of tiles to scatter food over at a time (at most all of them)
+    pub fn bloom(&self, time: u64) -> f64 {
+        self.events
+            .iter()
+            .map(|event| event.bloom(time))
+            .sum::<f64>()
+            .min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule() {
+        let schedule: EnvironmentSchedule = serde_json::from_str(
+            r#"[
+                {"type": "seasons", "period": 100, "amplitude": 0.5},
+                {"type": "drought", "start": 1000, "duration": 50, "every": 500},
+                {"type": "bloom", "start": 10, "density": 0.1}
+            ]"#,
+        )
+        .unwrap();
+        assert!(schedule.validate().is_ok());
+
+        assert!((schedule.growth(0) - 1.0).abs() < 1e-9);
+        assert!((schedule.growth(25) - 1.5).abs() < 1e-9);
+        assert!((schedule.growth(75) - 0.5).abs() < 1e-9);
+        // Droughts start at 1000 and recur every 500 steps
+        assert_eq!(schedule.growth(1010), 0.0);
+        assert_eq!(schedule.growth(1510), 0.0);
+        assert!(schedule.growth(1460) > 0.0);
+        assert!(schedule.growth(990) > 0.0);
+
+        assert_eq!(schedule.bloom(10), 0.1);
+        assert_eq!(schedule.bloom(11), 0.0);
+        assert_eq!(schedule.bloom(1010), 0.0);
+
+        let drift = EnvironmentSchedule {
+            events: vec![ScheduleEvent::Drift { rate: -0.5 }],
+        };
+        assert_eq!(drift.growth(1000), 0.5);
+        assert_eq!(drift.growth(5000), 0.0);
+
+        for event in [
+            ScheduleEvent::Seasons {
+                period: 0,
+                amplitude: 0.5,
+            },
+            ScheduleEvent::Drought {
+                start: 0,
+                duration: 10,
+                every: Some(5),
+                growth: 0.0,
+            },
+            ScheduleEvent::Bloom {
+                start: 0,
+                every: None,
+                density: 2.0,
+            },
+        ] {
+            assert!(event.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Ground under a tile, shaping how creatures move and how plants grow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Terrain {
+    #[default]
+    Grassland,
+    /// Rich soil: more plants that regrow faster
+    Fertile,
+    /// Impassable to creatures, with no plants or food
+    Water,
+    /// Slow to cross, with few plants that regrow slowly
+    Mountain,
+}
+
+impl Terrain {
+    pub const ALL: [Terrain; 4] = [
+        Terrain::Grassland,
+        Terrain::Fertile,
+        Terrain::Water,
+        Terrain::Mountain,
+    ];
+
+    /// Character standing for the terrain in text maps
+    pub fn symbol(self) -> char {
+        match self {
+            Terrain::Grassland => '.',
+            Terrain::Fertile => '*',
+            Terrain::Water => '~',
+            Terrain::Mountain => '^',
+        }
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.symbol() == symbol)
+    }
+
+    /// Colour of the terrain on the dashboard and in PNG maps
+    pub fn color(self) -> [u8; 3] {
+        match self {
+            Terrain::Grassland => [126, 200, 80],
+            Terrain::Fertile => [34, 110, 48],
+            Terrain::Water => [40, 90, 200],
+            Terrain::Mountain => [150, 140, 130],
+        }
+    }
+
+    /// Whether creatures can enter the tile
+    pub fn is_passable(self) -> bool {
+        self != Terrain::Water
+    }
+
+    /// How many attempts it takes on average to move onto the tile
+    pub fn movement_cost(self) -> f64 {
+        match self {
+            Terrain::Grassland | Terrain::Fertile => 1.0,
+            Terrain::Mountain => 3.0,
+            Terrain::Water => f64::INFINITY,
+        }
+    }
+
+    /// Plant density relative to grassland
+    pub fn fertility(self) -> f64 {
+        match self {
+            Terrain::Grassland => 1.0,
+            Terrain::Fertile => 2.5,
+            Terrain::Mountain => 0.3,
+            Terrain::Water => 0.0,
+        }
+    }
+
+    /// Ticks for a depleted plant to regrow one unit of food
+    pub fn regrowth_ticks(self) -> u32 {
+        match self {
+            Terrain::Grassland | Terrain::Water => 10,
+            Terrain::Fertile => 5,
+            Terrain::Mountain => 25,
+        }
+    }
+}
+
+/// Terrain of an island, as configured and sent with work assignments
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TerrainConfig {
+    /// Grassland everywhere
+    #[default]
+    Flat,
+
+    /// Landmasses from fractal noise, surrounded by water: low ground is
+    /// water, high ground mountains, and damp patches of land fertile
+    Islands {
+        /// Seed of the layout; every island gets a fresh layout if None
+        #[serde(default)]
+        seed: Option<u64>,
+        /// Size of the largest landforms, in tiles
+        #[serde(default = "default_scale")]
+        scale: f64,
+        /// Fraction of the elevation range below which the ground is water
+        #[serde(default = "default_water_level")]
+        water_level: f64,
+        /// Fraction of the elevation range above which the ground is mountain
+        #[serde(default = "default_mountain_level")]
+        mountain_level: f64,
+    },
+
+    /// Hand-authored map, one string per row and one symbol per tile (see
+    /// `Terrain::symbol`), stretched to the size of the world
+    Map { rows: Vec<String> },
+}
+
+fn default_scale() -> f64 {
+    60.0
+}
+
+fn default_water_level() -> f64 {
+    0.3
+}
+
+fn default_mountain_level() -> f64 {
+    0.75
+}
+
+/// Noise level above which land is fertile
+const FERTILE_MOISTURE: f64 = 0.62;
+
+/// How far the elevation is stretched away from the middle of the range
+const NOISE_CONTRAST: f64 = 1.5;
+
+/// How far the elevation drops towards the corners of the world
+const EDGE_DROP: f64 = 0.3;
+
+/// Octaves of noise summed into the elevation
+const NOISE_OCTAVES: u32 = 4;
+
+impl TerrainConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            TerrainConfig::Flat => Ok(()),
+            TerrainConfig::Islands {
+                scale,
+                water_level,
+                mountain_level,
+                ..
+            } => {
+                if !(scale.is_finite() && *scale >= 1.0) {
+                    return Err("Terrain scale must be at least 1".to_string());
+                }
+                if !(0.0..=1.0).contains(water_level) || !(0.0..=1.0).contains(mountain_level) {
+                    return Err("Terrain levels must be between 0 and 1".to_string());
+                }
+                if water_level > mountain_level {
+                    return Err("Terrain water_level must not exceed mountain_level".to_string());
+                }
+                Ok(())
+            }
+            TerrainConfig::Map { rows } => {
+                let width = rows.first().map_or(0, |row| row.chars().count());
+                if width == 0 {
+                    return Err("Terrain map must not be empty".to_string());
+                }
+                let mut passable = false;
+                for (y, row) in rows.iter().enumerate() {
+                    if row.chars().count() != width {
+                        return Err(format!("Terrain map row {} is not {} tiles wide", y, width));
+                    }
+                    for symbol in row.chars() {
+                        let terrain = Terrain::from_symbol(symbol).ok_or_else(|| {
+                            format!("Unknown terrain symbol '{}' in map row {}", symbol, y)
+                        })?;
+                        passable |= terrain.is_passable();
+                    }
+                }
+                if !passable {
+                    return Err("Terrain map has no land".to_string());
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Terrain of a world of the given size, row by row
+    pub fn generate(&self, width: usize, height: usize, rng: &mut dyn RngCore) -> Vec<Terrain> {
+        match self {
+            TerrainConfig::Flat => vec![Terrain::Grassland; width * height],
+            TerrainConfig::Islands {
+                seed,
+                scale,
+                water_level,
+                mountain_level,
+            } => {
+                let seed = seed.unwrap_or_else(|| rng.gen());
+                let elevation = ValueNoise::new(seed);
+                let moisture = ValueNoise::new(seed.wrapping_add(1));
+                let mut terrain = Vec::with_capacity(width * height);
+                for y in 0..height {
+                    for x in 0..width {
+                        let (fx, fy) = (x as f64 / scale, y as f64 / scale);
+                        // Sink the edges so land gathers into islands
+                        let dx = 2.0 * x as f64 / width as f64 - 1.0;
+                        let dy = 2.0 * y as f64 / height as f64 - 1.0;
+                        let falloff = (dx * dx + dy * dy) / 2.0;
+                        let noise = elevation.fractal(fx, fy);
+                        // Stretch the noise, which clusters around the middle
+                        let level = (0.5 + NOISE_CONTRAST * (noise - 0.5) - EDGE_DROP * falloff)
+                            .clamp(0.0, 1.0);
+
+                        terrain.push(if level < *water_level {
+                            Terrain::Water
+                        } else if level > *mountain_level {
+                            Terrain::Mountain
+                        } else if moisture.fractal(fx, fy) > FERTILE_MOISTURE {
+                            Terrain::Fertile
+                        } else {
+                            Terrain::Grassland
+                        });
+                    }
+                }
+                terrain
+            }
+            TerrainConfig::Map { rows } => {
+                let rows: Vec<Vec<Terrain>> = rows
+                    .iter()
+                    .map(|row| {
+                        row.chars()
+                            .map(|symbol| Terrain::from_symbol(symbol).unwrap_or_default())
+                            .collect()
+                    })
+                    .collect();
+                let mut terrain = Vec::with_capacity(width * height);
+                for y in 0..height {
+                    let row = &rows[y * rows.len() / height];
+                    for x in 0..width {
+                        terrain.push(row[x * row.len() / width]);
+                    }
+                }
+                terrain
+            }
+        }
+    }
+}
+
+/// Smoothly interpolated random values on a lattice
+struct ValueNoise {
+    seed: u64,
+}
+
+impl ValueNoise {
+    fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Random value from 0 to 1 at a lattice point
+    fn lattice(&self, x: i64, y: i64) -> f64 {
+        // SplitMix64 finalizer over the seed and coordinates
+        let mut h = self.seed
+            ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        h = (h ^ (h >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        h = (h ^ (h >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        h ^= h >> 31;
+        (h >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn sample(&self, x: f64, y: f64) -> f64 {
+        let (x0, y0) = (x.floor(), y.floor());
+        let smooth = |t: f64| t * t * (3.0 - 2.0 * t);
+        let (tx, ty) = (smooth(x - x0), smooth(y - y0));
+        let (ix, iy) = (x0 as i64, y0 as i64);
+
+        let top = self.lattice(ix, iy) * (1.0 - tx) + self.lattice(ix + 1, iy) * tx;
+        let bottom = self.lattice(ix, iy + 1) * (1.0 - tx) + self.lattice(ix + 1, iy + 1) * tx;
+        top * (1.0 - ty) + bottom * ty
+    }
+
+    /// Octaves of noise at doubling frequencies and halving amplitudes,
+    /// normalized to 0 to 1
+    fn fractal(&self, x: f64, y: f64) -> f64 {
+        let (mut sum, mut total, mut amplitude, mut frequency) = (0.0, 0.0, 1.0, 1.0);
+        for octave in 0..NOISE_OCTAVES {
+            // Offset each octave so their lattices don't line up
+            let offset = octave as f64 * 17.31;
+            sum += amplitude * self.sample(x * frequency + offset, y * frequency + offset);
+            total += amplitude;
+            amplitude /= 2.0;
+            frequency *= 2.0;
+        }
+        sum / total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_islands_are_seeded() {
+        let config = TerrainConfig::Islands {
+            seed: Some(7),
+            scale: default_scale(),
+            water_level: default_water_level(),
+            mountain_level: default_mountain_level(),
+        };
+        assert!(config.validate().is_ok());
+
+        let mut rng = rand::thread_rng();
+        let terrain = config.generate(120, 80, &mut rng);
+        assert_eq!(terrain.len(), 120 * 80);
+        assert_eq!(terrain, config.generate(120, 80, &mut rng));
+        for kind in [Terrain::Water, Terrain::Grassland] {
+            assert!(terrain.contains(&kind), "no {:?}", kind);
+        }
+        // Edges sink below the water more than the middle does
+        let water = |y: usize| {
+            (0..120)
+                .filter(|&x| terrain[y * 120 + x] == Terrain::Water)
+                .count()
+        };
+        assert!(water(0) >= water(40));
+    }
+
+    #[test]
+    fn test_map_is_stretched() {
+        let config = TerrainConfig::Map {
+            rows: vec!["~.".to_string(), "^*".to_string()],
+        };
+        assert!(config.validate().is_ok());
+        let terrain = config.generate(4, 4, &mut rand::thread_rng());
+        assert_eq!(
+            &terrain[..4],
+            &[
+                Terrain::Water,
+                Terrain::Water,
+                Terrain::Grassland,
+                Terrain::Grassland
+            ]
+        );
+        assert_eq!(terrain[15], Terrain::Fertile);
+        assert_eq!(terrain[8], Terrain::Mountain);
+
+        for rows in [
+            vec![],
+            vec!["..".to_string(), ".".to_string()],
+            vec!["x".to_string()],
+            vec!["~~".to_string()],
+        ] {
+            assert!(TerrainConfig::Map { rows }.validate().is_err());
+        }
+    }
+}
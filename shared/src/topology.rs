@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+/// What lies beyond the edges of an island's world
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldTopology {
+    /// Moves off the edge fail, leaving the creature where it was
+    #[default]
+    Bounded,
+
+    /// Each edge wraps around to the opposite one
+    Torus,
+
+    /// Moves off the edge bounce back off it
+    Walled,
+}
+
+impl WorldTopology {
+    /// Whether the edges wrap around
+    pub fn wraps(self) -> bool {
+        self == WorldTopology::Torus
+    }
+
+    /// Coordinate `delta` tiles from `from` along an axis of `size` tiles;
+    /// None if that is off a bounded edge
+    pub fn step(self, from: usize, delta: i64, size: usize) -> Option<usize> {
+        let size = size as i64;
+        let to = from as i64 + delta;
+        let to = match self {
+            WorldTopology::Bounded if !(0..size).contains(&to) => return None,
+            WorldTopology::Bounded => to,
+            WorldTopology::Torus => to.rem_euclid(size),
+            WorldTopology::Walled if to < 0 => -to,
+            WorldTopology::Walled if to >= size => 2 * (size - 1) - to,
+            WorldTopology::Walled => to,
+        };
+        // Only a world a tile wide bounces off both walls at once
+        Some(to.clamp(0, size - 1) as usize)
+    }
+
+    /// Signed distance from `from` to `to` along an axis of `size` tiles,
+    /// the short way round on a torus
+    pub fn offset(self, from: usize, to: usize, size: usize) -> i64 {
+        let offset = to as i64 - from as i64;
+        let size = size as i64;
+        if self.wraps() && offset.abs() * 2 > size {
+            offset - offset.signum() * size
+        } else {
+            offset
+        }
+    }
+
+    /// Coordinates within `radius` tiles of `center` along an axis of `size`
+    /// tiles, each once, nearest the lower edge of the range first
+    pub fn range(self, center: usize, radius: usize, size: usize) -> Vec<usize> {
+        if self.wraps() {
+            let span = (2 * radius + 1).min(size);
+            let start = center as i64 - radius as i64;
+            (0..span)
+                .map(|i| (start + i as i64).rem_euclid(size as i64) as usize)
+                .collect()
+        } else {
+            (center.saturating_sub(radius)..=(center + radius).min(size - 1)).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edges() {
+        use WorldTopology::*;
+
+        assert_eq!(Bounded.step(0, -1, 10), None);
+        assert_eq!(Bounded.step(9, 1, 10), None);
+        assert_eq!(Bounded.step(4, 1, 10), Some(5));
+        assert_eq!(Torus.step(0, -1, 10), Some(9));
+        assert_eq!(Torus.step(9, 1, 10), Some(0));
+        assert_eq!(Walled.step(0, -1, 10), Some(1));
+        assert_eq!(Walled.step(9, 1, 10), Some(8));
+        assert_eq!(Walled.step(0, 1, 1), Some(0));
+
+        assert_eq!(Bounded.offset(1, 9, 10), 8);
+        assert_eq!(Walled.offset(9, 1, 10), -8);
+        assert_eq!(Torus.offset(1, 9, 10), -2);
+        assert_eq!(Torus.offset(9, 1, 10), 2);
+        assert_eq!(Torus.offset(2, 5, 10), 3);
+
+        assert_eq!(Bounded.range(1, 2, 10), vec![0, 1, 2, 3]);
+        assert_eq!(Torus.range(1, 2, 10), vec![9, 0, 1, 2, 3]);
+        assert_eq!(Torus.range(1, 5, 4), vec![0, 1, 2, 3]);
+
+        let topology: WorldTopology = serde_json::from_str(r#""torus""#).unwrap();
+        assert_eq!(topology, Torus);
+    }
+}
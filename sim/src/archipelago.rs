@@ -0,0 +1,243 @@
+use crate::{DamageTaken, Island, IslandConfig, SurvivalStats};
+use rand::Rng;
+use shared::{GenomeWithFitness, MigrationConfig, SpectatorFrame};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Several islands run side by side from the same seed genomes, with
+/// creatures migrating between them every `interval` steps
+pub struct Archipelago {
+    pub islands: Vec<Island>,
+    pub migration: MigrationConfig,
+    pub step: u32,
+    max_steps: u32,
+}
+
+impl Archipelago {
+    /// Create `migration.islands` islands, each seeded with every genome
+    pub fn new(
+        config: IslandConfig,
+        migration: MigrationConfig,
+        seed_genomes: Vec<(Uuid, shared::Genome)>,
+    ) -> Self {
+        let max_steps = config.max_steps;
+        let islands = (0..migration.islands.max(1))
+            .map(|_| Island::new(config.clone(), seed_genomes.clone()))
+            .collect();
+        Self {
+            islands,
+            migration,
+            step: 0,
+            max_steps,
+        }
+    }
+
+    /// Run the complete simulation
+    pub fn run_simulation(&mut self) -> Vec<SurvivalStats> {
+        self.run_simulation_observed(|_| {})
+    }
+
+    /// Run the complete simulation, calling `observer` after every step
+    pub fn run_simulation_observed(
+        &mut self,
+        mut observer: impl FnMut(&Self),
+    ) -> Vec<SurvivalStats> {
+        let mut rng = rand::thread_rng();
+
+        while !self.is_finished() {
+            self.tick(&mut rng);
+            observer(self);
+        }
+
+        self.collect_survival_stats()
+    }
+
+    /// Whether the step budget is used up or a single lineage is left across
+    /// all islands
+    pub fn is_finished(&self) -> bool {
+        if self.step >= self.max_steps {
+            return true;
+        }
+        let lineages: HashSet<Uuid> = self
+            .islands
+            .iter()
+            .flat_map(|island| island.creatures.iter().map(|c| c.genome_id))
+            .collect();
+        lineages.len() <= 1
+    }
+
+    /// Advance every island by one step, then migrate if it is time to
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn tick<R: Rng>(&mut self, rng: &mut R) {
+        for island in &mut self.islands {
+            island.tick(rng);
+        }
+        self.step += 1;
+
+        if self.migration.is_archipelago()
+            && self.migration.interval > 0
+            && self.step % self.migration.interval == 0
+        {
+            self.migrate(rng);
+        }
+    }
+
+    /// Move `rate` of each island's creatures to islands its topology links
+    /// it to. Everyone leaves before anyone arrives, so no creature moves twice.
+    pub fn migrate<R: Rng>(&mut self, rng: &mut R) {
+        let count = self.islands.len();
+        let mut arrivals: Vec<Vec<_>> = vec![Vec::new(); count];
+
+        for source in 0..count {
+            let destinations = self.migration.topology.destinations(source, count, rng);
+            if destinations.is_empty() {
+                continue;
+            }
+            let island = &mut self.islands[source];
+            let leaving = (island.creatures.len() as f64 * self.migration.rate).round() as usize;
+            for migrant in island.emigrate(leaving, rng) {
+                let destination = destinations[rng.gen_range(0..destinations.len())];
+                arrivals[destination].push(migrant);
+            }
+        }
+
+        for (island, migrants) in self.islands.iter_mut().zip(arrivals) {
+            island.immigrate(migrants, rng);
+        }
+    }
+
+    /// Survival statistics of each seed genome, summed over the islands
+    pub fn collect_survival_stats(&self) -> Vec<SurvivalStats> {
+        let mut totals: HashMap<Uuid, SurvivalStats> = HashMap::new();
+        for stats in self
+            .islands
+            .iter()
+            .flat_map(|island| island.collect_survival_stats())
+        {
+            let total = totals.entry(stats.genome_id).or_insert(SurvivalStats {
+                genome_id: stats.genome_id,
+                survived: 0,
+                total_spawned: 0,
+                total_food_eaten: 0,
+            });
+            total.survived += stats.survived;
+            total.total_spawned += stats.total_spawned;
+            total.total_food_eaten += stats.total_food_eaten;
+        }
+        totals.into_values().collect()
+    }
+
+    /// The fittest `n` creatures across all islands
+    pub fn get_best_genomes(&self, n: usize) -> Vec<GenomeWithFitness> {
+        let mut best: Vec<GenomeWithFitness> = self
+            .islands
+            .iter()
+            .flat_map(|island| island.get_best_genomes(n))
+            .collect();
+        best.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+        best.truncate(n);
+        best
+    }
+
+    /// Snapshot of the first island for spectators
+    pub fn spectator_frame(&self, work_id: Uuid, client_id: Uuid) -> SpectatorFrame {
+        self.islands[0].spectator_frame(work_id, client_id)
+    }
+
+    /// Health lost on all islands, by cause
+    pub fn damage_taken(&self) -> DamageTaken {
+        let mut damage = DamageTaken::default();
+        for island in &self.islands {
+            damage.merge(&island.damage_taken());
+        }
+        damage
+    }
+
+    /// Creatures alive on all islands
+    pub fn population(&self) -> usize {
+        self.islands
+            .iter()
+            .map(|island| island.creatures.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{Genome, Topology};
+
+    fn seeds(count: usize) -> Vec<(Uuid, Genome)> {
+        (0..count)
+            .map(|_| (Uuid::new_v4(), Genome::random()))
+            .collect()
+    }
+
+    #[test]
+    fn test_migration_moves_creatures() {
+        let config = IslandConfig {
+            world_width: 40,
+            world_height: 40,
+            max_steps: 100,
+            ..Default::default()
+        };
+        let migration = MigrationConfig {
+            islands: 3,
+            topology: Topology::Ring,
+            rate: 0.5,
+            interval: 1,
+        };
+        let mut archipelago = Archipelago::new(config, migration, seeds(4));
+        assert_eq!(archipelago.islands.len(), 3);
+        assert_eq!(archipelago.population(), 12);
+
+        // Mark each creature with the island it starts on
+        for (i, island) in archipelago.islands.iter_mut().enumerate() {
+            for creature in &mut island.creatures {
+                creature.food_eaten = 1000 + i as u32;
+            }
+        }
+        let mut rng = rand::thread_rng();
+        let population = archipelago.population();
+        archipelago.migrate(&mut rng);
+        assert_eq!(archipelago.population(), population);
+        // On a ring, island 1 only receives from island 0
+        assert!(archipelago.islands[1]
+            .creatures
+            .iter()
+            .all(|c| c.food_eaten == 1000 || c.food_eaten == 1001));
+        assert!(archipelago.islands[1]
+            .creatures
+            .iter()
+            .any(|c| c.food_eaten == 1000));
+    }
+
+    #[test]
+    fn test_archipelago_reports_each_seed() {
+        let config = IslandConfig {
+            world_width: 40,
+            world_height: 40,
+            max_steps: 60,
+            ..Default::default()
+        };
+        let migration = MigrationConfig {
+            islands: 4,
+            topology: Topology::Random,
+            rate: 0.2,
+            interval: 10,
+        };
+        let seeds = seeds(3);
+        let mut archipelago = Archipelago::new(config, migration, seeds.clone());
+        let stats = archipelago.run_simulation();
+
+        assert!(archipelago.step <= 60);
+        assert_eq!(stats.len(), 3);
+        for (genome_id, _) in &seeds {
+            let stats = stats.iter().find(|s| s.genome_id == *genome_id).unwrap();
+            // Every island spawned each seed once
+            assert!(stats.total_spawned >= 4);
+            assert!(stats.survived <= stats.total_spawned);
+        }
+        assert!(archipelago.get_best_genomes(5).len() <= 5);
+    }
+}
@@ -1,7 +1,29 @@
+use crate::{IslandConfig, World};
+use rand::seq::SliceRandom;
 use rand::Rng;
-use shared::Genome;
+use shared::brain::BRAIN_HIDDEN;
+use shared::{CrossoverOperator, Genome, MutationOperator};
 use uuid::Uuid;
 
+/// Diet from which a creature hunts and scavenges instead of eating plants
+const CARNIVORE_DIET: f64 = 0.5;
+
+/// Energy per unit of meat for a pure carnivore; meat is richer than plants
+const MEAT_ENERGY: f64 = 2.0;
+
+/// Damage a pure carnivore of combat power 1 deals its prey per attack
+const HUNT_DAMAGE: f64 = 40.0;
+
+/// Damage prey of combat power 1 deals back to its attacker
+const RETALIATION_DAMAGE: f64 = 10.0;
+
+/// How sharply a creature that favours similar partners turns away unlike ones
+const CHOOSINESS: f64 = 3.0;
+
+/// Meat left by a dead creature, plus `CARCASS_SIZE_MEAT` for its size
+const CARCASS_BASE_MEAT: f64 = 20.0;
+const CARCASS_SIZE_MEAT: f64 = 30.0;
+
 #[derive(Debug, Clone)]
 pub struct Creature {
     pub id: Uuid,
@@ -12,6 +34,51 @@ pub struct Creature {
     pub x: usize,
     pub y: usize,
     pub food_eaten: u32,
+
+    /// Hidden neuron values of the creature's brain from its last step
+    pub memory: [f64; BRAIN_HIDDEN],
+
+    /// Health lost over the creature's life, by cause
+    pub damage_taken: DamageTaken,
+}
+
+/// What hurt a creature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageCause {
+    /// Fighting rivals over food
+    Fight,
+    /// Attacked by a hunter
+    Predation,
+    /// Hurt by prey fighting back
+    Retaliation,
+}
+
+/// Health lost to each cause
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DamageTaken {
+    pub fight: f64,
+    pub predation: f64,
+    pub retaliation: f64,
+}
+
+impl DamageTaken {
+    pub fn add(&mut self, cause: DamageCause, amount: f64) {
+        match cause {
+            DamageCause::Fight => self.fight += amount,
+            DamageCause::Predation => self.predation += amount,
+            DamageCause::Retaliation => self.retaliation += amount,
+        }
+    }
+
+    pub fn merge(&mut self, other: &DamageTaken) {
+        self.fight += other.fight;
+        self.predation += other.predation;
+        self.retaliation += other.retaliation;
+    }
+
+    pub fn total(&self) -> f64 {
+        self.fight + self.predation + self.retaliation
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -26,6 +93,67 @@ pub enum Direction {
     SouthWest,
 }
 
+impl Direction {
+    /// Direction of a step of -1, 0 or 1 along each axis; None for no step
+    fn from_offset(dx: i32, dy: i32) -> Option<Direction> {
+        match (dx, dy) {
+            (0, -1) => Some(Direction::North),
+            (0, 1) => Some(Direction::South),
+            (1, 0) => Some(Direction::East),
+            (-1, 0) => Some(Direction::West),
+            (1, -1) => Some(Direction::NorthEast),
+            (-1, -1) => Some(Direction::NorthWest),
+            (1, 1) => Some(Direction::SouthEast),
+            (-1, 1) => Some(Direction::SouthWest),
+            _ => None,
+        }
+    }
+
+    /// The direction closest to a vector; None for the zero vector
+    pub fn from_vector(dx: f64, dy: f64) -> Option<Direction> {
+        let larger = dx.abs().max(dy.abs());
+        if larger <= 0.0 {
+            return None;
+        }
+        // Components much smaller than the other don't count as a step
+        let step = |v: f64| {
+            if v.abs() >= larger / 2.0 {
+                v.signum() as i32
+            } else {
+                0
+            }
+        };
+        Self::from_offset(step(dx), step(dy))
+    }
+
+    /// Step along each axis, y growing southwards
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+            Direction::NorthEast => (1, -1),
+            Direction::NorthWest => (-1, -1),
+            Direction::SouthEast => (1, 1),
+            Direction::SouthWest => (-1, 1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::NorthWest => Direction::SouthEast,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::SouthWest => Direction::NorthEast,
+        }
+    }
+}
+
 impl Creature {
     /// Create a new creature with the given genome at a position
     pub fn new(genome: Genome, genome_id: Uuid, x: usize, y: usize) -> Self {
@@ -38,6 +166,8 @@ impl Creature {
             x,
             y,
             food_eaten: 0,
+            memory: [0.0; BRAIN_HIDDEN],
+            damage_taken: DamageTaken::default(),
         }
     }
 
@@ -52,14 +182,20 @@ impl Creature {
         self.energy += amount;
     }
 
+    /// Spend energy on an effort such as a fight, down to none left
+    pub fn spend_energy(&mut self, amount: f64) {
+        self.energy = (self.energy - amount).max(0.0);
+    }
+
     /// Check if creature is dead
     pub fn is_dead(&self) -> bool {
         self.health <= 0.0
     }
 
-    /// Take damage (reduces health)
-    pub fn take_damage(&mut self, amount: f64) {
+    /// Take damage (reduces health), recording its cause
+    pub fn take_damage(&mut self, amount: f64, cause: DamageCause) {
         self.health -= amount;
+        self.damage_taken.add(cause, amount);
     }
 
     /// Check if creature can reproduce
@@ -68,8 +204,14 @@ impl Creature {
     }
 
     /// Reproduce with another creature, consuming energy
-    /// Child spawns at the average position of the two parents
-    pub fn reproduce(&mut self, other: &mut Creature, mutation_rate: f64) -> Option<Creature> {
+    /// Child spawns on land next to or at the point halfway between the
+    /// parents, with a genome from the island's crossover and mutation operators
+    pub fn reproduce(
+        &mut self,
+        other: &mut Creature,
+        config: &IslandConfig,
+        world: &World,
+    ) -> Option<Creature> {
         if !self.can_reproduce(60.0) || !other.can_reproduce(60.0) {
             return None;
         }
@@ -79,16 +221,18 @@ impl Creature {
         self.energy -= cost;
         other.energy -= cost;
 
-        let mut child_genome = self.genome.crossover(&other.genome);
-        child_genome.mutate(mutation_rate);
+        let mut rng = rand::thread_rng();
+        let mut child_genome = config
+            .crossover
+            .crossover(&self.genome, &other.genome, &mut rng);
+        config
+            .mutation
+            .mutate(&mut child_genome, config.mutation_rate, &mut rng);
 
         // Child inherits genome_id from one of the parents (for lineage tracking)
         let child_genome_id = self.genome_id;
 
-        // Spawn at average position
-        let child_x = (self.x + other.x) / 2;
-        let child_y = (self.y + other.y) / 2;
-
+        let (child_x, child_y) = self.birthplace(other, world, &mut rng);
         Some(Creature::new(
             child_genome,
             child_genome_id,
@@ -97,11 +241,76 @@ impl Creature {
         ))
     }
 
+    /// A random land tile at or next to the point halfway to a partner (the
+    /// short way round on a torus), or this creature's own tile if there is
+    /// no land there
+    fn birthplace<R: Rng>(&self, partner: &Creature, world: &World, rng: &mut R) -> (usize, usize) {
+        let (dx, dy) = world.offset((self.x, self.y), (partner.x, partner.y));
+        let Some((mid_x, mid_y)) = world.step(self.x, self.y, dx / 2, dy / 2) else {
+            return (self.x, self.y);
+        };
+        let spots: Vec<(usize, usize)> = (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter_map(|(dx, dy)| world.step(mid_x, mid_y, dx, dy))
+            .filter(|&(x, y)| world.terrain_at(x, y).is_passable())
+            .collect();
+        spots.choose(rng).copied().unwrap_or((self.x, self.y))
+    }
+
+    /// Chance that the creature accepts a partner: a mate preference below
+    /// 0.5 turns away partners unlike it, above 0.5 weak ones, the more
+    /// firmly the further the preference is from 0.5
+    pub fn mate_appeal(&self, partner: &Creature) -> f64 {
+        let bias = 2.0 * self.genome.mate_preference - 1.0;
+        let appeal = if bias < 0.0 {
+            1.0 + bias * CHOOSINESS * (1.0 - self.genome.similarity(&partner.genome))
+        } else {
+            1.0 - bias * (1.0 - partner.genome.strength)
+        };
+        if appeal.is_nan() {
+            0.0
+        } else {
+            appeal.clamp(0.0, 1.0)
+        }
+    }
+
     /// Calculate fitness score
     pub fn fitness(&self) -> f64 {
         self.genome.fitness_score()
     }
 
+    /// Whether the creature hunts and scavenges rather than eating plants
+    pub fn is_carnivore(&self) -> bool {
+        self.genome.diet >= CARNIVORE_DIET
+    }
+
+    /// Energy from eating plant food or meat: the further the diet leans
+    /// towards a food, the more of it the creature digests
+    pub fn digest(&self, amount: u32, meat: bool) -> f64 {
+        if meat {
+            amount as f64 * MEAT_ENERGY * self.genome.diet
+        } else {
+            amount as f64 * (1.0 - self.genome.diet)
+        }
+    }
+
+    /// Meat the creature leaves when it dies
+    pub fn carcass_meat(&self) -> u32 {
+        (CARCASS_BASE_MEAT + self.genome.size * CARCASS_SIZE_MEAT) as u32
+    }
+
+    /// Attack another creature: the prey takes damage growing with the
+    /// hunter's combat power and diet and shrinking with its own combat
+    /// power, and fights back in proportion to its combat power
+    pub fn hunt(&mut self, prey: &mut Creature) {
+        let defence = prey.combat_power();
+        prey.take_damage(
+            HUNT_DAMAGE * self.combat_power() * self.genome.diet / (1.0 + defence),
+            DamageCause::Predation,
+        );
+        self.take_damage(RETALIATION_DAMAGE * defence, DamageCause::Retaliation);
+    }
+
     /// Get combat power (for resource competition)
     pub fn combat_power(&self) -> f64 {
         self.genome.strength + self.genome.size * 0.5
@@ -125,12 +334,11 @@ impl Creature {
     }
 
     /// Attempt to move in a direction
-    /// Returns new position if successful, None if failed or out of bounds
+    /// Returns new position if successful, None if failed or off a bounded edge
     pub fn try_move<R: Rng>(
         &self,
         direction: Direction,
-        world_width: usize,
-        world_height: usize,
+        world: &World,
         rng: &mut R,
     ) -> Option<(usize, usize)> {
         // Check if movement succeeds
@@ -138,57 +346,29 @@ impl Creature {
             return None;
         }
 
-        let (dx, dy) = match direction {
-            Direction::North => (0, -1),
-            Direction::South => (0, 1),
-            Direction::East => (1, 0),
-            Direction::West => (-1, 0),
-            Direction::NorthEast => (1, -1),
-            Direction::NorthWest => (-1, -1),
-            Direction::SouthEast => (1, 1),
-            Direction::SouthWest => (-1, 1),
-        };
-
-        let new_x = self.x as i32 + dx;
-        let new_y = self.y as i32 + dy;
-
-        // Check bounds
-        if new_x >= 0 && new_x < world_width as i32 && new_y >= 0 && new_y < world_height as i32 {
-            Some((new_x as usize, new_y as usize))
-        } else {
-            None
-        }
+        let (dx, dy) = direction.offset();
+        world.step(self.x, self.y, dx, dy)
     }
 
-    /// Find best direction to move towards a target
-    pub fn direction_to(&self, target_x: usize, target_y: usize) -> Direction {
-        let dx = target_x as i32 - self.x as i32;
-        let dy = target_y as i32 - self.y as i32;
-
-        match (dx.signum(), dy.signum()) {
-            (0, -1) => Direction::North,
-            (0, 1) => Direction::South,
-            (1, 0) => Direction::East,
-            (-1, 0) => Direction::West,
-            (1, -1) => Direction::NorthEast,
-            (-1, -1) => Direction::NorthWest,
-            (1, 1) => Direction::SouthEast,
-            (-1, 1) => Direction::SouthWest,
-            _ => Direction::North, // Already at target or edge case
-        }
+    /// Find best direction to move towards a target, the short way round on
+    /// a torus
+    pub fn direction_to(&self, target_x: usize, target_y: usize, world: &World) -> Direction {
+        let (dx, dy) = world.offset((self.x, self.y), (target_x, target_y));
+
+        // Already at target: any direction
+        Direction::from_offset(dx.signum() as i32, dy.signum() as i32).unwrap_or(Direction::North)
     }
 
-    /// Calculate distance to a point
-    pub fn distance_to(&self, x: usize, y: usize) -> f64 {
-        let dx = x as f64 - self.x as f64;
-        let dy = y as f64 - self.y as f64;
-        (dx * dx + dy * dy).sqrt()
+    /// Calculate distance to a point, the short way round on a torus
+    pub fn distance_to(&self, x: usize, y: usize, world: &World) -> f64 {
+        world.distance((self.x, self.y), (x, y))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared::operators::{Crossover, Mutation};
 
     #[test]
     fn test_creature_creation() {
@@ -224,6 +404,54 @@ mod tests {
         assert!(creature.is_dead());
     }
 
+    #[test]
+    fn test_diet() {
+        let genome_id = Uuid::new_v4();
+        let herbivore = Creature::new(Genome::default(), genome_id, 10, 10);
+        let carnivore = Creature::new(
+            Genome {
+                diet: 1.0,
+                ..Default::default()
+            },
+            genome_id,
+            10,
+            10,
+        );
+
+        assert!(!herbivore.is_carnivore());
+        assert!(carnivore.is_carnivore());
+        assert_eq!(herbivore.digest(10, false), 10.0);
+        assert_eq!(herbivore.digest(10, true), 0.0);
+        assert_eq!(carnivore.digest(10, false), 0.0);
+        assert_eq!(carnivore.digest(10, true), 10.0 * MEAT_ENERGY);
+    }
+
+    #[test]
+    fn test_hunt() {
+        let genome = Genome {
+            strength: 1.0,
+            diet: 1.0,
+            ..Default::default()
+        };
+        let mut hunter = Creature::new(genome, Uuid::new_v4(), 10, 10);
+        let mut prey = Creature::new(Genome::default(), Uuid::new_v4(), 10, 10);
+
+        hunter.hunt(&mut prey);
+        assert!(prey.health < 100.0);
+        assert!(hunter.health < 100.0);
+        // The stronger hunter deals more than it takes
+        assert!(100.0 - prey.health > 100.0 - hunter.health);
+        assert!((prey.damage_taken.predation - (100.0 - prey.health)).abs() < 1e-9);
+        assert!((hunter.damage_taken.retaliation - (100.0 - hunter.health)).abs() < 1e-9);
+        assert_eq!(hunter.damage_taken.fight, 0.0);
+
+        // A plant eater barely hurts its prey
+        let mut grazer = Creature::new(Genome::default(), Uuid::new_v4(), 10, 10);
+        let mut target = Creature::new(Genome::default(), Uuid::new_v4(), 10, 10);
+        grazer.hunt(&mut target);
+        assert_eq!(target.health, 100.0);
+    }
+
     #[test]
     fn test_reproduction() {
         let genome = Genome::default();
@@ -234,11 +462,77 @@ mod tests {
         parent1.energy = 150.0;
         parent2.energy = 150.0;
 
-        let child = parent1.reproduce(&mut parent2, 0.1);
+        let config = IslandConfig {
+            mutation_rate: 0.1,
+            ..Default::default()
+        };
+        let world = World::new(20, 20);
+        let child = parent1.reproduce(&mut parent2, &config, &world).unwrap();
 
-        assert!(child.is_some());
         assert!(parent1.energy < 150.0);
         assert!(parent2.energy < 150.0);
+        // Born next to the point halfway between the parents
+        assert!(child.x.abs_diff(12) <= 1 && child.y.abs_diff(12) <= 1);
+
+        // ...which is across the edge on a torus
+        let mut world = World::new(20, 20);
+        world.set_topology(shared::WorldTopology::Torus);
+        parent1.x = 1;
+        parent2.x = 17;
+        parent1.energy = 150.0;
+        parent2.energy = 150.0;
+        let child = parent1.reproduce(&mut parent2, &config, &world).unwrap();
+        assert!([18, 19, 0].contains(&child.x), "{}", child.x);
+    }
+
+    #[test]
+    fn test_mate_appeal() {
+        let strong = Genome::new(1.0, 0.5, 0.5, 0.5, 0.0, 0.0, 0.5);
+        let weak = Genome::new(0.0, 0.5, 0.5, 0.5, 1.0, 0.0, 0.5);
+        let creature = |genome: &Genome, preference: f64| {
+            let genome = Genome {
+                mate_preference: preference,
+                ..genome.clone()
+            };
+            Creature::new(genome, Uuid::new_v4(), 0, 0)
+        };
+
+        // Indifferent creatures take anyone
+        assert_eq!(
+            creature(&weak, 0.5).mate_appeal(&creature(&strong, 0.5)),
+            1.0
+        );
+        // Choosy ones prefer their own kind...
+        let choosy = creature(&weak, 0.0);
+        assert!(choosy.mate_appeal(&creature(&weak, 0.0)) > 0.9);
+        assert!(choosy.mate_appeal(&creature(&strong, 0.0)) < 0.5);
+        // ...or strength
+        let picky = creature(&weak, 1.0);
+        assert!(
+            picky.mate_appeal(&creature(&strong, 0.5)) > picky.mate_appeal(&creature(&weak, 0.5))
+        );
+    }
+
+    #[test]
+    fn test_reproduction_uses_island_operators() {
+        let genome_id = Uuid::new_v4();
+        let mut parent1 = Creature::new(Genome::default(), genome_id, 10, 10);
+        let mut parent2 = Creature::new(Genome::default(), genome_id, 15, 15);
+        parent1.energy = 150.0;
+        parent2.energy = 150.0;
+        let world = World::new(20, 20);
+
+        let config = IslandConfig {
+            mutation_rate: 1.0,
+            mutation: Mutation::Gaussian,
+            crossover: Crossover::Blend { alpha: 0.5 },
+            ..Default::default()
+        };
+        let child = parent1.reproduce(&mut parent2, &config, &world).unwrap();
+
+        // Only Gaussian mutation gives genomes their own step sizes
+        assert!(child.genome.mutation_steps.is_some());
+        assert!(child.genome.is_valid());
     }
 
     #[test]
@@ -250,8 +544,13 @@ mod tests {
 
         parent1.energy = 50.0; // Below the 60.0 threshold
         parent2.energy = 50.0;
+        let world = World::new(20, 20);
 
-        let child = parent1.reproduce(&mut parent2, 0.1);
+        let config = IslandConfig {
+            mutation_rate: 0.1,
+            ..Default::default()
+        };
+        let child = parent1.reproduce(&mut parent2, &config, &world);
 
         assert!(child.is_none());
     }
@@ -287,11 +586,52 @@ mod tests {
         let genome = Genome::default();
         let genome_id = Uuid::new_v4();
         let creature = Creature::new(genome, genome_id, 10, 10);
+        let mut world = World::new(40, 40);
+
+        assert_eq!(creature.direction_to(10, 5, &world), Direction::North);
+        assert_eq!(creature.direction_to(10, 15, &world), Direction::South);
+        assert_eq!(creature.direction_to(15, 10, &world), Direction::East);
+        assert_eq!(creature.direction_to(5, 10, &world), Direction::West);
+        assert_eq!(creature.direction_to(15, 5, &world), Direction::NorthEast);
+        assert_eq!(creature.direction_to(35, 10, &world), Direction::East);
+
+        // Across the edge is shorter on a torus
+        world.set_topology(shared::WorldTopology::Torus);
+        assert_eq!(creature.direction_to(35, 10, &world), Direction::West);
+        assert_eq!(creature.distance_to(35, 10, &world), 15.0);
+    }
 
-        assert_eq!(creature.direction_to(10, 5), Direction::North);
-        assert_eq!(creature.direction_to(10, 15), Direction::South);
-        assert_eq!(creature.direction_to(15, 10), Direction::East);
-        assert_eq!(creature.direction_to(5, 10), Direction::West);
-        assert_eq!(creature.direction_to(15, 5), Direction::NorthEast);
+    #[test]
+    fn test_move_off_edge() {
+        let genome = Genome {
+            speed: 1.0,
+            ..Default::default()
+        };
+        let creature = Creature::new(genome, Uuid::new_v4(), 0, 5);
+        let mut world = World::new(10, 10);
+        let mut rng = rand::thread_rng();
+
+        assert_eq!(creature.try_move(Direction::West, &world, &mut rng), None);
+        world.set_topology(shared::WorldTopology::Torus);
+        assert_eq!(
+            creature.try_move(Direction::West, &world, &mut rng),
+            Some((9, 5))
+        );
+        world.set_topology(shared::WorldTopology::Walled);
+        assert_eq!(
+            creature.try_move(Direction::NorthWest, &world, &mut rng),
+            Some((1, 4))
+        );
+    }
+
+    #[test]
+    fn test_direction_from_vector() {
+        assert_eq!(Direction::from_vector(0.9, 0.1), Some(Direction::East));
+        assert_eq!(
+            Direction::from_vector(-0.6, -0.5),
+            Some(Direction::NorthWest)
+        );
+        assert_eq!(Direction::from_vector(0.0, 0.0), None);
+        assert_eq!(Direction::SouthWest.opposite(), Direction::NorthEast);
     }
 }
@@ -1,19 +1,60 @@
+use crate::creature::{DamageCause, DamageTaken, Direction};
 use crate::{Creature, World};
+use rand::distributions::{Distribution, WeightedIndex};
 use rand::seq::SliceRandom;
 use rand::Rng;
-use shared::{Genome, GenomeWithFitness};
-use std::collections::HashMap;
+use shared::brain::{BRAIN_INPUTS, BRAIN_OUTPUTS};
+use shared::operators::{Crossover, Mutation};
+use shared::{
+    Brain, CombatConfig, EnvironmentSchedule, Genome, GenomeWithFitness, IslandEnvironment,
+    SpectatorFrame, Terrain, TerrainConfig, WorkAssignment, WorldTopology,
+};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use uuid::Uuid;
 
+/// Most creatures listed in a spectator frame
+const SPECTATOR_MAX_CREATURES: usize = 5000;
+
+/// Food blocks across the longer side of the world in a spectator frame
+const SPECTATOR_FOOD_BLOCKS: usize = 100;
+
+/// Distance within which a brain senses other creatures
+const NEIGHBOUR_RADIUS: usize = 5;
+
+/// Partners a creature weighs up when it may mate anywhere on the island
+const MATE_CANDIDATES: usize = 8;
+
+/// Brain outputs weaker than this leave the creature where it is
+const BRAIN_IDLE_THRESHOLD: f64 = 0.2;
+
 #[derive(Debug, Clone)]
 pub struct IslandConfig {
     pub world_width: usize,
     pub world_height: usize,
     pub max_steps: u32,
     pub mutation_rate: f64,
+    pub mutation: Mutation,
+    pub crossover: Crossover,
+    /// Give seed genomes without a brain a random one
+    pub brains: bool,
+    pub terrain: TerrainConfig,
+    /// Whether the edges of the world are walls, bounce creatures back or
+    /// wrap around
+    pub world_topology: WorldTopology,
+    /// Fleeing, fighting and sharing over food, and fleeing hunters
+    pub combat: CombatConfig,
+    /// Seasons, droughts, blooms and drift over the environment clock
+    pub schedule: EnvironmentSchedule,
+    /// Environment clock at the first step, so schedules carry on across
+    /// an experiment's islands
+    pub clock_start: u64,
     pub plant_density: f64,
     pub food_density: f64,
     pub reproduction_threshold: f64,
+    /// Farthest apart, in tiles, two creatures can be to mate; None lets
+    /// them mate anywhere on the island
+    pub mating_radius: Option<f64>,
 }
 
 impl Default for IslandConfig {
@@ -23,9 +64,49 @@ impl Default for IslandConfig {
             world_height: 300,
             max_steps: 3000,
             mutation_rate: 0.05,
+            mutation: Mutation::default(),
+            crossover: Crossover::default(),
+            brains: false,
+            terrain: TerrainConfig::Flat,
+            world_topology: WorldTopology::Bounded,
+            combat: CombatConfig::default(),
+            schedule: EnvironmentSchedule::default(),
+            clock_start: 0,
             plant_density: 0.08, // Increased from 5% to 8% for more food availability
             food_density: 0.04,  // Increased from 2% to 4% for more food availability
             reproduction_threshold: 60.0, // Reduced from 100.0 to match creature.rs changes
+            mating_radius: Some(3.0),
+        }
+    }
+}
+
+impl IslandConfig {
+    /// Island described by a work assignment. Assignments from servers that
+    /// predate per-experiment environments get the original world parameters.
+    pub fn from_assignment(assignment: &WorkAssignment) -> Self {
+        let environment = assignment.environment.unwrap_or(IslandEnvironment {
+            plant_density: 0.05,
+            food_density: 0.02,
+            reproduction_threshold: 100.0,
+            mating_radius: None,
+        });
+        Self {
+            world_width: assignment.grid_width,
+            world_height: assignment.grid_height,
+            max_steps: assignment.max_steps,
+            mutation_rate: assignment.mutation_rate,
+            mutation: assignment.mutation,
+            crossover: assignment.crossover,
+            brains: assignment.brains,
+            terrain: assignment.terrain.clone(),
+            world_topology: assignment.world_topology,
+            combat: assignment.combat,
+            schedule: assignment.schedule.clone(),
+            clock_start: assignment.clock_start,
+            plant_density: environment.plant_density,
+            food_density: environment.food_density,
+            reproduction_threshold: environment.reproduction_threshold,
+            mating_radius: environment.mating_radius,
         }
     }
 }
@@ -45,6 +126,12 @@ pub struct Island {
     pub creatures: Vec<Creature>,
     pub step: u32,
     genome_stats: HashMap<Uuid, GenomeLineage>,
+
+    /// Damage taken by creatures that have since died or been culled
+    damage_lost: DamageTaken,
+
+    /// Seed genome IDs in the order they were given
+    lineages: Vec<Uuid>,
 }
 
 #[derive(Debug, Clone)]
@@ -56,20 +143,26 @@ struct GenomeLineage {
 impl Island {
     /// Create a new spatial island with seed genomes
     pub fn new(config: IslandConfig, seed_genomes: Vec<(Uuid, Genome)>) -> Self {
-        let mut world = World::new(config.world_width, config.world_height);
         let mut rng = rand::thread_rng();
+        let (width, height) = (config.world_width, config.world_height);
+        let terrain = config.terrain.generate(width, height, &mut rng);
+        let mut world = World::with_terrain(width, height, terrain);
+        world.set_topology(config.world_topology);
 
         // Initialize resources
         world.initialize_resources(&mut rng, config.plant_density, config.food_density);
 
         let mut creatures = Vec::new();
         let mut genome_stats = HashMap::new();
+        let lineages = seed_genomes.iter().map(|(id, _)| *id).collect();
 
         // Create creatures from seed genomes
-        for (genome_id, genome) in seed_genomes {
-            // Random position
-            let x = rng.gen_range(0..config.world_width);
-            let y = rng.gen_range(0..config.world_height);
+        for (genome_id, mut genome) in seed_genomes {
+            if config.brains && genome.brain.is_none() {
+                genome.brain = Some(Brain::random(&mut rng));
+            }
+
+            let (x, y) = random_land(&world, &mut rng);
 
             creatures.push(Creature::new(genome, genome_id, x, y));
 
@@ -89,20 +182,121 @@ impl Island {
             creatures,
             step: 0,
             genome_stats,
+            damage_lost: DamageTaken::default(),
+            lineages,
         }
     }
 
     /// Run the complete spatial simulation
     pub fn run_simulation(&mut self) -> Vec<SurvivalStats> {
+        self.run_simulation_observed(|_| {})
+    }
+
+    /// Run the complete simulation, calling `observer` after every step
+    pub fn run_simulation_observed(
+        &mut self,
+        mut observer: impl FnMut(&Self),
+    ) -> Vec<SurvivalStats> {
         let mut rng = rand::thread_rng();
 
-        while self.step < self.config.max_steps && !self.should_stop() {
+        while !self.is_finished() {
             self.tick(&mut rng);
+            observer(self);
         }
 
         self.collect_survival_stats()
     }
 
+    /// Environment clock at the current step
+    pub fn time(&self) -> u64 {
+        self.config.clock_start + self.step as u64
+    }
+
+    /// Health lost by every creature that has lived on the island, by cause
+    pub fn damage_taken(&self) -> DamageTaken {
+        let mut damage = self.damage_lost;
+        for creature in &self.creatures {
+            damage.merge(&creature.damage_taken);
+        }
+        damage
+    }
+
+    /// Seed genome IDs in the order they were given
+    pub fn lineages(&self) -> &[Uuid] {
+        &self.lineages
+    }
+
+    /// Snapshot of the island for spectators: creature positions by lineage
+    /// (evenly sampled on crowded islands) and food summed over blocks of tiles
+    pub fn spectator_frame(&self, work_id: Uuid, client_id: Uuid) -> SpectatorFrame {
+        let stride = self
+            .creatures
+            .len()
+            .div_ceil(SPECTATOR_MAX_CREATURES)
+            .max(1);
+        let creatures = self
+            .creatures
+            .iter()
+            .step_by(stride)
+            .flat_map(|c| {
+                let lineage = self
+                    .lineages
+                    .iter()
+                    .position(|id| *id == c.genome_id)
+                    .unwrap_or(self.lineages.len());
+                [c.x as u32, c.y as u32, lineage as u32]
+            })
+            .collect();
+
+        let width = self.world.width;
+        let height = self.world.height;
+        let food_cell = width.max(height).div_ceil(SPECTATOR_FOOD_BLOCKS).max(1);
+        let blocks_x = width.div_ceil(food_cell);
+        let blocks_y = height.div_ceil(food_cell);
+        let mut food = vec![0u32; blocks_x * blocks_y];
+        for y in 0..height {
+            for x in 0..width {
+                food[(y / food_cell) * blocks_x + x / food_cell] +=
+                    self.world.get_available_food(x, y);
+            }
+        }
+
+        // Terrain at the middle of each block, unless it is grassland everywhere
+        let terrain = if self.world.is_flat() {
+            Vec::new()
+        } else {
+            (0..blocks_x * blocks_y)
+                .map(|block| {
+                    let x = ((block % blocks_x) * food_cell + food_cell / 2).min(width - 1);
+                    let y = ((block / blocks_x) * food_cell + food_cell / 2).min(height - 1);
+                    let terrain = self.world.terrain_at(x, y);
+                    Terrain::ALL.iter().position(|t| *t == terrain).unwrap_or(0) as u8
+                })
+                .collect()
+        };
+
+        SpectatorFrame {
+            work_id,
+            client_id,
+            experiment: None,
+            step: self.step,
+            max_steps: self.config.max_steps,
+            world_width: width,
+            world_height: height,
+            lineages: self.lineages.clone(),
+            creatures,
+            population: self.creatures.len(),
+            food_cell,
+            food: food.into_iter().map(|f| f.min(255) as u8).collect(),
+            terrain,
+        }
+    }
+
+    /// Whether the step budget is used up or a single lineage is left
+    pub fn is_finished(&self) -> bool {
+        self.step >= self.config.max_steps || self.should_stop()
+    }
+
     /// Check if simulation should stop (only one genome type left)
     fn should_stop(&self) -> bool {
         let unique_genomes: std::collections::HashSet<Uuid> =
@@ -112,7 +306,15 @@ impl Island {
 
     /// Advance the simulation by one step
     pub fn tick<R: Rng>(&mut self, rng: &mut R) {
-        // 1. Regrow plants
+        // 1. Apply the environment schedule and regrow plants
+        if !self.config.schedule.is_empty() {
+            let time = self.time();
+            self.world.set_growth(self.config.schedule.growth(time));
+            let bloom = self.config.schedule.bloom(time);
+            if bloom > 0.0 {
+                self.world.scatter_food(rng, bloom);
+            }
+        }
         self.world.tick_plants();
 
         // 2. Creatures sense and decide actions
@@ -121,15 +323,18 @@ impl Island {
         // 3. Execute movements
         self.execute_movements(actions, rng);
 
-        // 4. Creatures try to eat
+        // 4. Carnivores attack creatures sharing their tile
+        self.execute_hunting(rng);
+
+        // 5. Creatures try to eat
         self.execute_eating(rng);
 
-        // 5. Consume energy (currently disabled - energy only affects movement)
+        // 6. Consume energy (currently disabled - energy only affects movement)
         for creature in &mut self.creatures {
             creature.consume_energy();
         }
 
-        // 6. Remove dead creatures and update stats
+        // 7. Remove dead creatures, leaving their carcasses, and update stats
         let dead_creatures: Vec<_> = self
             .creatures
             .iter()
@@ -138,6 +343,8 @@ impl Island {
             .collect();
 
         for dead in dead_creatures {
+            self.world.add_carcass(dead.x, dead.y, dead.carcass_meat());
+            self.damage_lost.merge(&dead.damage_taken);
             if let Some(stats) = self.genome_stats.get_mut(&dead.genome_id) {
                 stats.total_food_eaten += dead.food_eaten;
             }
@@ -145,44 +352,136 @@ impl Island {
 
         self.creatures.retain(|c| !c.is_dead());
 
-        // 7. Reproduction phase
+        // 8. Reproduction phase
         self.reproduce(rng);
 
         self.step += 1;
     }
 
-    /// Creatures sense environment and decide what to do
-    fn decide_actions<R: Rng>(&self, rng: &mut R) -> Vec<(usize, Action)> {
-        let mut actions = Vec::new();
+    /// Creatures sense environment and decide what to do: creatures with a
+    /// brain ask it, the others forage
+    fn decide_actions<R: Rng>(&mut self, rng: &mut R) -> Vec<(usize, Action)> {
+        let grid = self
+            .creatures
+            .iter()
+            .any(|c| c.genome.brain.is_some() || c.is_carnivore())
+            .then(|| CreatureGrid::new(&self.creatures));
+
+        (0..self.creatures.len())
+            .map(|idx| {
+                let action = match &grid {
+                    Some(grid) if self.creatures[idx].genome.brain.is_some() => {
+                        self.think(idx, grid)
+                    }
+                    _ => self.forage(idx, grid.as_ref(), rng),
+                };
+                (idx, action)
+            })
+            .collect()
+    }
 
-        for (idx, creature) in self.creatures.iter().enumerate() {
-            // Find food within vision radius
-            let food_in_vision =
-                self.world
-                    .find_food_in_radius(creature.x, creature.y, creature.vision_radius());
-
-            if let Some((food_x, food_y, _)) = food_in_vision.first() {
-                // Move towards nearest food
-                let direction = creature.direction_to(*food_x, *food_y);
-                actions.push((idx, Action::Move(direction)));
+    /// Food a creature can digest within its vision: carcasses for
+    /// carnivores, plants and food for the others
+    fn food_in_vision(&self, creature: &Creature) -> Vec<(usize, usize, u32)> {
+        let (x, y, radius) = (creature.x, creature.y, creature.vision_radius());
+        if creature.is_carnivore() {
+            self.world.find_meat_in_radius(x, y, radius)
+        } else {
+            self.world.find_food_in_radius(x, y, radius)
+        }
+    }
+
+    /// Fixed rule: move towards visible food, carnivores otherwise towards
+    /// prey, and otherwise randomly
+    fn forage<R: Rng>(&self, idx: usize, grid: Option<&CreatureGrid>, rng: &mut R) -> Action {
+        let creature = &self.creatures[idx];
+
+        let prey = || {
+            let grid = grid.filter(|_| creature.is_carnivore())?;
+            let (prey, _) = grid.nearest(&self.creatures, &self.world, idx, |other| {
+                other.genome_id != creature.genome_id
+            });
+            prey.map(|p| &self.creatures[p])
+        };
+
+        if let Some((food_x, food_y, _)) = self.food_in_vision(creature).first() {
+            // Move towards nearest food
+            Action::Move(creature.direction_to(*food_x, *food_y, &self.world))
+        } else if let Some(prey) = prey() {
+            if (prey.x, prey.y) == (creature.x, creature.y) {
+                Action::Stay
             } else {
-                // Random movement
-                let directions = [
-                    crate::creature::Direction::North,
-                    crate::creature::Direction::South,
-                    crate::creature::Direction::East,
-                    crate::creature::Direction::West,
-                    crate::creature::Direction::NorthEast,
-                    crate::creature::Direction::NorthWest,
-                    crate::creature::Direction::SouthEast,
-                    crate::creature::Direction::SouthWest,
-                ];
-                let direction = directions.choose(rng).unwrap();
-                actions.push((idx, Action::Move(*direction)));
+                Action::Move(creature.direction_to(prey.x, prey.y, &self.world))
             }
+        } else {
+            // Random movement
+            let directions = [
+                Direction::North,
+                Direction::South,
+                Direction::East,
+                Direction::West,
+                Direction::NorthEast,
+                Direction::NorthWest,
+                Direction::SouthEast,
+                Direction::SouthWest,
+            ];
+            Action::Move(*directions.choose(rng).unwrap())
         }
+    }
 
-        actions
+    /// Run a creature's brain on what it senses and turn its outputs into an action
+    fn think(&mut self, idx: usize, grid: &CreatureGrid) -> Action {
+        let (nearest, crowd) = grid.neighbours(&self.creatures, &self.world, idx);
+        let inputs = self.senses(idx, nearest, crowd);
+
+        let creature = &mut self.creatures[idx];
+        let Some(brain) = &creature.genome.brain else {
+            return Action::Stay;
+        };
+        let outputs = brain.think(&inputs, &mut creature.memory);
+        brain_action(
+            outputs,
+            &self.creatures[idx],
+            nearest.map(|n| &self.creatures[n]),
+            &self.world,
+        )
+    }
+
+    /// Brain inputs of a creature; see `BRAIN_INPUTS` for their meaning
+    fn senses(&self, idx: usize, nearest: Option<usize>, crowd: usize) -> [f64; BRAIN_INPUTS] {
+        let creature = &self.creatures[idx];
+        let offset = |x: usize, y: usize| {
+            let (dx, dy) = self.world.offset((creature.x, creature.y), (x, y));
+            (dx as f64, dy as f64)
+        };
+        let mut inputs = [0.0; BRAIN_INPUTS];
+
+        let vision = creature.vision_radius();
+        let nearest_food = self
+            .food_in_vision(creature)
+            .into_iter()
+            .map(|(x, y, _)| offset(x, y))
+            .min_by(|a, b| a.0.hypot(a.1).total_cmp(&b.0.hypot(b.1)));
+        if let Some((dx, dy)) = nearest_food {
+            inputs[0] = dx / vision;
+            inputs[1] = dy / vision;
+            inputs[2] = 1.0 - dx.hypot(dy) / vision;
+        }
+
+        if let Some(other) = nearest.map(|n| &self.creatures[n]) {
+            let (dx, dy) = offset(other.x, other.y);
+            let radius = NEIGHBOUR_RADIUS as f64;
+            inputs[3] = dx / radius;
+            inputs[4] = dy / radius;
+            inputs[5] = 1.0 - dx.hypot(dy) / radius;
+            inputs[6] = (other.combat_power() - creature.combat_power()).tanh();
+        }
+
+        inputs[7] = (crowd as f64 / 8.0).min(1.0);
+        inputs[8] = (creature.energy / 100.0).min(2.0);
+        inputs[9] = creature.health / 100.0;
+        inputs[10] = 1.0;
+        inputs
     }
 
     /// Execute movement actions
@@ -192,22 +491,76 @@ impl Island {
                 continue;
             }
 
-            let Action::Move(direction) = action;
+            let Action::Move(direction) = action else {
+                continue;
+            };
             let creature = &self.creatures[idx];
-            if let Some((new_x, new_y)) = creature.try_move(
-                direction,
-                self.config.world_width,
-                self.config.world_height,
-                rng,
-            ) {
+            if let Some((new_x, new_y)) = creature.try_move(direction, &self.world, rng) {
+                // Rough terrain takes several attempts to cross
+                let terrain = self.world.terrain_at(new_x, new_y);
+                if !terrain.is_passable() || rng.gen::<f64>() * terrain.movement_cost() > 1.0 {
+                    continue;
+                }
                 self.creatures[idx].x = new_x;
                 self.creatures[idx].y = new_y;
             }
         }
     }
 
+    /// Carnivores attack the weakest creature of another lineage on their
+    /// tile unless it flees; prey killed here leaves a carcass for the
+    /// scavenging phase
+    fn execute_hunting<R: Rng>(&mut self, rng: &mut R) {
+        let mut hunters: Vec<usize> = (0..self.creatures.len())
+            .filter(|&idx| self.creatures[idx].is_carnivore())
+            .collect();
+        if hunters.is_empty() {
+            return;
+        }
+        hunters.shuffle(rng);
+
+        let mut positions: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (idx, creature) in self.creatures.iter().enumerate() {
+            positions
+                .entry((creature.x, creature.y))
+                .or_default()
+                .push(idx);
+        }
+
+        for hunter in hunters {
+            let creature = &self.creatures[hunter];
+            if creature.is_dead() {
+                continue;
+            }
+            let prey = positions[&(creature.x, creature.y)]
+                .iter()
+                .copied()
+                .filter(|&other| {
+                    let other = &self.creatures[other];
+                    other.genome_id != creature.genome_id && !other.is_dead()
+                })
+                .min_by(|&a, &b| {
+                    let power = |idx: usize| self.creatures[idx].combat_power();
+                    power(a).total_cmp(&power(b))
+                });
+
+            let Some(prey) = prey else {
+                continue;
+            };
+            let combat = self.config.combat;
+            if rng.gen::<f64>() < combat.flee_probability(self.creatures[prey].genome.speed) {
+                continue;
+            }
+            let (hunter, prey) = pair_mut(&mut self.creatures, hunter, prey);
+            hunter.hunt(prey);
+            hunter.spend_energy(combat.energy_cost);
+            prey.spend_energy(combat.energy_cost);
+        }
+    }
+
     /// Creatures try to eat food at their positions
-    /// Implements hybrid combat: peaceful movement, but fight over food
+    /// Implements hybrid combat: peaceful movement, but fight over food.
+    /// Carnivores eat from carcasses and the others from plants and food.
     fn execute_eating<R: Rng>(&mut self, rng: &mut R) {
         // Group creatures by position
         let mut positions: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
@@ -220,58 +573,117 @@ impl Island {
 
         // Process each position with creatures
         for ((x, y), creature_indices) in positions {
-            let available_food = self.world.get_available_food(x, y);
-
-            if available_food == 0 {
+            let meat = self.world.get_available_meat(x, y) > 0;
+            if !meat && self.world.get_available_food(x, y) == 0 {
                 continue; // No food here
             }
 
-            if creature_indices.len() == 1 {
+            // Only creatures that can digest the food compete for it
+            let diners: Vec<usize> = creature_indices
+                .into_iter()
+                .filter(|&idx| {
+                    let creature = &self.creatures[idx];
+                    creature.is_carnivore() == meat && !creature.is_dead()
+                })
+                .collect();
+
+            match diners.len() {
+                0 => {}
                 // Single creature eats peacefully
-                let idx = creature_indices[0];
-                let food_eaten = self.world.consume_food(x, y, 10);
-                self.creatures[idx].add_energy(food_eaten as f64);
-                self.creatures[idx].food_eaten += food_eaten;
-            } else {
+                1 => self.feed(diners[0], x, y, meat),
                 // Multiple creatures - COMBAT!
-                self.resolve_combat(&creature_indices, x, y, rng);
+                _ => self.resolve_combat(&diners, x, y, meat, rng),
             }
         }
     }
 
-    /// Resolve combat between creatures at the same food source
+    /// A creature eats from its tile
+    fn feed(&mut self, idx: usize, x: usize, y: usize, meat: bool) {
+        let food_eaten = self.world.consume_food(x, y, 10);
+        let creature = &mut self.creatures[idx];
+        creature.add_energy(creature.digest(food_eaten, meat));
+        creature.food_eaten += food_eaten;
+    }
+
+    /// Resolve a contest between creatures at the same food source: weaker
+    /// creatures may flee, and those left either share the food in
+    /// proportion to their combat power or fight, the winner drawn with odds
+    /// in proportion to it
     fn resolve_combat<R: Rng>(
         &mut self,
         creature_indices: &[usize],
         x: usize,
         y: usize,
-        _rng: &mut R,
+        meat: bool,
+        rng: &mut R,
     ) {
-        // Calculate combat powers
-        let mut combatants: Vec<(usize, f64)> = creature_indices
+        let combat = self.config.combat;
+        let power = |idx: usize| combat_weight(self.creatures[idx].combat_power());
+        let strongest = creature_indices
             .iter()
-            .map(|&idx| (idx, self.creatures[idx].combat_power()))
-            .collect();
+            .map(|&idx| power(idx))
+            .fold(0.0, f64::max);
 
-        // Sort by combat power (highest first)
-        combatants.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        // Creatures facing a stronger rival may flee, faster ones more often
+        let combatants: Vec<usize> = creature_indices
+            .iter()
+            .copied()
+            .filter(|&idx| {
+                power(idx) >= strongest
+                    || rng.gen::<f64>() >= combat.flee_probability(self.creatures[idx].genome.speed)
+            })
+            .collect();
+        let powers: Vec<f64> = combatants.iter().map(|&idx| power(idx)).collect();
 
-        // Winner gets the food
-        let (winner_idx, winner_power) = combatants[0];
+        if combatants.len() == 1 {
+            self.feed(combatants[0], x, y, meat);
+            return;
+        }
 
-        let food_eaten = self.world.consume_food(x, y, 10);
-        self.creatures[winner_idx].add_energy(food_eaten as f64);
-        self.creatures[winner_idx].food_eaten += food_eaten;
+        if rng.gen::<f64>() >= combat.fight_chance {
+            // Decline to fight: split one meal by combat power
+            let meal = self.world.consume_food(x, y, 10);
+            for (&idx, share) in combatants.iter().zip(proportional_shares(meal, &powers)) {
+                let creature = &mut self.creatures[idx];
+                creature.add_energy(creature.digest(share, meat));
+                creature.food_eaten += share;
+            }
+            return;
+        }
 
-        // Losers take damage to health (25% of winner's combat power)
-        for (loser_idx, _) in combatants.iter().skip(1) {
-            self.creatures[*loser_idx].take_damage(winner_power * 0.25);
+        // Fight: the odds of winning follow combat power, so ties are a coin toss
+        let winner = match WeightedIndex::new(&powers) {
+            Ok(weights) => weights.sample(rng),
+            Err(_) => rng.gen_range(0..combatants.len()),
+        };
+        self.feed(combatants[winner], x, y, meat);
+
+        let strongest_loser = (0..combatants.len())
+            .filter(|&i| i != winner)
+            .map(|i| powers[i])
+            .fold(0.0, f64::max);
+        for (i, &idx) in combatants.iter().enumerate() {
+            let creature = &mut self.creatures[idx];
+            creature.spend_energy(combat.energy_cost);
+            if i == winner {
+                creature.take_damage(
+                    combat.damage * combat.retaliation * strongest_loser,
+                    DamageCause::Fight,
+                );
+            } else {
+                creature.take_damage(combat.damage * powers[winner], DamageCause::Fight);
+            }
         }
     }
 
-    /// Handle reproduction with population control
+    /// Handle reproduction with population control: each creature ready to
+    /// breed, in random order, courts a ready partner within the mating
+    /// radius, and they mate if both accept by their mate preferences
     fn reproduce<R: Rng>(&mut self, rng: &mut R) {
         let mut new_creatures = Vec::new();
+        // Creatures culled to make room, removed once pairing is done so
+        // the indices stay valid
+        let mut culled = HashSet::new();
 
         // Need at least 2 creatures to reproduce
         if self.creatures.len() < 2 {
@@ -282,65 +694,131 @@ impl Island {
         let world_area = self.config.world_width * self.config.world_height;
         let population_limit = world_area / 2;
 
-        // Shuffle to randomize mating pairs
-        let mut indices: Vec<usize> = (0..self.creatures.len()).collect();
-        indices.shuffle(rng);
-
-        // Try to pair up creatures for reproduction
-        for i in (0..indices.len() - 1).step_by(2) {
-            let idx1 = indices[i];
-            let idx2 = indices[i + 1];
-
-            // Check if both can reproduce
-            if self.creatures[idx1].can_reproduce(self.config.reproduction_threshold)
-                && self.creatures[idx2].can_reproduce(self.config.reproduction_threshold)
-            {
-                // Use split_at_mut to get two mutable references safely
-                let (left, right) = if idx1 < idx2 {
-                    let (left, right) = self.creatures.split_at_mut(idx2);
-                    (&mut left[idx1], &mut right[0])
-                } else {
-                    let (left, right) = self.creatures.split_at_mut(idx1);
-                    (&mut right[0], &mut left[idx2])
-                };
+        let mut ready: Vec<usize> = (0..self.creatures.len())
+            .filter(|&idx| self.creatures[idx].can_reproduce(self.config.reproduction_threshold))
+            .collect();
+        if ready.len() < 2 {
+            return;
+        }
+        ready.shuffle(rng);
+        let mut by_tile: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for &idx in &ready {
+            let creature = &self.creatures[idx];
+            by_tile
+                .entry((creature.x, creature.y))
+                .or_default()
+                .push(idx);
+        }
 
-                // Create offspring
-                if let Some(child) = left.reproduce(right, self.config.mutation_rate) {
-                    // Check if we're at population limit
-                    if self.creatures.len() + new_creatures.len() >= population_limit {
-                        // Find creature with lowest health and zero energy to remove
-                        if let Some(remove_idx) = self.find_weakest_creature() {
-                            // Track stats before removal
-                            let removed = self.creatures.remove(remove_idx);
-                            if let Some(stats) = self.genome_stats.get_mut(&removed.genome_id) {
-                                stats.total_food_eaten += removed.food_eaten;
-                            }
-                        } else {
-                            // No creature with zero energy found, block spawning
-                            continue;
-                        }
+        let mut mated = HashSet::new();
+        for &idx1 in &ready {
+            if mated.contains(&idx1) {
+                continue;
+            }
+            let Some(idx2) = self.choose_mate(idx1, &ready, &by_tile, &mated, rng) else {
+                continue;
+            };
+            // Both have to accept
+            let (a, b) = (&self.creatures[idx1], &self.creatures[idx2]);
+            if rng.gen::<f64>() >= a.mate_appeal(b) * b.mate_appeal(a) {
+                continue;
+            }
+            mated.insert(idx1);
+            mated.insert(idx2);
+
+            let (left, right) = pair_mut(&mut self.creatures, idx1, idx2);
+
+            // Create offspring
+            if let Some(child) = left.reproduce(right, &self.config, &self.world) {
+                // Check if we're at population limit
+                if self.creatures.len() - culled.len() + new_creatures.len() >= population_limit {
+                    // Find creature with lowest health and zero energy to remove
+                    if let Some(remove_idx) = self.find_weakest_creature(&culled) {
+                        culled.insert(remove_idx);
+                    } else {
+                        // No creature with zero energy found, block spawning
+                        continue;
                     }
+                }
 
-                    // Track lineage
-                    if let Some(stats) = self.genome_stats.get_mut(&child.genome_id) {
-                        stats.total_spawned += 1;
-                    }
-                    new_creatures.push(child);
+                // Track lineage
+                if let Some(stats) = self.genome_stats.get_mut(&child.genome_id) {
+                    stats.total_spawned += 1;
                 }
+                new_creatures.push(child);
             }
         }
 
-        // Add new creatures
+        // Remove culled creatures, tracking their stats, then add new ones
+        let mut culled: Vec<usize> = culled.into_iter().collect();
+        culled.sort_unstable_by_key(|&idx| Reverse(idx));
+        for idx in culled {
+            let removed = self.creatures.swap_remove(idx);
+            self.damage_lost.merge(&removed.damage_taken);
+            if let Some(stats) = self.genome_stats.get_mut(&removed.genome_id) {
+                stats.total_food_eaten += removed.food_eaten;
+            }
+        }
         self.creatures.extend(new_creatures);
     }
 
-    /// Find the creature with lowest health and zero energy
-    /// Returns None if no creature has zero energy
-    fn find_weakest_creature(&self) -> Option<usize> {
+    /// A partner a creature courts, drawn by how appealing it finds them from
+    /// the unmated creatures ready to breed within the mating radius, or a
+    /// sample of those anywhere on the island without one
+    fn choose_mate<R: Rng>(
+        &self,
+        idx: usize,
+        ready: &[usize],
+        by_tile: &HashMap<(usize, usize), Vec<usize>>,
+        mated: &HashSet<usize>,
+        rng: &mut R,
+    ) -> Option<usize> {
+        let creature = &self.creatures[idx];
+        let candidates: Vec<usize> = match self.config.mating_radius {
+            Some(radius) => {
+                let reach = radius.ceil() as usize;
+                let topology = self.world.topology();
+                let columns = topology.range(creature.x, reach, self.world.width);
+                topology
+                    .range(creature.y, reach, self.world.height)
+                    .into_iter()
+                    .flat_map(|y| columns.iter().map(move |&x| (x, y)))
+                    .flat_map(|tile| by_tile.get(&tile).into_iter().flatten().copied())
+                    .filter(|&other| {
+                        let other = &self.creatures[other];
+                        creature.distance_to(other.x, other.y, &self.world) <= radius
+                    })
+                    .collect()
+            }
+            None => ready
+                .choose_multiple(rng, MATE_CANDIDATES)
+                .copied()
+                .collect(),
+        };
+
+        let candidates: Vec<usize> = candidates
+            .into_iter()
+            .filter(|&other| other != idx && !mated.contains(&other))
+            .collect();
+        let appeal: Vec<f64> = candidates
+            .iter()
+            .map(|&other| creature.mate_appeal(&self.creatures[other]))
+            .collect();
+        let choice = WeightedIndex::new(&appeal).ok()?.sample(rng);
+        Some(candidates[choice])
+    }
+
+    /// Find the creature with lowest health and zero energy, other than
+    /// those already culled
+    /// Returns None if no such creature has zero energy
+    fn find_weakest_creature(&self, culled: &HashSet<usize>) -> Option<usize> {
         let mut weakest_idx: Option<usize> = None;
         let mut lowest_health = f64::MAX;
 
         for (idx, creature) in self.creatures.iter().enumerate() {
+            if culled.contains(&idx) {
+                continue;
+            }
             if creature.energy <= 0.0 && (weakest_idx.is_none() || creature.health < lowest_health)
             {
                 weakest_idx = Some(idx);
@@ -352,7 +830,7 @@ impl Island {
     }
 
     /// Collect survival statistics for all genomes
-    fn collect_survival_stats(&self) -> Vec<SurvivalStats> {
+    pub fn collect_survival_stats(&self) -> Vec<SurvivalStats> {
         let mut results = Vec::new();
 
         // Count current survivors by genome
@@ -386,10 +864,37 @@ impl Island {
         total / self.creatures.len() as f64
     }
 
+    /// Remove `count` random creatures, to move to another island
+    pub fn emigrate<R: Rng>(&mut self, count: usize, rng: &mut R) -> Vec<Creature> {
+        let count = count.min(self.creatures.len());
+        let mut picked = rand::seq::index::sample(rng, self.creatures.len(), count).into_vec();
+        // Remove from the back so swap_remove doesn't move a creature still to be picked
+        picked.sort_unstable_by(|a, b| b.cmp(a));
+        picked
+            .into_iter()
+            .map(|idx| self.creatures.swap_remove(idx))
+            .collect()
+    }
+
+    /// Place creatures arriving from another island on random land. Their
+    /// lineages are tracked here from now on, without counting them as spawned.
+    pub fn immigrate<R: Rng>(&mut self, creatures: Vec<Creature>, rng: &mut R) {
+        for mut creature in creatures {
+            (creature.x, creature.y) = random_land(&self.world, rng);
+            self.genome_stats
+                .entry(creature.genome_id)
+                .or_insert(GenomeLineage {
+                    total_spawned: 0,
+                    total_food_eaten: 0,
+                });
+            self.creatures.push(creature);
+        }
+    }
+
     /// Get the best N genomes from the island (deprecated - use survival stats instead)
     pub fn get_best_genomes(&self, n: usize) -> Vec<GenomeWithFitness> {
         let mut creatures = self.creatures.clone();
-        creatures.sort_by(|a, b| b.fitness().partial_cmp(&a.fitness()).unwrap());
+        creatures.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
 
         creatures
             .iter()
@@ -397,19 +902,190 @@ impl Island {
             .map(|c| GenomeWithFitness {
                 genome: c.genome.clone(),
                 fitness: c.fitness(),
+                genome_id: Some(c.genome_id),
+                empirical: None,
             })
             .collect()
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A random position on land, or anywhere if the world is all water
+fn random_land<R: Rng>(world: &World, rng: &mut R) -> (usize, usize) {
+    world.random_passable_position(rng).unwrap_or((
+        rng.gen_range(0..world.width),
+        rng.gen_range(0..world.height),
+    ))
+}
+
+/// Combat power as a weight for the odds of a fight: at least zero, and
+/// zero for invalid powers
+fn combat_weight(power: f64) -> f64 {
+    if power.is_finite() {
+        power.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+/// Split `amount` in proportion to `weights`, handing units left over by
+/// rounding down to the largest remainders; equal shares if no weight is
+/// positive
+fn proportional_shares(amount: u32, weights: &[f64]) -> Vec<u32> {
+    let total: f64 = weights.iter().sum();
+    let exact: Vec<f64> = weights
+        .iter()
+        .map(|&w| {
+            if total > 0.0 {
+                amount as f64 * w / total
+            } else {
+                amount as f64 / weights.len() as f64
+            }
+        })
+        .collect();
+    let mut shares: Vec<u32> = exact.iter().map(|e| e.floor() as u32).collect();
+
+    let mut by_remainder: Vec<usize> = (0..weights.len()).collect();
+    by_remainder
+        .sort_by(|&a, &b| (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor())));
+    let left = amount - shares.iter().sum::<u32>();
+    for &i in by_remainder.iter().take(left as usize) {
+        shares[i] += 1;
+    }
+    shares
+}
+
+/// Mutable references to two different creatures
+fn pair_mut(creatures: &mut [Creature], a: usize, b: usize) -> (&mut Creature, &mut Creature) {
+    // Use split_at_mut to get two mutable references safely
+    if a < b {
+        let (left, right) = creatures.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = creatures.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Action {
-    Move(crate::creature::Direction),
+    Move(Direction),
+    Stay,
+}
+
+/// Action for a brain's outputs: chase or flee the nearest creature when
+/// that reaction is stronger than the urge to move, otherwise move as told
+fn brain_action(
+    outputs: [f64; BRAIN_OUTPUTS],
+    creature: &Creature,
+    nearest: Option<&Creature>,
+    world: &World,
+) -> Action {
+    let [dx, dy, reaction] = outputs;
+    let movement = dx.abs().max(dy.abs());
+
+    if let Some(other) = nearest {
+        let same_tile = (other.x, other.y) == (creature.x, creature.y);
+        if reaction.abs() > movement.max(BRAIN_IDLE_THRESHOLD) && !same_tile {
+            let towards = creature.direction_to(other.x, other.y, world);
+            return Action::Move(if reaction > 0.0 {
+                towards
+            } else {
+                towards.opposite()
+            });
+        }
+        if reaction > movement.max(BRAIN_IDLE_THRESHOLD) {
+            // Already next to its target
+            return Action::Stay;
+        }
+    }
+
+    if movement < BRAIN_IDLE_THRESHOLD {
+        return Action::Stay;
+    }
+    Direction::from_vector(dx, dy).map_or(Action::Stay, Action::Move)
+}
+
+/// Creatures bucketed by position, so brains can find their neighbours
+/// without comparing every pair of creatures
+struct CreatureGrid {
+    buckets: HashMap<(usize, usize), Vec<usize>>,
+}
+
+impl CreatureGrid {
+    fn new(creatures: &[Creature]) -> Self {
+        let mut buckets: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (idx, creature) in creatures.iter().enumerate() {
+            buckets
+                .entry((creature.x / NEIGHBOUR_RADIUS, creature.y / NEIGHBOUR_RADIUS))
+                .or_default()
+                .push(idx);
+        }
+        Self { buckets }
+    }
+
+    /// The nearest other creature within `NEIGHBOUR_RADIUS` of a creature,
+    /// and how many creatures are that close
+    fn neighbours(
+        &self,
+        creatures: &[Creature],
+        world: &World,
+        idx: usize,
+    ) -> (Option<usize>, usize) {
+        self.nearest(creatures, world, idx, |_| true)
+    }
+
+    /// The nearest other creature within `NEIGHBOUR_RADIUS` of a creature
+    /// that passes `filter`, and how many such creatures are that close
+    fn nearest(
+        &self,
+        creatures: &[Creature],
+        world: &World,
+        idx: usize,
+        filter: impl Fn(&Creature) -> bool,
+    ) -> (Option<usize>, usize) {
+        let creature = &creatures[idx];
+        let mut nearest: Option<(usize, f64)> = None;
+        let mut count = 0;
+
+        // Buckets of the tiles in reach, which wrap around on a torus
+        let buckets = |center: usize, size: usize| -> BTreeSet<usize> {
+            world
+                .topology()
+                .range(center, NEIGHBOUR_RADIUS, size)
+                .into_iter()
+                .map(|tile| tile / NEIGHBOUR_RADIUS)
+                .collect()
+        };
+        let columns = buckets(creature.x, world.width);
+
+        for y in buckets(creature.y, world.height) {
+            for &x in &columns {
+                for &other in self.buckets.get(&(x, y)).into_iter().flatten() {
+                    if other == idx || !filter(&creatures[other]) {
+                        continue;
+                    }
+                    let other_creature = &creatures[other];
+                    let distance = creature.distance_to(other_creature.x, other_creature.y, world);
+                    if distance > NEIGHBOUR_RADIUS as f64 {
+                        continue;
+                    }
+                    count += 1;
+                    if nearest.is_none_or(|(_, closest)| distance < closest) {
+                        nearest = Some((other, distance));
+                    }
+                }
+            }
+        }
+
+        (nearest.map(|(other, _)| other), count)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::world::Tile;
+    use shared::ScheduleEvent;
 
     #[test]
     fn test_spatial_island_creation() {
@@ -494,4 +1170,348 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(results[0].total_spawned > 0);
     }
+
+    #[test]
+    fn test_brains_drive_creatures() {
+        let config = IslandConfig {
+            world_width: 40,
+            world_height: 40,
+            max_steps: 200,
+            brains: true,
+            reproduction_threshold: 60.0,
+            ..Default::default()
+        };
+        let seeds: Vec<_> = (0..4).map(|_| (Uuid::new_v4(), Genome::random())).collect();
+        let mut island = Island::new(config, seeds);
+        assert!(island.creatures.iter().all(|c| c.genome.brain.is_some()));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            island.tick(&mut rng);
+        }
+        assert!(island.creatures.iter().all(|c| c
+            .genome
+            .brain
+            .as_ref()
+            .is_some_and(|b| b.is_valid())));
+        assert!(island
+            .creatures
+            .iter()
+            .any(|c| c.memory.iter().any(|m| *m != 0.0)));
+    }
+
+    #[test]
+    fn test_brain_actions() {
+        let creature = Creature::new(Genome::default(), Uuid::new_v4(), 10, 10);
+        let other = Creature::new(Genome::default(), Uuid::new_v4(), 13, 10);
+        let world = World::new(20, 20);
+
+        assert_eq!(
+            brain_action([0.9, -0.1, 0.0], &creature, None, &world),
+            Action::Move(Direction::East)
+        );
+        assert_eq!(
+            brain_action([0.1, 0.1, 0.9], &creature, None, &world),
+            Action::Stay
+        );
+        assert_eq!(
+            brain_action([0.1, 0.1, 0.9], &creature, Some(&other), &world),
+            Action::Move(Direction::East)
+        );
+        assert_eq!(
+            brain_action([0.1, 0.1, -0.9], &creature, Some(&other), &world),
+            Action::Move(Direction::West)
+        );
+
+        let grid = CreatureGrid::new(&[creature.clone(), other.clone()]);
+        assert_eq!(grid.neighbours(&[creature, other], &world, 0), (Some(1), 1));
+
+        // Neighbours across the edge of a torus
+        let mut world = World::new(101, 20);
+        world.set_topology(WorldTopology::Torus);
+        let creatures = [
+            Creature::new(Genome::default(), Uuid::new_v4(), 99, 10),
+            Creature::new(Genome::default(), Uuid::new_v4(), 3, 10),
+        ];
+        let grid = CreatureGrid::new(&creatures);
+        assert_eq!(grid.neighbours(&creatures, &world, 0), (Some(1), 1));
+        assert_eq!(
+            brain_action([0.1, 0.1, 0.9], &creatures[0], Some(&creatures[1]), &world),
+            Action::Move(Direction::East)
+        );
+    }
+
+    #[test]
+    fn test_hunting_leaves_carcass() {
+        // A single tile, so neither creature can get away from the other
+        let config = IslandConfig {
+            world_width: 1,
+            world_height: 1,
+            plant_density: 0.0,
+            food_density: 0.0,
+            // Prey that never gets away
+            combat: CombatConfig {
+                flee_chance: 0.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let hunter = Genome {
+            strength: 1.0,
+            diet: 1.0,
+            ..Default::default()
+        };
+        let seeds = vec![
+            (Uuid::new_v4(), hunter),
+            (Uuid::new_v4(), Genome::default()),
+        ];
+        let mut island = Island::new(config, seeds);
+        island.creatures[1].health = 1.0;
+        let prey_id = island.creatures[1].id;
+        let meat = island.creatures[1].carcass_meat();
+
+        let mut rng = rand::thread_rng();
+        island.tick(&mut rng);
+        assert!(island.creatures.iter().all(|c| c.id != prey_id));
+        assert_eq!(island.world.get_available_meat(0, 0), meat);
+
+        // The hunter scavenges the carcass it left
+        island.tick(&mut rng);
+        assert!(island.world.get_available_meat(0, 0) < meat);
+    }
+
+    #[test]
+    fn test_combat() {
+        let config = IslandConfig {
+            world_width: 10,
+            world_height: 10,
+            plant_density: 0.0,
+            food_density: 0.0,
+            combat: CombatConfig {
+                flee_chance: 0.0,
+                fight_chance: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let seeds = vec![
+            (Uuid::new_v4(), Genome::default()),
+            (Uuid::new_v4(), Genome::default()),
+        ];
+        let mut rng = rand::thread_rng();
+
+        // Evenly matched rivals each win some fights, and the losers are hurt
+        let mut wins = [0, 0];
+        for _ in 0..100 {
+            let mut island = Island::new(config.clone(), seeds.clone());
+            *island.world.get_tile_mut(5, 5).unwrap() = Tile::Food { amount: 20 };
+            island.resolve_combat(&[0, 1], 5, 5, false, &mut rng);
+            let winner = (island.creatures[1].food_eaten > 0) as usize;
+            wins[winner] += 1;
+            let loser = &island.creatures[1 - winner];
+            assert!(loser.health < 100.0);
+            assert_eq!(loser.damage_taken.fight, 100.0 - loser.health);
+            assert!(loser.energy < 100.0);
+            assert_eq!(
+                island.damage_taken().fight,
+                200.0 - island.creatures[0].health - island.creatures[1].health
+            );
+        }
+        assert!(wins[0] > 10 && wins[1] > 10, "{:?}", wins);
+
+        // Creatures that never fight share the food by combat power
+        let mut peaceful = Island::new(
+            IslandConfig {
+                combat: CombatConfig {
+                    fight_chance: 0.0,
+                    ..config.combat
+                },
+                ..config.clone()
+            },
+            seeds.clone(),
+        );
+        peaceful.creatures[0].genome.strength = 1.0;
+        peaceful.creatures[1].genome.strength = 0.0;
+        peaceful.creatures[1].genome.size = 0.5;
+        *peaceful.world.get_tile_mut(5, 5).unwrap() = Tile::Food { amount: 20 };
+        peaceful.resolve_combat(&[0, 1], 5, 5, false, &mut rng);
+        let eaten: Vec<u32> = peaceful.creatures.iter().map(|c| c.food_eaten).collect();
+        assert_eq!(eaten.iter().sum::<u32>(), 10);
+        assert!(eaten[0] > eaten[1] && eaten[1] > 0, "{:?}", eaten);
+        assert!(peaceful.creatures.iter().all(|c| c.health == 100.0));
+
+        // A fast weakling always flees a stronger rival
+        let mut chase = Island::new(
+            IslandConfig {
+                combat: CombatConfig {
+                    flee_chance: 1.0,
+                    ..config.combat
+                },
+                ..config.clone()
+            },
+            seeds,
+        );
+        chase.creatures[0].genome.strength = 1.0;
+        chase.creatures[1].genome.strength = 0.0;
+        chase.creatures[1].genome.speed = 1.0;
+        // Invalid powers don't stop the contest
+        chase.creatures[1].genome.size = f64::NAN;
+        *chase.world.get_tile_mut(5, 5).unwrap() = Tile::Food { amount: 20 };
+        chase.resolve_combat(&[0, 1], 5, 5, false, &mut rng);
+        assert_eq!(chase.creatures[0].food_eaten, 10);
+        assert_eq!(chase.creatures[1].food_eaten, 0);
+        assert_eq!(chase.creatures[1].health, 100.0);
+
+        assert_eq!(proportional_shares(10, &[2.0, 1.0, 1.0]), vec![5, 3, 2]);
+        assert_eq!(proportional_shares(3, &[0.0, 0.0]).iter().sum::<u32>(), 3);
+    }
+
+    #[test]
+    fn test_local_mating() {
+        let config = IslandConfig {
+            world_width: 20,
+            world_height: 20,
+            mating_radius: Some(2.0),
+            ..Default::default()
+        };
+        let genome_id = Uuid::new_v4();
+        let seeds = vec![
+            (genome_id, Genome::default()),
+            (genome_id, Genome::default()),
+        ];
+        let mut rng = rand::thread_rng();
+        let mut island = Island::new(config, seeds);
+        let place = |island: &mut Island, positions: [(usize, usize); 2]| {
+            island.creatures.truncate(2);
+            for (creature, (x, y)) in island.creatures.iter_mut().zip(positions) {
+                (creature.x, creature.y) = (x, y);
+                creature.energy = 150.0;
+            }
+        };
+
+        // Too far apart to mate
+        place(&mut island, [(2, 2), (12, 12)]);
+        island.reproduce(&mut rng);
+        assert_eq!(island.creatures.len(), 2);
+
+        // Close enough, and the child is born between them
+        place(&mut island, [(2, 2), (4, 2)]);
+        island.reproduce(&mut rng);
+        assert_eq!(island.creatures.len(), 3);
+        let child = &island.creatures[2];
+        assert!(child.x.abs_diff(3) <= 1 && child.y.abs_diff(2) <= 1);
+
+        // Choosy creatures turn away partners unlike them
+        let strong = Genome {
+            mate_preference: 0.0,
+            ..Genome::new(1.0, 0.0, 0.5, 0.5, 0.5, 0.0, 0.0)
+        };
+        let weak = Genome {
+            mate_preference: 0.0,
+            ..Genome::new(0.0, 1.0, 0.5, 0.5, 0.5, 0.0, 0.0)
+        };
+        island.creatures[0].genome = strong;
+        island.creatures[1].genome = weak;
+        let mut born = 0;
+        for _ in 0..20 {
+            place(&mut island, [(2, 2), (3, 3)]);
+            island.reproduce(&mut rng);
+            born += island.creatures.len() - 2;
+        }
+        assert!(born < 5, "{} born", born);
+    }
+
+    #[test]
+    fn test_creatures_stay_on_land() {
+        // Water everywhere but a strip of land down the middle
+        let rows = vec!["~~~..~~~".to_string(); 4];
+        let config = IslandConfig {
+            world_width: 40,
+            world_height: 20,
+            terrain: TerrainConfig::Map { rows },
+            ..Default::default()
+        };
+        let seeds: Vec<_> = (0..6).map(|_| (Uuid::new_v4(), Genome::random())).collect();
+        let mut island = Island::new(config, seeds);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            for creature in &island.creatures {
+                assert!(island
+                    .world
+                    .terrain_at(creature.x, creature.y)
+                    .is_passable());
+            }
+            island.tick(&mut rng);
+        }
+
+        let frame = island.spectator_frame(Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(frame.terrain.len(), frame.food.len());
+        assert!(frame.terrain.contains(&2)); // water
+    }
+
+    #[test]
+    fn test_environment_schedule() {
+        let config = IslandConfig {
+            world_width: 30,
+            world_height: 30,
+            plant_density: 0.0,
+            food_density: 0.0,
+            clock_start: 1000,
+            schedule: EnvironmentSchedule {
+                events: vec![
+                    ScheduleEvent::Bloom {
+                        start: 1001,
+                        every: None,
+                        density: 0.2,
+                    },
+                    ScheduleEvent::Drought {
+                        start: 0,
+                        duration: 5000,
+                        every: None,
+                        growth: 0.0,
+                    },
+                ],
+            },
+            ..Default::default()
+        };
+        let mut island = Island::new(config, Vec::new());
+        let mut rng = rand::thread_rng();
+
+        island.tick(&mut rng);
+        assert_eq!(island.time(), 1001);
+        assert_eq!(island.world.total_food(), 0);
+        island.tick(&mut rng);
+        assert!(island.world.total_food() > 0);
+    }
+
+    #[test]
+    fn test_spectator_frame() {
+        let config = IslandConfig {
+            world_width: 250,
+            world_height: 120,
+            max_steps: 5,
+            ..Default::default()
+        };
+        let seeds: Vec<_> = (0..3).map(|_| (Uuid::new_v4(), Genome::random())).collect();
+        let mut island = Island::new(config, seeds.clone());
+        let work_id = Uuid::new_v4();
+
+        let mut frames = Vec::new();
+        island.run_simulation_observed(|island| {
+            frames.push(island.spectator_frame(work_id, Uuid::nil()))
+        });
+
+        assert_eq!(frames.len(), island.step as usize);
+        let frame = frames.last().unwrap();
+        assert_eq!(frame.work_id, work_id);
+        assert_eq!(
+            frame.lineages,
+            seeds.iter().map(|(id, _)| *id).collect::<Vec<_>>()
+        );
+        assert_eq!(frame.creatures.len(), frame.population * 3);
+        assert!(frame.creatures.chunks(3).all(|c| c[2] < 3));
+        assert_eq!(frame.food_cell, 3);
+        assert_eq!(frame.food.len(), 84 * 40);
+    }
 }
@@ -1,8 +1,10 @@
+pub mod archipelago;
 pub mod creature;
 pub mod island;
 pub mod world;
 
-pub use creature::Creature;
+pub use archipelago::Archipelago;
+pub use creature::{Creature, DamageCause, DamageTaken};
 pub use island::{Island, IslandConfig, SurvivalStats};
 pub use world::World;
 
@@ -37,12 +39,14 @@ pub fn run_simulation(
     // behavior when the final population goes extinct by the end of the run.
     let mut seed_fallback: Vec<GenomeWithFitness> = seed_genomes_with_ids
         .iter()
-        .map(|(_, g)| GenomeWithFitness {
+        .map(|(id, g)| GenomeWithFitness {
             genome: g.clone(),
             fitness: g.fitness_score(),
+            genome_id: Some(*id),
+            empirical: None,
         })
         .collect();
-    seed_fallback.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
+    seed_fallback.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
     if seed_fallback.len() > 10 {
         seed_fallback.truncate(10);
     }
@@ -58,6 +62,7 @@ pub fn run_simulation(
         plant_density: 0.10,
         food_density: 0.05,
         reproduction_threshold: 100.0,
+        ..Default::default()
     };
 
     let mut island = Island::new(config, seed_genomes_with_ids);
@@ -1,4 +1,9 @@
+use rand::seq::SliceRandom;
 use rand::Rng;
+use shared::{Terrain, WorldTopology};
+
+/// Ticks for a carcass to lose one unit of meat
+const CARCASS_DECAY_TICKS: u32 = 5;
 
 /// Types of tiles in the world
 #[derive(Debug, Clone, PartialEq)]
@@ -14,6 +19,11 @@ pub enum Tile {
     Food {
         amount: u32,
     },
+    /// Remains of a dead creature: meat for carnivores that rots away over time
+    Carcass {
+        meat: u32,
+        decay_timer: u32,
+    },
 }
 
 /// 2D grid world for spatial simulation
@@ -22,21 +32,44 @@ pub struct World {
     pub width: usize,
     pub height: usize,
     grid: Vec<Vec<Tile>>,
+    /// Terrain of each tile, row by row
+    terrain: Vec<Terrain>,
+    /// Rate plants regrow at, relative to their terrain's usual rate
+    growth: f64,
+    /// Regrowth owed from earlier ticks, less than one pass
+    growth_credit: f64,
+    /// What lies beyond the edges
+    topology: WorldTopology,
 }
 
 impl World {
-    /// Create a new world with specified dimensions
+    /// Create a new world of grassland with specified dimensions
     pub fn new(width: usize, height: usize) -> Self {
+        Self::with_terrain(width, height, vec![Terrain::Grassland; width * height])
+    }
+
+    /// Create a new world with the given terrain, row by row
+    pub fn with_terrain(width: usize, height: usize, terrain: Vec<Terrain>) -> Self {
+        assert_eq!(
+            terrain.len(),
+            width * height,
+            "terrain must cover the world"
+        );
         let grid = vec![vec![Tile::Empty; width]; height];
         World {
             width,
             height,
             grid,
+            terrain,
+            growth: 1.0,
+            growth_credit: 0.0,
+            topology: WorldTopology::default(),
         }
     }
 
     /// Initialize world with plants and food scattered randomly
-    /// plant_density: percentage of tiles that are plants (e.g., 0.05 = 5%)
+    /// plant_density: percentage of grassland tiles that are plants (e.g., 0.05 = 5%),
+    /// scaled on other terrain by its fertility
     /// food_density: percentage of tiles that are consumable food (e.g., 0.02 = 2%)
     pub fn initialize_resources<R: Rng>(
         &mut self,
@@ -44,30 +77,31 @@ impl World {
         plant_density: f64,
         food_density: f64,
     ) {
-        let total_tiles = self.width * self.height;
-        let num_plants = (total_tiles as f64 * plant_density) as usize;
-        let num_food = (total_tiles as f64 * food_density) as usize;
-
         // Place plants
-        for _ in 0..num_plants {
-            let x = rng.gen_range(0..self.width);
-            let y = rng.gen_range(0..self.height);
-
-            if matches!(self.grid[y][x], Tile::Empty) {
-                self.grid[y][x] = Tile::Plant {
-                    current_food: 10,
-                    max_food: 10,
-                    regrowth_timer: 0,
-                };
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let chance = plant_density * self.terrain_at(x, y).fertility();
+                if rng.gen::<f64>() < chance {
+                    self.grid[y][x] = Tile::Plant {
+                        current_food: 10,
+                        max_food: 10,
+                        regrowth_timer: 0,
+                    };
+                }
             }
         }
 
-        // Place consumable food
-        for _ in 0..num_food {
+        self.scatter_food(rng, food_density);
+    }
+
+    /// Place consumable food on empty land, about `density` of the tiles
+    pub fn scatter_food<R: Rng>(&mut self, rng: &mut R, density: f64) {
+        let num_food = (self.width * self.height) as f64 * density;
+        for _ in 0..num_food as usize {
             let x = rng.gen_range(0..self.width);
             let y = rng.gen_range(0..self.height);
 
-            if matches!(self.grid[y][x], Tile::Empty) {
+            if matches!(self.grid[y][x], Tile::Empty) && self.terrain_at(x, y).is_passable() {
                 self.grid[y][x] = Tile::Food {
                     amount: rng.gen_range(5..=15),
                 };
@@ -93,6 +127,67 @@ impl World {
         }
     }
 
+    /// Terrain of a tile (grassland if out of bounds)
+    pub fn terrain_at(&self, x: usize, y: usize) -> Terrain {
+        if x < self.width && y < self.height {
+            self.terrain[y * self.width + x]
+        } else {
+            Terrain::default()
+        }
+    }
+
+    pub fn topology(&self) -> WorldTopology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: WorldTopology) {
+        self.topology = topology;
+    }
+
+    /// Position a step of (dx, dy) away, wrapping or bouncing off the edges
+    /// as the topology has it; None if that is off a bounded edge
+    pub fn step(&self, x: usize, y: usize, dx: i64, dy: i64) -> Option<(usize, usize)> {
+        Some((
+            self.topology.step(x, dx, self.width)?,
+            self.topology.step(y, dy, self.height)?,
+        ))
+    }
+
+    /// Offset from one position to another, the short way round on a torus
+    pub fn offset(&self, from: (usize, usize), to: (usize, usize)) -> (i64, i64) {
+        (
+            self.topology.offset(from.0, to.0, self.width),
+            self.topology.offset(from.1, to.1, self.height),
+        )
+    }
+
+    /// Distance between two positions, the short way round on a torus
+    pub fn distance(&self, from: (usize, usize), to: (usize, usize)) -> f64 {
+        let (dx, dy) = self.offset(from, to);
+        (dx as f64).hypot(dy as f64)
+    }
+
+    /// Whether the world is grassland everywhere
+    pub fn is_flat(&self) -> bool {
+        self.terrain.iter().all(|t| *t == Terrain::Grassland)
+    }
+
+    /// A random tile creatures can stand on, if there is any
+    pub fn random_passable_position<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        // Sample first, since most worlds are mostly land
+        for _ in 0..100 {
+            let x = rng.gen_range(0..self.width);
+            let y = rng.gen_range(0..self.height);
+            if self.terrain_at(x, y).is_passable() {
+                return Some((x, y));
+            }
+        }
+        let land: Vec<usize> = (0..self.terrain.len())
+            .filter(|&i| self.terrain[i].is_passable())
+            .collect();
+        land.choose(rng).map(|&i| (i % self.width, i / self.width))
+    }
+
     /// Check if position is valid
     pub fn is_valid_position(&self, x: usize, y: usize) -> bool {
         x < self.width && y < self.height
@@ -108,9 +203,60 @@ impl World {
         }
     }
 
-    /// Consume food from a tile
+    /// Meat left on a tile by dead creatures (0 if none)
+    pub fn get_available_meat(&self, x: usize, y: usize) -> u32 {
+        match self.get_tile(x, y) {
+            Some(Tile::Carcass { meat, .. }) => *meat,
+            _ => 0,
+        }
+    }
+
+    /// Leave a dead creature's meat on a tile. It joins any carcass already
+    /// there; a tile with plants or food passes it on to the nearest tile
+    /// that can take it.
+    pub fn add_carcass(&mut self, x: usize, y: usize, amount: u32) {
+        let Some((x, y)) = self.carcass_position(x, y) else {
+            return;
+        };
+        match self.get_tile_mut(x, y) {
+            Some(tile @ Tile::Empty) => {
+                *tile = Tile::Carcass {
+                    meat: amount,
+                    decay_timer: CARCASS_DECAY_TICKS,
+                }
+            }
+            Some(Tile::Carcass { meat, .. }) => *meat += amount,
+            _ => {}
+        }
+    }
+
+    /// The passable tile nearest (x, y), in rings of growing radius, that is
+    /// empty or already holds a carcass
+    fn carcass_position(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        let takes_meat = |(x, y): (usize, usize)| {
+            self.terrain_at(x, y).is_passable()
+                && matches!(
+                    self.get_tile(x, y),
+                    Some(Tile::Empty | Tile::Carcass { .. })
+                )
+        };
+        if takes_meat((x, y)) {
+            return Some((x, y));
+        }
+        let max_radius = self.width.max(self.height) as i64;
+        (1..=max_radius).find_map(|radius| {
+            (-radius..=radius)
+                .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+                .filter(|(dx, dy)| dx.abs().max(dy.abs()) == radius)
+                .filter_map(|(dx, dy)| self.step(x, y, dx, dy))
+                .find(|&position| takes_meat(position))
+        })
+    }
+
+    /// Consume food or meat from a tile
     /// Returns the amount actually consumed
     pub fn consume_food(&mut self, x: usize, y: usize, amount_requested: u32) -> u32 {
+        let regrowth_ticks = self.terrain_at(x, y).regrowth_ticks();
         if let Some(tile) = self.get_tile_mut(x, y) {
             match tile {
                 Tile::Plant {
@@ -122,11 +268,11 @@ impl World {
                     *current_food -= consumed;
                     // Start regrowth timer when depleted
                     if *current_food == 0 {
-                        *regrowth_timer = 10; // Takes 10 ticks to regrow 1 food
+                        *regrowth_timer = regrowth_ticks; // Ticks to regrow 1 food
                     }
                     consumed
                 }
-                Tile::Food { amount } => {
+                Tile::Food { amount } | Tile::Carcass { meat: amount, .. } => {
                     let consumed = (*amount).min(amount_requested);
                     *amount -= consumed;
                     // Remove tile if depleted
@@ -142,31 +288,56 @@ impl World {
         }
     }
 
-    /// Update all plants - regrow food over time
+    /// Set the rate plants regrow at, relative to their terrain's usual rate
+    pub fn set_growth(&mut self, growth: f64) {
+        self.growth = growth.max(0.0);
+    }
+
+    /// Update all plants - regrow food over time - and let carcasses rot
     pub fn tick_plants(&mut self) {
+        // Regrowth passes this tick: fewer than one per tick when growth is
+        // slow, several when it is fast
+        self.growth_credit += self.growth;
+        let passes = self.growth_credit.floor();
+        self.growth_credit -= passes;
+
         for y in 0..self.height {
             for x in 0..self.width {
-                if let Tile::Plant {
+                let regrowth_ticks = self.terrain[y * self.width + x].regrowth_ticks();
+                let tile = &mut self.grid[y][x];
+                if let Tile::Carcass { meat, decay_timer } = tile {
+                    *decay_timer -= 1;
+                    if *decay_timer == 0 {
+                        *meat = meat.saturating_sub(1);
+                        *decay_timer = CARCASS_DECAY_TICKS;
+                    }
+                    if *meat == 0 {
+                        *tile = Tile::Empty;
+                    }
+                } else if let Tile::Plant {
                     current_food,
                     max_food,
                     regrowth_timer,
-                } = &mut self.grid[y][x]
+                } = tile
                 {
-                    if *current_food < *max_food {
+                    for _ in 0..passes as u32 {
+                        if *current_food >= *max_food {
+                            break;
+                        }
                         if *regrowth_timer > 0 {
                             // Count down and regrow exactly when hitting zero
                             *regrowth_timer -= 1;
                             if *regrowth_timer == 0 {
                                 *current_food += 1;
                                 if *current_food < *max_food {
-                                    *regrowth_timer = 10;
+                                    *regrowth_timer = regrowth_ticks;
                                 }
                             }
                         } else {
                             // Immediate regrowth path when timer already zero
                             *current_food += 1;
                             if *current_food < *max_food {
-                                *regrowth_timer = 10;
+                                *regrowth_timer = regrowth_ticks;
                             }
                         }
                     }
@@ -182,23 +353,40 @@ impl World {
         center_y: usize,
         radius: f64,
     ) -> Vec<(usize, usize, u32)> {
-        let mut food_positions = Vec::new();
-        let radius_squared = radius * radius;
+        self.find_in_radius(center_x, center_y, radius, |x, y| {
+            self.get_available_food(x, y)
+        })
+    }
+
+    /// Find all carcass positions within a radius of a point
+    pub fn find_meat_in_radius(
+        &self,
+        center_x: usize,
+        center_y: usize,
+        radius: f64,
+    ) -> Vec<(usize, usize, u32)> {
+        self.find_in_radius(center_x, center_y, radius, |x, y| {
+            self.get_available_meat(x, y)
+        })
+    }
 
-        let min_x = center_x.saturating_sub(radius.ceil() as usize);
-        let max_x = (center_x + radius.ceil() as usize).min(self.width - 1);
-        let min_y = center_y.saturating_sub(radius.ceil() as usize);
-        let max_y = (center_y + radius.ceil() as usize).min(self.height - 1);
+    /// Positions within a radius of a point where `amount` is positive
+    fn find_in_radius(
+        &self,
+        center_x: usize,
+        center_y: usize,
+        radius: f64,
+        amount: impl Fn(usize, usize) -> u32,
+    ) -> Vec<(usize, usize, u32)> {
+        let mut food_positions = Vec::new();
+        let reach = radius.ceil() as usize;
+        let columns = self.topology.range(center_x, reach, self.width);
 
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
+        for y in self.topology.range(center_y, reach, self.height) {
+            for &x in &columns {
                 // Check if within radius
-                let dx = x as f64 - center_x as f64;
-                let dy = y as f64 - center_y as f64;
-                let dist_squared = dx * dx + dy * dy;
-
-                if dist_squared <= radius_squared {
-                    let food = self.get_available_food(x, y);
+                if self.distance((center_x, center_y), (x, y)) <= radius {
+                    let food = amount(x, y);
                     if food > 0 {
                         food_positions.push((x, y, food));
                     }
@@ -291,6 +479,124 @@ mod tests {
         assert_eq!(world.get_available_food(5, 5), 2);
     }
 
+    #[test]
+    fn test_terrain() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let terrain = (0..100 * 100)
+            .map(|i| match i % 4 {
+                0 => Terrain::Water,
+                1 => Terrain::Fertile,
+                _ => Terrain::Grassland,
+            })
+            .collect();
+        let mut world = World::with_terrain(100, 100, terrain);
+        assert!(!world.is_flat());
+        world.initialize_resources(&mut rng, 0.2, 0.1);
+
+        let count = |terrain: Terrain| {
+            (0..100 * 100)
+                .filter(|i| world.terrain_at(i % 100, i / 100) == terrain)
+                .filter(|i| !matches!(world.get_tile(i % 100, i / 100), Some(Tile::Empty)))
+                .count()
+        };
+        assert_eq!(count(Terrain::Water), 0);
+        // Fertile soil has a quarter of the tiles but more plants than half of them
+        assert!(count(Terrain::Fertile) > count(Terrain::Grassland));
+
+        for _ in 0..20 {
+            let (x, y) = world.random_passable_position(&mut rng).unwrap();
+            assert!(world.terrain_at(x, y).is_passable());
+        }
+
+        // Plants on fertile soil regrow twice as fast
+        world.grid[0][1] = Tile::Plant {
+            current_food: 10,
+            max_food: 10,
+            regrowth_timer: 0,
+        };
+        world.consume_food(1, 0, 10);
+        for _ in 0..Terrain::Fertile.regrowth_ticks() {
+            world.tick_plants();
+        }
+        assert_eq!(world.get_available_food(1, 0), 1);
+    }
+
+    #[test]
+    fn test_carcasses() {
+        let mut world = World::new(10, 10);
+        world.add_carcass(5, 5, 2);
+        world.add_carcass(5, 5, 1);
+        assert_eq!(world.get_available_meat(5, 5), 3);
+        assert_eq!(world.get_available_food(5, 5), 0);
+        assert_eq!(world.find_meat_in_radius(5, 5, 2.0), vec![(5, 5, 3)]);
+
+        // Meat dropped on food or plants lands on the nearest free tile
+        world.grid[2][2] = Tile::Food { amount: 4 };
+        world.add_carcass(2, 2, 5);
+        assert_eq!(world.get_available_food(2, 2), 4);
+        assert_eq!(world.get_available_meat(2, 2), 0);
+        let spilled = world.find_meat_in_radius(2, 2, 1.5);
+        assert_eq!(spilled.len(), 1);
+        assert_eq!(spilled[0].2, 5);
+
+        // Meat dropped on a plant moves next to it
+        let mut crowded = World::new(2, 1);
+        crowded.grid[0][0] = Tile::Plant {
+            current_food: 1,
+            max_food: 1,
+            regrowth_timer: 0,
+        };
+        crowded.add_carcass(0, 0, 3);
+        assert_eq!(crowded.get_available_meat(1, 0), 3);
+
+        assert_eq!(world.consume_food(5, 5, 1), 1);
+        assert_eq!(world.get_available_meat(5, 5), 2);
+
+        // Meat rots away, leaving the ground empty
+        for _ in 0..2 * CARCASS_DECAY_TICKS {
+            world.tick_plants();
+        }
+        assert!(matches!(world.get_tile(5, 5), Some(Tile::Empty)));
+    }
+
+    #[test]
+    fn test_growth_rate() {
+        let mut world = World::new(10, 10);
+        world.grid[5][5] = Tile::Plant {
+            current_food: 10,
+            max_food: 10,
+            regrowth_timer: 0,
+        };
+        world.consume_food(5, 5, 10);
+
+        // Half speed: 20 ticks per food
+        world.set_growth(0.5);
+        for _ in 0..19 {
+            world.tick_plants();
+        }
+        assert_eq!(world.get_available_food(5, 5), 0);
+        world.tick_plants();
+        assert_eq!(world.get_available_food(5, 5), 1);
+
+        // Triple speed: 3 food in the next 10 ticks
+        world.set_growth(3.0);
+        for _ in 0..10 {
+            world.tick_plants();
+        }
+        assert_eq!(world.get_available_food(5, 5), 4);
+
+        // No growth at all
+        world.set_growth(0.0);
+        for _ in 0..100 {
+            world.tick_plants();
+        }
+        assert_eq!(world.get_available_food(5, 5), 4);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        world.scatter_food(&mut rng, 0.5);
+        assert!(world.total_food() > 4);
+    }
+
     #[test]
     fn test_find_food_in_radius() {
         let mut world = World::new(20, 20);
@@ -304,4 +610,23 @@ mod tests {
         let food = world.find_food_in_radius(10, 10, 10.0);
         assert_eq!(food.len(), 3); // Should find all three
     }
+
+    #[test]
+    fn test_torus() {
+        let mut world = World::new(20, 10);
+        world.grid[9][19] = Tile::Food { amount: 5 };
+        assert!(world.find_food_in_radius(0, 0, 2.0).is_empty());
+        assert_eq!(world.step(19, 9, 1, 1), None);
+
+        world.set_topology(WorldTopology::Torus);
+        assert_eq!(world.find_food_in_radius(0, 0, 2.0), vec![(19, 9, 5)]);
+        assert_eq!(world.find_food_in_radius(0, 0, 50.0).len(), 1);
+        assert_eq!(world.step(19, 9, 1, 1), Some((0, 0)));
+        assert_eq!(world.offset((0, 0), (19, 9)), (-1, -1));
+        assert!((world.distance((0, 0), (19, 9)) - 2f64.sqrt()).abs() < 1e-9);
+
+        world.set_topology(WorldTopology::Walled);
+        assert_eq!(world.step(19, 9, 1, 1), Some((18, 8)));
+        assert_eq!(world.offset((0, 0), (19, 9)), (19, 9));
+    }
 }
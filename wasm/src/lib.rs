@@ -0,0 +1,168 @@
+//! Browser bindings for the island simulation, so the dashboard runs the same
+//! `Island` and `World` code as the clients. Build with
+//! `wasm-pack build wasm --target web --out-dir ../server/static/wasm`.
+
+use shared::{SurvivalResult, WorkAssignment};
+use sim::{Island, IslandConfig};
+use uuid::Uuid;
+use wasm_bindgen::prelude::*;
+
+/// Values per creature in [`WasmIsland::creatures`]
+pub const CREATURE_STRIDE: usize = 6;
+
+/// An island simulation driven step by step from JavaScript
+#[wasm_bindgen]
+pub struct WasmIsland {
+    island: Island,
+}
+
+#[wasm_bindgen]
+impl WasmIsland {
+    /// Build the island for a work assignment, as returned by `/api/work/request`
+    #[wasm_bindgen(constructor)]
+    pub fn new(assignment_json: &str) -> Result<WasmIsland, JsError> {
+        let assignment: WorkAssignment = serde_json::from_str(assignment_json)?;
+        Ok(Self::from_assignment(assignment))
+    }
+
+    /// Advance up to `steps` steps. Returns true once the simulation is finished.
+    pub fn tick(&mut self, steps: u32) -> bool {
+        let mut rng = rand::thread_rng();
+        for _ in 0..steps {
+            if self.island.is_finished() {
+                break;
+            }
+            self.island.tick(&mut rng);
+        }
+        self.island.is_finished()
+    }
+
+    pub fn step(&self) -> u32 {
+        self.island.step
+    }
+
+    #[wasm_bindgen(js_name = maxSteps)]
+    pub fn max_steps(&self) -> u32 {
+        self.island.config.max_steps
+    }
+
+    pub fn width(&self) -> usize {
+        self.island.world.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.island.world.height
+    }
+
+    #[wasm_bindgen(js_name = isFinished)]
+    pub fn is_finished(&self) -> bool {
+        self.island.is_finished()
+    }
+
+    #[wasm_bindgen(js_name = creatureCount)]
+    pub fn creature_count(&self) -> usize {
+        self.island.creatures.len()
+    }
+
+    /// Living creatures as `[x, y, lineage, strength, size, efficiency]` per creature,
+    /// where `lineage` indexes [`WasmIsland::lineages`]
+    pub fn creatures(&self) -> Vec<f32> {
+        let mut out = Vec::with_capacity(self.island.creatures.len() * CREATURE_STRIDE);
+        let lineages = self.island.lineages();
+        for creature in &self.island.creatures {
+            let lineage = lineages
+                .iter()
+                .position(|id| *id == creature.genome_id)
+                .unwrap_or(lineages.len());
+            out.extend_from_slice(&[
+                creature.x as f32,
+                creature.y as f32,
+                lineage as f32,
+                creature.genome.strength as f32,
+                creature.genome.size as f32,
+                creature.genome.efficiency as f32,
+            ]);
+        }
+        out
+    }
+
+    /// Seed genome IDs, in the order used by the creature lineage index
+    pub fn lineages(&self) -> Vec<String> {
+        self.island.lineages().iter().map(Uuid::to_string).collect()
+    }
+
+    /// Food available on every tile, row by row
+    pub fn food(&self) -> Vec<u32> {
+        let world = &self.island.world;
+        let mut out = Vec::with_capacity(world.width * world.height);
+        for y in 0..world.height {
+            for x in 0..world.width {
+                out.push(world.get_available_food(x, y));
+            }
+        }
+        out
+    }
+
+    /// Survival results so far, as the JSON a client would submit
+    #[wasm_bindgen(js_name = survivalResults)]
+    pub fn survival_results(&self) -> String {
+        let results: Vec<SurvivalResult> = self
+            .island
+            .collect_survival_stats()
+            .into_iter()
+            .map(|s| SurvivalResult {
+                genome_id: s.genome_id,
+                survived: s.survived,
+                total_spawned: s.total_spawned,
+                total_food_eaten: s.total_food_eaten,
+            })
+            .collect();
+        serde_json::to_string(&results).unwrap_or_default()
+    }
+}
+
+impl WasmIsland {
+    pub fn from_assignment(assignment: WorkAssignment) -> Self {
+        let config = IslandConfig::from_assignment(&assignment);
+        let seeds = assignment
+            .seed_genomes_v2
+            .into_iter()
+            .map(|g| (g.genome_id, g.genome))
+            .collect();
+
+        Self {
+            island: Island::new(config, seeds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{Genome, GenomeWithId};
+
+    #[test]
+    fn test_island_from_assignment() {
+        let seeds: Vec<GenomeWithId> = (0..3)
+            .map(|_| GenomeWithId {
+                genome_id: Uuid::new_v4(),
+                genome: Genome::random(),
+            })
+            .collect();
+        let assignment = WorkAssignment::new_spatial(seeds, 20, 10, 5, 0.05);
+        let mut island = WasmIsland::from_assignment(assignment);
+
+        assert_eq!((island.width(), island.height()), (20, 10));
+        assert_eq!(island.food().len(), 200);
+        assert_eq!(island.lineages().len(), 3);
+        let creatures = island.creatures();
+        assert_eq!(creatures.len(), 3 * CREATURE_STRIDE);
+        assert!(creatures.chunks(CREATURE_STRIDE).all(|c| c[2] < 3.0));
+
+        while !island.tick(2) {}
+        assert!(island.step() <= 5);
+        let results: Vec<SurvivalResult> =
+            serde_json::from_str(&island.survival_results()).unwrap();
+        assert_eq!(results.len(), 3);
+    }
+}